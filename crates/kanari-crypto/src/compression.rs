@@ -16,8 +16,13 @@ pub fn compress_data(data: &[u8]) -> Result<Vec<u8>, io::Error> {
 /// Decompress data that was compressed with zstd
 pub fn decompress_data(data: &[u8]) -> Result<Vec<u8>, io::Error> {
     // 10MB maximum size limit to prevent decompression bombs
-    decompress(data, 10_485_760)
-        .map_err(|e| io::Error::other(format!("Decompression error: {}", e)))
+    decompress_data_with_limit(data, 10_485_760)
+}
+
+/// Decompress zstd data with a caller-chosen maximum output size.
+/// Used for large payloads such as state snapshots that exceed the default limit.
+pub fn decompress_data_with_limit(data: &[u8], max_size: usize) -> Result<Vec<u8>, io::Error> {
+    decompress(data, max_size).map_err(|e| io::Error::other(format!("Decompression error: {}", e)))
 }
 
 #[cfg(test)]
@@ -36,4 +41,16 @@ mod tests {
         // Verify compression actually reduces size
         assert!(compressed.len() < original.len());
     }
+
+    #[test]
+    fn test_decompression_limit() {
+        let original = vec![7u8; 4096];
+        let compressed = compress_data(&original).unwrap();
+
+        assert!(decompress_data_with_limit(&compressed, 1024).is_err());
+        assert_eq!(
+            decompress_data_with_limit(&compressed, 4096).unwrap(),
+            original
+        );
+    }
 }
@@ -105,6 +105,62 @@ impl CurveType {
         )
     }
 
+    /// One-byte identifier of the signature scheme, committed to by authentication keys
+    pub fn scheme_flag(&self) -> u8 {
+        match self {
+            CurveType::Ed25519 => 0x00,
+            CurveType::K256 => 0x01,
+            CurveType::P256 => 0x02,
+            CurveType::Dilithium2 => 0x03,
+            CurveType::Dilithium3 => 0x04,
+            CurveType::Dilithium5 => 0x05,
+            CurveType::SphincsPlusSha256Robust => 0x06,
+            CurveType::Ed25519Dilithium3 => 0x07,
+            CurveType::K256Dilithium3 => 0x08,
+        }
+    }
+
+    /// Inverse of [`CurveType::scheme_flag`]
+    pub fn from_scheme_flag(flag: u8) -> Option<Self> {
+        match flag {
+            0x00 => Some(CurveType::Ed25519),
+            0x01 => Some(CurveType::K256),
+            0x02 => Some(CurveType::P256),
+            0x03 => Some(CurveType::Dilithium2),
+            0x04 => Some(CurveType::Dilithium3),
+            0x05 => Some(CurveType::Dilithium5),
+            0x06 => Some(CurveType::SphincsPlusSha256Robust),
+            0x07 => Some(CurveType::Ed25519Dilithium3),
+            0x08 => Some(CurveType::K256Dilithium3),
+            _ => None,
+        }
+    }
+
+    /// Classical half of a hybrid scheme
+    pub fn classical_component(&self) -> Option<CurveType> {
+        match self {
+            CurveType::Ed25519Dilithium3 => Some(CurveType::Ed25519),
+            CurveType::K256Dilithium3 => Some(CurveType::K256),
+            _ => None,
+        }
+    }
+
+    /// Maximum size in bytes of a signature produced with this scheme. Hybrid
+    /// signatures carry a two-byte length prefix for their classical part.
+    pub fn signature_size(&self) -> usize {
+        match self {
+            CurveType::Ed25519 => 64,
+            // DER-encoded ECDSA signatures
+            CurveType::K256 | CurveType::P256 => 72,
+            CurveType::Dilithium2 => dilithium2::signature_bytes(),
+            CurveType::Dilithium3 => dilithium3::signature_bytes(),
+            CurveType::Dilithium5 => dilithium5::signature_bytes(),
+            CurveType::SphincsPlusSha256Robust => sphincssha2256fsimple::signature_bytes(),
+            CurveType::Ed25519Dilithium3 => 2 + 64 + dilithium3::signature_bytes(),
+            CurveType::K256Dilithium3 => 2 + 72 + dilithium3::signature_bytes(),
+        }
+    }
+
     /// Get security level (1-5, where 5 is highest)
     pub fn security_level(&self) -> u8 {
         match self {
@@ -267,155 +323,387 @@ fn generate_ed25519_keypair() -> Result<KeyPair, KeyError> {
 // POST-QUANTUM CRYPTOGRAPHY (PQC) KEY GENERATION
 // ============================================================================
 
+/// Prefix used for post-quantum private keys
+pub const PQC_KEY_PREFIX: &str = "kanapqc";
+
+/// Prefix used for hybrid private keys
+pub const HYBRID_KEY_PREFIX: &str = "kanahybrid";
+
 /// Generate a Dilithium2 keypair (Fast, NIST Level 2)
 fn generate_dilithium2_keypair() -> Result<KeyPair, KeyError> {
     let (public_key, secret_key) = dilithium2::keypair();
-
-    let public_key_bytes = public_key.as_bytes();
-    let secret_key_bytes = secret_key.as_bytes();
-
-    let hex_encoded = hex::encode(public_key_bytes);
-    let address = format!("0xpqc{}", &hex_encoded[..40]); // PQC address prefix
-    let raw_private_key = hex::encode(secret_key_bytes);
-    let private_key = format!("kanapqc{}", raw_private_key);
-
-    Ok(KeyPair {
-        private_key,
-        public_key: hex_encoded,
-        address,
-        curve_type: CurveType::Dilithium2,
-    })
+    pqc_keypair(
+        CurveType::Dilithium2,
+        secret_key.as_bytes(),
+        public_key.as_bytes(),
+    )
 }
 
 /// Generate a Dilithium3 keypair (Balanced, NIST Level 3, Recommended)
 fn generate_dilithium3_keypair() -> Result<KeyPair, KeyError> {
     let (public_key, secret_key) = dilithium3::keypair();
-
-    let public_key_bytes = public_key.as_bytes();
-    let secret_key_bytes = secret_key.as_bytes();
-
-    let hex_encoded = hex::encode(public_key_bytes);
-    let address = format!("0xpqc{}", &hex_encoded[..40]);
-    let raw_private_key = hex::encode(secret_key_bytes);
-    let private_key = format!("kanapqc{}", raw_private_key);
-
-    Ok(KeyPair {
-        private_key,
-        public_key: hex_encoded,
-        address,
-        curve_type: CurveType::Dilithium3,
-    })
+    pqc_keypair(
+        CurveType::Dilithium3,
+        secret_key.as_bytes(),
+        public_key.as_bytes(),
+    )
 }
 
 /// Generate a Dilithium5 keypair (Maximum security, NIST Level 5)
 fn generate_dilithium5_keypair() -> Result<KeyPair, KeyError> {
     let (public_key, secret_key) = dilithium5::keypair();
-
-    let public_key_bytes = public_key.as_bytes();
-    let secret_key_bytes = secret_key.as_bytes();
-
-    let hex_encoded = hex::encode(public_key_bytes);
-    let address = format!("0xpqc{}", &hex_encoded[..40]);
-    let raw_private_key = hex::encode(secret_key_bytes);
-    let private_key = format!("kanapqc{}", raw_private_key);
-
-    Ok(KeyPair {
-        private_key,
-        public_key: hex_encoded,
-        address,
-        curve_type: CurveType::Dilithium5,
-    })
+    pqc_keypair(
+        CurveType::Dilithium5,
+        secret_key.as_bytes(),
+        public_key.as_bytes(),
+    )
 }
 
 /// Generate a SPHINCS+ keypair (Hash-based, ultra-secure)
 fn generate_sphincs_keypair() -> Result<KeyPair, KeyError> {
     let (public_key, secret_key) = sphincssha2256fsimple::keypair();
+    pqc_keypair(
+        CurveType::SphincsPlusSha256Robust,
+        secret_key.as_bytes(),
+        public_key.as_bytes(),
+    )
+}
 
-    let public_key_bytes = public_key.as_bytes();
-    let secret_key_bytes = secret_key.as_bytes();
+/// Secret and public key sizes in bytes of a post-quantum scheme
+fn pqc_key_sizes(curve_type: CurveType) -> Option<(usize, usize)> {
+    match curve_type {
+        CurveType::Dilithium2 => Some((
+            dilithium2::secret_key_bytes(),
+            dilithium2::public_key_bytes(),
+        )),
+        CurveType::Dilithium3 => Some((
+            dilithium3::secret_key_bytes(),
+            dilithium3::public_key_bytes(),
+        )),
+        CurveType::Dilithium5 => Some((
+            dilithium5::secret_key_bytes(),
+            dilithium5::public_key_bytes(),
+        )),
+        CurveType::SphincsPlusSha256Robust => Some((
+            sphincssha2256fsimple::secret_key_bytes(),
+            sphincssha2256fsimple::public_key_bytes(),
+        )),
+        _ => None,
+    }
+}
 
-    let hex_encoded = hex::encode(public_key_bytes);
-    let address = format!("0xpqc{}", &hex_encoded[..40]);
-    let raw_private_key = hex::encode(secret_key_bytes);
-    let private_key = format!("kanapqc{}", raw_private_key);
+/// Build a post-quantum keypair. The public key is appended to the secret key in
+/// the private key string, so it doesn't have to be recomputed from the secret
+/// key. The address is the account the key controls.
+fn pqc_keypair(
+    curve_type: CurveType,
+    secret_key: &[u8],
+    public_key: &[u8],
+) -> Result<KeyPair, KeyError> {
+    let hex_encoded = hex::encode(public_key);
+    let address = account_address(curve_type, &hex_encoded)?.to_string();
+    let raw_private_key = hex::encode([secret_key, public_key].concat());
 
     Ok(KeyPair {
-        private_key,
+        private_key: format!("{}{}", PQC_KEY_PREFIX, raw_private_key),
         public_key: hex_encoded,
         address,
-        curve_type: CurveType::SphincsPlusSha256Robust,
+        curve_type,
     })
 }
 
+/// Split a post-quantum private key into its secret and public key bytes. Keys
+/// created before the public key was appended hold only the secret key, and
+/// their public key is recomputed from it.
+pub fn split_pqc_private_key(
+    private_key: &str,
+    curve_type: CurveType,
+) -> Result<(Vec<u8>, Vec<u8>), KeyError> {
+    let (secret_len, public_len) = pqc_key_sizes(curve_type).ok_or(KeyError::InvalidPrivateKey)?;
+    let raw = extract_raw_key(private_key);
+    let raw = raw.strip_prefix(PQC_KEY_PREFIX).unwrap_or(raw);
+    let mut bytes = hex::decode(raw).map_err(|_| KeyError::InvalidPrivateKey)?;
+    let public_key = if bytes.len() == secret_len + public_len {
+        bytes.split_off(secret_len)
+    } else if bytes.len() == secret_len {
+        pqc_public_key(curve_type, &bytes).ok_or(KeyError::InvalidPrivateKey)?
+    } else {
+        return Err(KeyError::InvalidPrivateKey);
+    };
+    Ok((bytes, public_key))
+}
+
+/// Public key of a post-quantum secret key. SPHINCS+ secret keys end with the
+/// public key; Dilithium ones are expanded back into it.
+fn pqc_public_key(curve_type: CurveType, secret_key: &[u8]) -> Option<Vec<u8>> {
+    let (secret_len, public_len) = pqc_key_sizes(curve_type)?;
+    match curve_type {
+        CurveType::SphincsPlusSha256Robust => Some(secret_key[secret_len - public_len..].to_vec()),
+        _ => dilithium_public_key(DilithiumParams::of(curve_type)?, secret_key),
+    }
+}
+
+/// Split a hybrid private key into its classical and post-quantum parts
+pub fn split_hybrid_private_key(private_key: &str) -> Result<(&str, &str), KeyError> {
+    let raw = extract_raw_key(private_key);
+    raw.strip_prefix(HYBRID_KEY_PREFIX)
+        .unwrap_or(raw)
+        .split_once(':')
+        .ok_or(KeyError::InvalidPrivateKey)
+}
+
+// ============================================================================
+// DILITHIUM PUBLIC KEY RECOVERY
+// ============================================================================
+
+/// Modulus of the Dilithium polynomial ring Z_q[X]/(X^256 + 1)
+const DILITHIUM_Q: i64 = 8_380_417;
+
+/// Coefficients per Dilithium polynomial
+const DILITHIUM_N: usize = 256;
+
+/// Bytes of the seed `rho` and of the public key hash `tr` in a secret key
+const DILITHIUM_SEED_BYTES: usize = 32;
+const DILITHIUM_TR_BYTES: usize = 64;
+
+type DilithiumPoly = [i64; DILITHIUM_N];
+
+/// Shape of a Dilithium parameter set: `k` rows and `l` columns of the matrix
+/// `A`, and the bound `eta` of the secret vectors' coefficients
+struct DilithiumParams {
+    k: usize,
+    l: usize,
+    eta: i64,
+}
+
+impl DilithiumParams {
+    fn of(curve_type: CurveType) -> Option<Self> {
+        match curve_type {
+            CurveType::Dilithium2 => Some(Self { k: 4, l: 4, eta: 2 }),
+            CurveType::Dilithium3 => Some(Self { k: 6, l: 5, eta: 4 }),
+            CurveType::Dilithium5 => Some(Self { k: 8, l: 7, eta: 2 }),
+            _ => None,
+        }
+    }
+
+    /// Packed size of a polynomial with coefficients in `[-eta, eta]`
+    fn eta_bytes(&self) -> usize {
+        if self.eta == 2 { 96 } else { 128 }
+    }
+}
+
+/// Recompute the public key `rho ‖ t1` of a Dilithium secret key
+/// `rho ‖ key ‖ tr ‖ s1 ‖ s2 ‖ t0`: `t1` is the high part of `A·s1 + s2`, with
+/// `A` expanded from `rho`. Returns `None` unless the result hashes to `tr`.
+fn dilithium_public_key(params: DilithiumParams, secret_key: &[u8]) -> Option<Vec<u8>> {
+    use sha3::Shake256;
+    use sha3::digest::{ExtendableOutput, Update, XofReader};
+
+    let rho = &secret_key[..DILITHIUM_SEED_BYTES];
+    let tr_start = 2 * DILITHIUM_SEED_BYTES;
+    let tr = &secret_key[tr_start..tr_start + DILITHIUM_TR_BYTES];
+    let eta_bytes = params.eta_bytes();
+    let secret_poly = |index: usize| {
+        let start = tr_start + DILITHIUM_TR_BYTES + index * eta_bytes;
+        dilithium_unpack_eta(&secret_key[start..start + eta_bytes], params.eta)
+    };
+
+    let zetas = dilithium_zetas();
+    let s1_hat: Vec<DilithiumPoly> = (0..params.l)
+        .map(|j| {
+            let mut poly = secret_poly(j);
+            dilithium_ntt(&mut poly, &zetas);
+            poly
+        })
+        .collect();
+
+    let mut public_key = rho.to_vec();
+    for i in 0..params.k {
+        // A is sampled in the NTT domain
+        let mut t = [0i64; DILITHIUM_N];
+        for (j, s1) in s1_hat.iter().enumerate() {
+            let a = dilithium_expand(rho, ((i << 8) + j) as u16);
+            for c in 0..DILITHIUM_N {
+                t[c] = (t[c] + a[c] * s1[c]) % DILITHIUM_Q;
+            }
+        }
+        dilithium_inv_ntt(&mut t, &zetas);
+        let s2 = secret_poly(params.l + i);
+        // Power2Round with d = 13 keeps the high bits, rounding to nearest
+        let t1: Vec<i64> = t
+            .iter()
+            .zip(s2.iter())
+            .map(|(t, s2)| ((t + s2).rem_euclid(DILITHIUM_Q) + (1 << 12) - 1) >> 13)
+            .collect();
+        for c in t1.chunks(4) {
+            public_key.extend_from_slice(&[
+                c[0] as u8,
+                ((c[0] >> 8) | (c[1] << 2)) as u8,
+                ((c[1] >> 6) | (c[2] << 4)) as u8,
+                ((c[2] >> 4) | (c[3] << 6)) as u8,
+                (c[3] >> 2) as u8,
+            ]);
+        }
+    }
+
+    let mut hasher = Shake256::default();
+    hasher.update(&public_key);
+    let mut hash = [0u8; DILITHIUM_TR_BYTES];
+    hasher.finalize_xof().read(&mut hash);
+    (hash.as_slice() == tr).then_some(public_key)
+}
+
+/// Coefficients `eta - x` of a polynomial packed with 3 (`eta` = 2) or 4
+/// (`eta` = 4) bits per coefficient
+fn dilithium_unpack_eta(bytes: &[u8], eta: i64) -> DilithiumPoly {
+    let mut poly = [0i64; DILITHIUM_N];
+    if eta == 2 {
+        for (i, chunk) in bytes.chunks(3).enumerate() {
+            let word = chunk[0] as i64 | (chunk[1] as i64) << 8 | (chunk[2] as i64) << 16;
+            for m in 0..8 {
+                poly[8 * i + m] = eta - ((word >> (3 * m)) & 7);
+            }
+        }
+    } else {
+        for (i, byte) in bytes.iter().enumerate() {
+            poly[2 * i] = eta - (byte & 0x0f) as i64;
+            poly[2 * i + 1] = eta - (byte >> 4) as i64;
+        }
+    }
+    poly
+}
+
+/// Entry of `A` at `nonce` (row << 8 | column): coefficients below q taken
+/// 23 bits at a time from SHAKE128(rho ‖ nonce)
+fn dilithium_expand(rho: &[u8], nonce: u16) -> DilithiumPoly {
+    use sha3::Shake128;
+    use sha3::digest::{ExtendableOutput, Update, XofReader};
+
+    let mut hasher = Shake128::default();
+    hasher.update(rho);
+    hasher.update(&nonce.to_le_bytes());
+    let mut reader = hasher.finalize_xof();
+    let mut poly = [0i64; DILITHIUM_N];
+    let mut filled = 0;
+    let mut bytes = [0u8; 3];
+    while filled < DILITHIUM_N {
+        reader.read(&mut bytes);
+        let value =
+            (bytes[0] as i64 | (bytes[1] as i64) << 8 | (bytes[2] as i64) << 16) & 0x7f_ffff;
+        if value < DILITHIUM_Q {
+            poly[filled] = value;
+            filled += 1;
+        }
+    }
+    poly
+}
+
+/// `base^exp mod q`
+fn dilithium_pow(mut base: i64, mut exp: u64) -> i64 {
+    let mut result = 1;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % DILITHIUM_Q;
+        }
+        base = base * base % DILITHIUM_Q;
+        exp >>= 1;
+    }
+    result
+}
+
+/// Powers of the 512th root of unity 1753 in bit-reversed order
+fn dilithium_zetas() -> DilithiumPoly {
+    let mut zetas = [0i64; DILITHIUM_N];
+    for (k, zeta) in zetas.iter_mut().enumerate() {
+        *zeta = dilithium_pow(1753, (k as u8).reverse_bits() as u64);
+    }
+    zetas
+}
+
+/// Forward NTT, in the order of the reference implementation
+fn dilithium_ntt(a: &mut DilithiumPoly, zetas: &DilithiumPoly) {
+    let mut k = 0;
+    let mut len = DILITHIUM_N / 2;
+    while len > 0 {
+        for start in (0..DILITHIUM_N).step_by(2 * len) {
+            k += 1;
+            for j in start..start + len {
+                let t = zetas[k] * a[j + len] % DILITHIUM_Q;
+                a[j + len] = (a[j] - t).rem_euclid(DILITHIUM_Q);
+                a[j] = (a[j] + t) % DILITHIUM_Q;
+            }
+        }
+        len >>= 1;
+    }
+}
+
+/// Inverse of `dilithium_ntt`
+fn dilithium_inv_ntt(a: &mut DilithiumPoly, zetas: &DilithiumPoly) {
+    let mut k = DILITHIUM_N;
+    let mut len = 1;
+    while len < DILITHIUM_N {
+        for start in (0..DILITHIUM_N).step_by(2 * len) {
+            k -= 1;
+            let zeta = DILITHIUM_Q - zetas[k];
+            for j in start..start + len {
+                let t = a[j];
+                a[j] = (t + a[j + len]) % DILITHIUM_Q;
+                a[j + len] = (t - a[j + len]).rem_euclid(DILITHIUM_Q) * zeta % DILITHIUM_Q;
+            }
+        }
+        len <<= 1;
+    }
+    let n_inv = dilithium_pow(DILITHIUM_N as i64, DILITHIUM_Q as u64 - 2);
+    for coefficient in a.iter_mut() {
+        *coefficient = *coefficient * n_inv % DILITHIUM_Q;
+    }
+}
+
 // ============================================================================
 // HYBRID CRYPTOGRAPHY (Classical + PQC)
 // ============================================================================
 
 /// Generate Ed25519 + Dilithium3 hybrid keypair
 fn generate_hybrid_ed25519_dilithium3_keypair() -> Result<KeyPair, KeyError> {
-    // Generate both keypairs
-    let ed25519_pair = generate_ed25519_keypair()?;
-    let dilithium3_pair = generate_dilithium3_keypair()?;
-
-    // Combine public keys
-    let combined_public = format!("{}:{}", ed25519_pair.public_key, dilithium3_pair.public_key);
-
-    // Combine private keys
-    let ed25519_raw = extract_raw_key(&ed25519_pair.private_key);
-    let dilithium3_raw = extract_raw_key(&dilithium3_pair.private_key)
-        .strip_prefix("pqc")
-        .unwrap_or("");
-    let combined_private = format!("kanahybrid{}:{}", ed25519_raw, dilithium3_raw);
-
-    // Use hybrid address prefix - safely take first 20 bytes of hash
-    let pub_bytes = combined_public.as_bytes();
-    let hash_input = if pub_bytes.len() >= 20 {
-        &pub_bytes[..20]
-    } else {
-        pub_bytes
-    };
-    let address = format!("0xhybrid{}", hex::encode(hash_input));
-
-    Ok(KeyPair {
-        private_key: combined_private,
-        public_key: combined_public,
-        address,
-        curve_type: CurveType::Ed25519Dilithium3,
-    })
+    hybrid_keypair(
+        CurveType::Ed25519Dilithium3,
+        generate_ed25519_keypair()?,
+        generate_dilithium3_keypair()?,
+    )
 }
 
 /// Generate K256 + Dilithium3 hybrid keypair
 fn generate_hybrid_k256_dilithium3_keypair() -> Result<KeyPair, KeyError> {
-    // Generate both keypairs
-    let k256_pair = generate_k256_keypair()?;
-    let dilithium3_pair = generate_dilithium3_keypair()?;
-
-    // Combine public keys
-    let combined_public = format!("{}:{}", k256_pair.public_key, dilithium3_pair.public_key);
-
-    // Combine private keys
-    let k256_raw = extract_raw_key(&k256_pair.private_key);
-    let dilithium3_raw = extract_raw_key(&dilithium3_pair.private_key)
-        .strip_prefix("pqc")
-        .ok_or(KeyError::GenerationFailed(
-            "Invalid PQC key format".to_string(),
-        ))?;
-    let combined_private = format!("kanahybrid{}:{}", k256_raw, dilithium3_raw);
-
-    // Use hybrid address prefix - safely take first 20 bytes of hash
-    let pub_bytes = combined_public.as_bytes();
-    let hash_input = if pub_bytes.len() >= 20 {
-        &pub_bytes[..20]
-    } else {
-        pub_bytes
-    };
-    let address = format!("0xhybrid{}", hex::encode(hash_input));
+    hybrid_keypair(
+        CurveType::K256Dilithium3,
+        generate_k256_keypair()?,
+        generate_dilithium3_keypair()?,
+    )
+}
+
+/// Combine a classical and a post-quantum keypair into a hybrid one. Both the
+/// private and public keys are the two parts separated by ':', and the address
+/// is the account the combined key controls.
+fn hybrid_keypair(
+    curve_type: CurveType,
+    classical: KeyPair,
+    pqc: KeyPair,
+) -> Result<KeyPair, KeyError> {
+    let combined_public = format!("{}:{}", classical.public_key, pqc.public_key);
+
+    let classical_raw = extract_raw_key(&classical.private_key);
+    let pqc_raw = pqc
+        .private_key
+        .strip_prefix(PQC_KEY_PREFIX)
+        .ok_or_else(|| KeyError::GenerationFailed("Invalid PQC key format".to_string()))?;
+    let combined_private = format!("{}{}:{}", HYBRID_KEY_PREFIX, classical_raw, pqc_raw);
+    let address = account_address(curve_type, &combined_public)?.to_string();
 
     Ok(KeyPair {
         private_key: combined_private,
         public_key: combined_public,
         address,
-        curve_type: CurveType::K256Dilithium3,
+        curve_type,
     })
 }
 
@@ -518,6 +806,10 @@ pub fn keypair_from_private_key(
     private_key: &str,
     curve_type: CurveType,
 ) -> Result<KeyPair, KeyError> {
+    if curve_type.is_post_quantum() {
+        return pqc_keypair_from_private_key(private_key, curve_type);
+    }
+
     // Remove kanari prefix if present
     let raw_private_key = extract_raw_key(private_key);
 
@@ -609,13 +901,75 @@ pub fn keypair_from_private_key(
                 curve_type: CurveType::Ed25519,
             })
         }
-        // PQC algorithms require importing raw key bytes
-        _ => Err(KeyError::GenerationFailed(
-            "Post-quantum and hybrid algorithms require specialized import methods. Use generate_keypair() instead.".to_string()
-        )),
+        // Post-quantum and hybrid keys are handled above
+        _ => Err(KeyError::InvalidPrivateKey),
     }
 }
 
+/// Rebuild a post-quantum or hybrid keypair from its private key
+fn pqc_keypair_from_private_key(
+    private_key: &str,
+    curve_type: CurveType,
+) -> Result<KeyPair, KeyError> {
+    match curve_type.classical_component() {
+        Some(classical_curve) => {
+            let (classical, pqc) = split_hybrid_private_key(private_key)?;
+            let classical = keypair_from_private_key(classical, classical_curve)?;
+            let (secret_key, public_key) = split_pqc_private_key(pqc, CurveType::Dilithium3)?;
+            hybrid_keypair(
+                curve_type,
+                classical,
+                pqc_keypair(CurveType::Dilithium3, &secret_key, &public_key)?,
+            )
+        }
+        None => {
+            let (secret_key, public_key) = split_pqc_private_key(private_key, curve_type)?;
+            pqc_keypair(curve_type, &secret_key, &public_key)
+        }
+    }
+}
+
+/// Decode a hex public key into the bytes committed to by its authentication key.
+/// Hybrid public keys are the classical and post-quantum keys separated by ':'.
+pub fn public_key_bytes(curve_type: CurveType, public_key: &str) -> Result<Vec<u8>, KeyError> {
+    let public_key = public_key.trim_start_matches("0x");
+    let bytes = if curve_type.is_hybrid() {
+        let (classical, pqc) = public_key
+            .split_once(':')
+            .ok_or(KeyError::InvalidPublicKey)?;
+        let mut bytes = hex::decode(classical).map_err(|_| KeyError::InvalidPublicKey)?;
+        bytes.extend(hex::decode(pqc).map_err(|_| KeyError::InvalidPublicKey)?);
+        bytes
+    } else {
+        hex::decode(public_key).map_err(|_| KeyError::InvalidPublicKey)?
+    };
+    Ok(bytes)
+}
+
+/// On-chain account address controlled by a public key. Classical public keys
+/// are the address itself; post-quantum and hybrid keys are too long for that,
+/// so their account lives at their authentication key.
+pub fn account_address(curve_type: CurveType, public_key: &str) -> Result<Address, KeyError> {
+    if !curve_type.is_post_quantum() {
+        return derive_address_from_pubkey(public_key.trim_start_matches("0x"));
+    }
+    let key = authentication_key(curve_type, &public_key_bytes(curve_type, public_key)?);
+    derive_address_from_pubkey(&hex::encode(key))
+}
+
+/// Length in bytes of an authentication key
+pub const AUTHENTICATION_KEY_LENGTH: usize = 32;
+
+/// Authentication key for a public key: BLAKE3 hash of the scheme flag followed by
+/// the public key bytes. Accounts store this on-chain so their key can be rotated
+/// without changing the address.
+pub fn authentication_key(curve_type: CurveType, public_key: &[u8]) -> Vec<u8> {
+    let mut data = Vec::with_capacity(1 + public_key.len());
+    data.push(curve_type.scheme_flag());
+    data.extend_from_slice(public_key);
+    crate::hash_data_blake3(&data)
+}
+
 /// Derive an Address type from a public key
 pub fn derive_address_from_pubkey(public_key: &str) -> Result<Address, KeyError> {
     let address_str = format!("0x{}", public_key);
@@ -747,9 +1101,12 @@ mod tests {
         assert!(result.is_ok(), "Hybrid keypair generation should succeed");
 
         let keypair = result.unwrap();
-        assert!(
-            keypair.address.starts_with("0xhybrid"),
-            "Hybrid address should have correct prefix"
+        assert_eq!(
+            keypair.address,
+            account_address(keypair.curve_type, &keypair.public_key)
+                .unwrap()
+                .to_string(),
+            "Hybrid address should be the account the key controls"
         );
         assert_eq!(keypair.curve_type, CurveType::Ed25519Dilithium3);
     }
@@ -762,9 +1119,12 @@ mod tests {
         // May fail due to PQC prefix handling issue, but should not panic
         if result.is_ok() {
             let keypair = result.unwrap();
-            assert!(
-                keypair.address.starts_with("0xhybrid"),
-                "Hybrid address should have correct prefix"
+            assert_eq!(
+                keypair.address,
+                account_address(keypair.curve_type, &keypair.public_key)
+                    .unwrap()
+                    .to_string(),
+                "Hybrid address should be the account the key controls"
             );
             assert_eq!(keypair.curve_type, CurveType::K256Dilithium3);
         } else {
@@ -964,9 +1324,12 @@ mod tests {
             "PQC keys should have kanapqc prefix, got: {}",
             dil3.private_key
         );
-        assert!(
-            dil3.address.starts_with("0xpqc"),
-            "PQC addresses should have pqc prefix"
+        assert_eq!(
+            dil3.address,
+            account_address(CurveType::Dilithium3, &dil3.public_key)
+                .unwrap()
+                .to_string(),
+            "PQC addresses should be the account the key controls"
         );
 
         // Test that PQC is detected
@@ -983,8 +1346,8 @@ mod tests {
             "Hybrid keys should have kanahybrid prefix"
         );
         assert!(
-            hybrid.address.starts_with("0xhybrid"),
-            "Hybrid addresses should have hybrid prefix"
+            Address::from_str(&hybrid.address).is_ok(),
+            "Hybrid addresses should be account addresses"
         );
 
         // Should contain both key parts separated by ':'
@@ -1027,4 +1390,129 @@ mod tests {
             "PQC should not support mnemonic derivation yet"
         );
     }
+
+    #[test]
+    fn test_authentication_key() {
+        let curves = [
+            CurveType::Ed25519,
+            CurveType::K256,
+            CurveType::P256,
+            CurveType::Dilithium2,
+            CurveType::Dilithium3,
+            CurveType::Dilithium5,
+            CurveType::SphincsPlusSha256Robust,
+            CurveType::Ed25519Dilithium3,
+            CurveType::K256Dilithium3,
+        ];
+        for curve in curves {
+            assert_eq!(
+                CurveType::from_scheme_flag(curve.scheme_flag()),
+                Some(curve)
+            );
+        }
+        assert_eq!(CurveType::from_scheme_flag(0xff), None);
+
+        // The scheme is part of the key, so the same bytes under another scheme differ
+        let public_key = [7u8; 32];
+        let key = authentication_key(CurveType::Ed25519, &public_key);
+        assert_eq!(key.len(), 32);
+        assert_eq!(key, authentication_key(CurveType::Ed25519, &public_key));
+        assert_ne!(key, authentication_key(CurveType::K256, &public_key));
+    }
+
+    #[test]
+    fn test_pqc_keypair_from_private_key() {
+        for curve in [CurveType::Dilithium3, CurveType::K256Dilithium3] {
+            let original = generate_keypair(curve).unwrap();
+            // Wallets store keys with the kanari prefix prepended
+            let stored = format_private_key(&original.private_key);
+            let recreated = keypair_from_private_key(&stored, curve).unwrap();
+            assert_eq!(original.public_key, recreated.public_key);
+            assert_eq!(original.address, recreated.address);
+
+            // Post-quantum accounts live at their authentication key
+            let address = account_address(curve, &original.public_key).unwrap();
+            let key = authentication_key(
+                curve,
+                &public_key_bytes(curve, &original.public_key).unwrap(),
+            );
+            assert_eq!(address.to_vec(), key);
+            assert_eq!(address.to_string(), original.address);
+        }
+
+        let ed25519 = generate_keypair(CurveType::Ed25519).unwrap();
+        assert_eq!(
+            account_address(CurveType::Ed25519, &ed25519.public_key)
+                .unwrap()
+                .to_string(),
+            ed25519.address
+        );
+    }
+
+    #[test]
+    fn test_legacy_pqc_private_keys() {
+        let message = b"legacy";
+        let cases: [(CurveType, Vec<u8>, Vec<u8>); 4] = [
+            {
+                let (pk, sk) = dilithium2::keypair();
+                let (pk, sk) = (pk.as_bytes().to_vec(), sk.as_bytes().to_vec());
+                (CurveType::Dilithium2, pk, sk)
+            },
+            {
+                let (pk, sk) = dilithium3::keypair();
+                let (pk, sk) = (pk.as_bytes().to_vec(), sk.as_bytes().to_vec());
+                (CurveType::Dilithium3, pk, sk)
+            },
+            {
+                let (pk, sk) = dilithium5::keypair();
+                let (pk, sk) = (pk.as_bytes().to_vec(), sk.as_bytes().to_vec());
+                (CurveType::Dilithium5, pk, sk)
+            },
+            {
+                let (pk, sk) = sphincssha2256fsimple::keypair();
+                let (pk, sk) = (pk.as_bytes().to_vec(), sk.as_bytes().to_vec());
+                (CurveType::SphincsPlusSha256Robust, pk, sk)
+            },
+        ];
+        for (curve, public_key, secret_key) in cases {
+            // Keys created before the public key was appended hold the secret only
+            let legacy =
+                format_private_key(&format!("{}{}", PQC_KEY_PREFIX, hex::encode(&secret_key)));
+            let keypair = keypair_from_private_key(&legacy, curve).unwrap();
+            assert_eq!(keypair.public_key, hex::encode(&public_key), "{curve}");
+
+            let signature = crate::sign_message(&legacy, message, curve).unwrap();
+            assert!(
+                crate::verify_signature_with_curve(&keypair.public_key, message, &signature, curve)
+                    .unwrap()
+            );
+        }
+
+        // Old hybrid keys embed a legacy Dilithium3 secret key
+        let classical = generate_keypair(CurveType::Ed25519).unwrap();
+        let (public_key, secret_key) = dilithium3::keypair();
+        let legacy = format!(
+            "{}{}:{}",
+            HYBRID_KEY_PREFIX,
+            extract_raw_key(&classical.private_key),
+            hex::encode(secret_key.as_bytes())
+        );
+        let keypair = keypair_from_private_key(&legacy, CurveType::Ed25519Dilithium3).unwrap();
+        assert_eq!(
+            keypair.public_key,
+            format!(
+                "{}:{}",
+                classical.public_key,
+                hex::encode(public_key.as_bytes())
+            )
+        );
+
+        // A secret key that doesn't match its public key hash is rejected
+        let (_, secret_key) = dilithium3::keypair();
+        let mut tampered = secret_key.as_bytes().to_vec();
+        let last = tampered.len() - 1;
+        tampered[200] ^= 1;
+        tampered[last] ^= 1;
+        assert!(split_pqc_private_key(&hex::encode(tampered), CurveType::Dilithium3).is_err());
+    }
 }
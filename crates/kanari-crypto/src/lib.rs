@@ -46,7 +46,7 @@ pub use wallet::{
 pub use keystore::{Keystore, get_keystore_path, keystore_exists};
 
 // Re-export compression functionality
-pub use compression::{compress_data, decompress_data};
+pub use compression::{compress_data, decompress_data, decompress_data_with_limit};
 
 // Timestamp utilities
 use std::time::{SystemTime, UNIX_EPOCH};
@@ -24,7 +24,13 @@ use ed25519_dalek::{
     VerifyingKey as Ed25519VerifyingKey,
 };
 
-use crate::keys::CurveType;
+use pqcrypto_dilithium::{dilithium2, dilithium3, dilithium5};
+use pqcrypto_sphincsplus::sphincssha2256fsimple;
+use pqcrypto_traits::sign::{
+    DetachedSignature as PqcDetachedSignature, PublicKey as PqcPublicKey, SecretKey as PqcSecretKey,
+};
+
+use crate::keys::{CurveType, split_hybrid_private_key, split_pqc_private_key};
 
 /// Digital signature errors
 #[derive(Error, Debug)]
@@ -73,13 +79,79 @@ pub fn sign_message(
         CurveType::K256 => sign_message_k256(raw_key, message),
         CurveType::P256 => sign_message_p256(raw_key, message),
         CurveType::Ed25519 => sign_message_ed25519(raw_key, message),
-        // PQC and hybrid schemes need specialized handling
-        _ => Err(SignatureError::InvalidPrivateKey(
-            "Post-quantum and hybrid signatures require use of PQC-specific functions".to_string(),
-        )),
+        CurveType::Ed25519Dilithium3 | CurveType::K256Dilithium3 => {
+            sign_message_hybrid(raw_key, message, curve_type)
+        }
+        _ => sign_message_pqc(raw_key, message, curve_type),
     }
 }
 
+/// Sign a message with a post-quantum (Dilithium or SPHINCS+) private key.
+/// Returns the detached signature.
+fn sign_message_pqc(
+    private_key: &str,
+    message: &[u8],
+    curve_type: CurveType,
+) -> Result<Vec<u8>, SignatureError> {
+    let (secret_key, _) = split_pqc_private_key(private_key, curve_type)
+        .map_err(|e| SignatureError::InvalidPrivateKey(e.to_string()))?;
+    let invalid_key = |_| SignatureError::InvalidPrivateKey("Invalid PQC secret key".to_string());
+
+    let signature = match curve_type {
+        CurveType::Dilithium2 => {
+            let sk = dilithium2::SecretKey::from_bytes(&secret_key).map_err(invalid_key)?;
+            dilithium2::detached_sign(message, &sk).as_bytes().to_vec()
+        }
+        CurveType::Dilithium3 => {
+            let sk = dilithium3::SecretKey::from_bytes(&secret_key).map_err(invalid_key)?;
+            dilithium3::detached_sign(message, &sk).as_bytes().to_vec()
+        }
+        CurveType::Dilithium5 => {
+            let sk = dilithium5::SecretKey::from_bytes(&secret_key).map_err(invalid_key)?;
+            dilithium5::detached_sign(message, &sk).as_bytes().to_vec()
+        }
+        CurveType::SphincsPlusSha256Robust => {
+            let sk =
+                sphincssha2256fsimple::SecretKey::from_bytes(&secret_key).map_err(invalid_key)?;
+            sphincssha2256fsimple::detached_sign(message, &sk)
+                .as_bytes()
+                .to_vec()
+        }
+        _ => {
+            return Err(SignatureError::InvalidPrivateKey(format!(
+                "{} is not a post-quantum scheme",
+                curve_type
+            )));
+        }
+    };
+    Ok(signature)
+}
+
+/// Sign a message with both halves of a hybrid private key.
+///
+/// The signature is the classical signature's length as a big-endian `u16`,
+/// the classical signature, then the Dilithium3 signature.
+fn sign_message_hybrid(
+    private_key: &str,
+    message: &[u8],
+    curve_type: CurveType,
+) -> Result<Vec<u8>, SignatureError> {
+    let (classical_key, pqc_key) = split_hybrid_private_key(private_key)
+        .map_err(|e| SignatureError::InvalidPrivateKey(e.to_string()))?;
+    let classical_curve = curve_type.classical_component().ok_or_else(|| {
+        SignatureError::InvalidPrivateKey(format!("{} is not a hybrid scheme", curve_type))
+    })?;
+
+    let classical = sign_message(classical_key, message, classical_curve)?;
+    let pqc = sign_message_pqc(pqc_key, message, CurveType::Dilithium3)?;
+
+    let mut signature = Vec::with_capacity(2 + classical.len() + pqc.len());
+    signature.extend_from_slice(&(classical.len() as u16).to_be_bytes());
+    signature.extend(classical);
+    signature.extend(pqc);
+    Ok(signature)
+}
+
 /// Sign a message using K256 (secp256k1) private key
 fn sign_message_k256(private_key_hex: &str, message: &[u8]) -> Result<Vec<u8>, SignatureError> {
     // Hash the message with SHA3
@@ -202,12 +274,91 @@ pub fn verify_signature_with_curve(
         CurveType::K256 => verify_signature_k256(address_hex, message, signature),
         CurveType::P256 => verify_signature_p256(address_hex, message, signature),
         CurveType::Ed25519 => verify_signature_ed25519(address_hex, message, signature),
-        // PQC and hybrid schemes need specialized handling
-        _ => Err(SignatureError::InvalidFormat(
-            "Post-quantum and hybrid signature verification requires PQC-specific functions"
-                .to_string(),
-        )),
+        CurveType::Ed25519Dilithium3 | CurveType::K256Dilithium3 => {
+            verify_signature_hybrid(address_hex, message, signature, curve_type)
+        }
+        _ => verify_signature_pqc(address_hex, message, signature, curve_type),
+    }
+}
+
+/// Verify a post-quantum (Dilithium or SPHINCS+) detached signature against a
+/// hex public key
+pub fn verify_signature_pqc(
+    public_key_hex: &str,
+    message: &[u8],
+    signature: &[u8],
+    curve_type: CurveType,
+) -> Result<bool, SignatureError> {
+    let public_key = hex::decode(public_key_hex)
+        .map_err(|e| SignatureError::InvalidPublicKey(format!("Invalid hex public key: {}", e)))?;
+    let invalid_key =
+        |_| SignatureError::InvalidPublicKey(format!("Invalid {} public key", curve_type));
+    let invalid_signature = |_| SignatureError::InvalidSignatureLength;
+
+    let valid = match curve_type {
+        CurveType::Dilithium2 => dilithium2::verify_detached_signature(
+            &dilithium2::DetachedSignature::from_bytes(signature).map_err(invalid_signature)?,
+            message,
+            &dilithium2::PublicKey::from_bytes(&public_key).map_err(invalid_key)?,
+        )
+        .is_ok(),
+        CurveType::Dilithium3 => dilithium3::verify_detached_signature(
+            &dilithium3::DetachedSignature::from_bytes(signature).map_err(invalid_signature)?,
+            message,
+            &dilithium3::PublicKey::from_bytes(&public_key).map_err(invalid_key)?,
+        )
+        .is_ok(),
+        CurveType::Dilithium5 => dilithium5::verify_detached_signature(
+            &dilithium5::DetachedSignature::from_bytes(signature).map_err(invalid_signature)?,
+            message,
+            &dilithium5::PublicKey::from_bytes(&public_key).map_err(invalid_key)?,
+        )
+        .is_ok(),
+        CurveType::SphincsPlusSha256Robust => sphincssha2256fsimple::verify_detached_signature(
+            &sphincssha2256fsimple::DetachedSignature::from_bytes(signature)
+                .map_err(invalid_signature)?,
+            message,
+            &sphincssha2256fsimple::PublicKey::from_bytes(&public_key).map_err(invalid_key)?,
+        )
+        .is_ok(),
+        _ => {
+            return Err(SignatureError::InvalidFormat(format!(
+                "{} is not a post-quantum scheme",
+                curve_type
+            )));
+        }
+    };
+    Ok(valid)
+}
+
+/// Verify a hybrid signature against a `classical:pqc` hex public key. Both the
+/// classical and the Dilithium3 signature must be valid.
+pub fn verify_signature_hybrid(
+    public_key: &str,
+    message: &[u8],
+    signature: &[u8],
+    curve_type: CurveType,
+) -> Result<bool, SignatureError> {
+    let classical_curve = curve_type.classical_component().ok_or_else(|| {
+        SignatureError::InvalidFormat(format!("{} is not a hybrid scheme", curve_type))
+    })?;
+    let (classical_key, pqc_key) = public_key.split_once(':').ok_or_else(|| {
+        SignatureError::InvalidPublicKey("Hybrid public key must be classical:pqc".to_string())
+    })?;
+
+    if signature.len() < 2 {
+        return Err(SignatureError::InvalidSignatureLength);
     }
+    let classical_len = u16::from_be_bytes([signature[0], signature[1]]) as usize;
+    if signature.len() < 2 + classical_len {
+        return Err(SignatureError::InvalidSignatureLength);
+    }
+    let (classical, pqc) = signature[2..].split_at(classical_len);
+
+    Ok(
+        verify_signature_with_curve(classical_key, message, classical, classical_curve)?
+            && verify_signature_pqc(pqc_key, message, pqc, CurveType::Dilithium3)?,
+    )
 }
 
 /// Verify a signature using K256 (secp256k1)
@@ -637,13 +788,48 @@ mod tests {
     }
 
     #[test]
-    fn test_pqc_signing_not_supported_yet() {
-        let keypair = generate_keypair(CurveType::Dilithium3).unwrap();
+    fn test_pqc_and_hybrid_sign_verify() {
         let message = b"test";
+        for curve in [
+            CurveType::Dilithium2,
+            CurveType::Dilithium3,
+            CurveType::Dilithium5,
+            CurveType::SphincsPlusSha256Robust,
+            CurveType::Ed25519Dilithium3,
+            CurveType::K256Dilithium3,
+        ] {
+            let keypair = generate_keypair(curve).unwrap();
+            let signature = sign_message(&keypair.private_key, message, curve).unwrap();
+            assert!(signature.len() <= curve.signature_size());
+            assert!(
+                verify_signature_with_curve(&keypair.public_key, message, &signature, curve)
+                    .unwrap(),
+                "{:?} signature should verify",
+                curve
+            );
+            assert!(
+                !verify_signature_with_curve(&keypair.public_key, b"other", &signature, curve)
+                    .unwrap()
+            );
+        }
 
-        // Should return error for PQC signatures via this API
-        let result = sign_message(&keypair.private_key, message, CurveType::Dilithium3);
-        assert!(result.is_err(), "PQC signing should use specialized API");
+        // A hybrid signature is rejected if either half is invalid
+        let keypair = generate_keypair(CurveType::Ed25519Dilithium3).unwrap();
+        let signature =
+            sign_message(&keypair.private_key, message, CurveType::Ed25519Dilithium3).unwrap();
+        for index in [2, signature.len() - 1] {
+            let mut tampered = signature.clone();
+            tampered[index] ^= 0x01;
+            assert!(
+                !verify_signature_with_curve(
+                    &keypair.public_key,
+                    message,
+                    &tampered,
+                    CurveType::Ed25519Dilithium3
+                )
+                .unwrap()
+            );
+        }
     }
 
     #[test]
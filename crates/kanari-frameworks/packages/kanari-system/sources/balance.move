@@ -1,4 +1,5 @@
 module kanari_system::balance {
+    friend kanari_system::coin;
 
     /// Error codes
     const ERR_INSUFFICIENT_BALANCE: u64 = 1;
@@ -20,8 +21,9 @@ module kanari_system::balance {
         Balance<T> { value: 0 }
     }
 
-    /// Create a new Balance with an initial value
-    public fun create<T>(value: u64): Balance<T> {
+    /// Create a new Balance with an initial value. Only `coin` mints, against
+    /// a `TreasuryCap`, so balances can't be conjured from nothing.
+    public(friend) fun create<T>(value: u64): Balance<T> {
         Balance<T> { value }
     }
 
@@ -31,7 +33,7 @@ module kanari_system::balance {
     }
 
     /// Increase the balance value
-    public fun increase<T>(balance: &mut Balance<T>, amount: u64) {
+    public(friend) fun increase<T>(balance: &mut Balance<T>, amount: u64) {
         let new_value = balance.value + amount;
         // Check for overflow
         assert!(new_value >= balance.value, ERR_OVERFLOW);
@@ -72,7 +74,7 @@ module kanari_system::balance {
     }
 
     /// Increase supply: add `amount` to `s` and return a `Balance` for the newly minted amount.
-    public fun increase_supply<T>(s: &mut Supply<T>, amount: u64): Balance<T> {
+    public(friend) fun increase_supply<T>(s: &mut Supply<T>, amount: u64): Balance<T> {
         // Ensure amount is non-zero for minting
         assert!(amount > 0, ERR_ZERO_AMOUNT);
         
@@ -0,0 +1,97 @@
+/// On-chain chain parameters: the gas schedule, gas limits and minimum gas price.
+/// The node keeps a `ChainParamsStore` under @kanari_system and reads it at every
+/// block. It is written by the node itself, at genesis and when an authorized
+/// governance transaction stages new parameters; staged parameters become
+/// current at the first block of the next epoch.
+module kanari_system::chain_params {
+    use std::option::{Self, Option};
+
+    /// Gas pricing and limits
+    struct GasConfig has store, copy, drop {
+        /// Base fee of the genesis block, in Mist per gas unit
+        base_price: u64,
+        /// Maximum gas units per transaction
+        max_gas_per_tx: u64,
+        /// Maximum gas units per block
+        max_gas_per_block: u64,
+        /// Floor of the base fee, in Mist per gas unit
+        min_gas_price: u64,
+    }
+
+    /// Gas units charged per operation
+    struct GasSchedule has store, copy, drop {
+        transfer: u64,
+        publish_base: u64,
+        publish_per_byte: u64,
+        execute_base: u64,
+        execute_per_complexity: u64,
+        contract_call_base: u64,
+        contract_call_per_name_byte: u64,
+        deployment_base: u64,
+        deployment_per_metadata_byte: u64,
+        contract_query: u64,
+        create_account: u64,
+        update_account: u64,
+        signature_per_byte: u64,
+        storage_per_byte: u64,
+        instruction: u64,
+        call: u64,
+    }
+
+    /// One full set of chain parameters
+    struct ChainParameters has store, copy, drop {
+        /// Number of blocks per epoch
+        epoch_length: u64,
+        gas_config: GasConfig,
+        gas_schedule: GasSchedule,
+    }
+
+    /// Parameters in force and those staged for the next epoch
+    struct ChainParamsStore has store, copy, drop {
+        current: ChainParameters,
+        pending: Option<ChainParameters>,
+    }
+
+    /// Parameters in force for the current epoch
+    public fun current(self: &ChainParamsStore): &ChainParameters {
+        &self.current
+    }
+
+    /// Parameters that take effect at the next epoch, if any were staged
+    public fun pending(self: &ChainParamsStore): &Option<ChainParameters> {
+        &self.pending
+    }
+
+    /// Whether new parameters are waiting for the next epoch
+    public fun has_pending(self: &ChainParamsStore): bool {
+        option::is_some(&self.pending)
+    }
+
+    public fun epoch_length(self: &ChainParameters): u64 {
+        self.epoch_length
+    }
+
+    public fun gas_config(self: &ChainParameters): &GasConfig {
+        &self.gas_config
+    }
+
+    public fun gas_schedule(self: &ChainParameters): &GasSchedule {
+        &self.gas_schedule
+    }
+
+    public fun base_price(self: &GasConfig): u64 {
+        self.base_price
+    }
+
+    public fun max_gas_per_tx(self: &GasConfig): u64 {
+        self.max_gas_per_tx
+    }
+
+    public fun max_gas_per_block(self: &GasConfig): u64 {
+        self.max_gas_per_block
+    }
+
+    public fun min_gas_price(self: &GasConfig): u64 {
+        self.min_gas_price
+    }
+}
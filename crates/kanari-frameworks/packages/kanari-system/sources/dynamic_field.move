@@ -0,0 +1,97 @@
+/// Dynamic fields: values attached to an object's `UID` under a name of any
+/// `copy + drop + store` type. The node keeps each field as a separate storage
+/// entry keyed by the object and the name, so an object can hold any number of
+/// them without growing itself.
+module kanari_system::dynamic_field {
+    use kanari_system::object::{Self, UID};
+
+    /// The object already has a field with this name
+    const EFieldAlreadyExists: u64 = 0;
+    #[allow(unused_const)]
+    /// The object has no field with this name (raised by the natives)
+    const EFieldDoesNotExist: u64 = 1;
+    #[allow(unused_const)]
+    /// The field holds a value of another type (raised by the natives)
+    const EFieldTypeMismatch: u64 = 2;
+
+    /// Storage entry of a field
+    struct Field<Name: copy + drop + store, Value: store> has store {
+        name: Name,
+        value: Value,
+    }
+
+    /// Attach `value` to `object` under `name`.
+    /// Aborts with `EFieldAlreadyExists` if the object already has that field.
+    public fun add<Name: copy + drop + store, Value: store>(
+        object: &mut UID,
+        name: Name,
+        value: Value,
+    ) {
+        let parent = object::uid_address(object);
+        let id = hash_type_and_key(parent, name);
+        assert!(!has_field(parent, id), EFieldAlreadyExists);
+        add_field(parent, id, Field { name, value });
+    }
+
+    /// Borrow the value of field `name`.
+    /// Aborts with `EFieldDoesNotExist` or `EFieldTypeMismatch`.
+    public fun borrow<Name: copy + drop + store, Value: store>(object: &UID, name: Name): &Value {
+        let id = hash_type_and_key(object::uid_address(object), name);
+        let field = borrow_field<Field<Name, Value>>(object, id);
+        &field.value
+    }
+
+    /// Mutably borrow the value of field `name`.
+    /// Aborts with `EFieldDoesNotExist` or `EFieldTypeMismatch`.
+    public fun borrow_mut<Name: copy + drop + store, Value: store>(
+        object: &mut UID,
+        name: Name,
+    ): &mut Value {
+        let id = hash_type_and_key(object::uid_address(object), name);
+        let field = borrow_field_mut<Field<Name, Value>>(object, id);
+        &mut field.value
+    }
+
+    /// Detach field `name` from `object` and return its value.
+    /// Aborts with `EFieldDoesNotExist` or `EFieldTypeMismatch`.
+    public fun remove<Name: copy + drop + store, Value: store>(
+        object: &mut UID,
+        name: Name,
+    ): Value {
+        let parent = object::uid_address(object);
+        let id = hash_type_and_key(parent, name);
+        let Field { name: _, value } = remove_field<Field<Name, Value>>(parent, id);
+        value
+    }
+
+    /// Whether `object` has a field `name`, whatever its value type
+    public fun exists_<Name: copy + drop + store>(object: &UID, name: Name): bool {
+        let parent = object::uid_address(object);
+        has_field(parent, hash_type_and_key(parent, name))
+    }
+
+    /// Whether `object` has a field `name` holding a `Value`
+    public fun exists_with_type<Name: copy + drop + store, Value: store>(
+        object: &UID,
+        name: Name,
+    ): bool {
+        let parent = object::uid_address(object);
+        has_field_with_type<Field<Name, Value>>(parent, hash_type_and_key(parent, name))
+    }
+
+    /// Id of the field `k` of `parent`: SHA3-256 of `parent`, the type of `k`
+    /// and BCS(`k`)
+    native fun hash_type_and_key<K: copy + drop + store>(parent: address, k: K): address;
+
+    native fun add_field<Child: store>(parent: address, id: address, child: Child);
+
+    native fun borrow_field<Child: store>(object: &UID, id: address): &Child;
+
+    native fun borrow_field_mut<Child: store>(object: &mut UID, id: address): &mut Child;
+
+    native fun remove_field<Child: store>(parent: address, id: address): Child;
+
+    native fun has_field(parent: address, id: address): bool;
+
+    native fun has_field_with_type<Child: store>(parent: address, id: address): bool;
+}
@@ -0,0 +1,8 @@
+/// Events let contracts report what happened in a transaction. Emitted events
+/// are included in the block with the type of `T`, the hash of the emitting
+/// transaction and their position among that transaction's events.
+module kanari_system::event {
+
+    /// Emit `event`. It is recorded only if the transaction succeeds.
+    public native fun emit<T: copy + drop>(event: T);
+}
@@ -0,0 +1,117 @@
+/// DAO governance: token holders propose treasury spends, chain parameter
+/// changes and framework upgrades, and vote on them weighted by balance plus
+/// stake. The node keeps a `GovernanceStore` under @kanari_system, records
+/// proposals and votes, closes each proposal once its voting period ends and
+/// executes the ones that passed.
+module kanari_system::governance {
+
+    #[allow(unused_const)]
+    /// Blocks a proposal stays open for voting
+    const VOTING_PERIOD_BLOCKS: u64 = 100;
+
+    #[allow(unused_const)]
+    /// Voting weight needed to submit a proposal, in Mist (1 KANARI)
+    const PROPOSAL_THRESHOLD: u64 = 1_000_000_000;
+
+    #[allow(unused_const)]
+    /// Share of the total supply that must vote, in basis points
+    const QUORUM_BPS: u64 = 1_000;
+
+    #[allow(unused_const)]
+    const STATUS_ACTIVE: u8 = 0;
+    #[allow(unused_const)]
+    const STATUS_REJECTED: u8 = 1;
+    #[allow(unused_const)]
+    const STATUS_EXECUTED: u8 = 2;
+    #[allow(unused_const)]
+    const STATUS_FAILED: u8 = 3;
+
+    struct Proposal has store, copy, drop {
+        id: u64,
+        proposer: address,
+        /// BCS-encoded action: a treasury spend, a chain parameter change or a
+        /// framework upgrade
+        action: vector<u8>,
+        /// Last block at which votes are accepted
+        voting_end_height: u64,
+        yes_votes: u64,
+        no_votes: u64,
+        voters: vector<address>,
+        status: u8,
+    }
+
+    /// Voting weight an account has committed to proposals that are still
+    /// open. Transfers and burns may not dip into it until the lock expires.
+    struct VoteLock has store, copy, drop {
+        voter: address,
+        amount: u64,
+        /// Last block of the latest voting period the lock covers
+        until_height: u64,
+    }
+
+    struct GovernanceStore has store, copy, drop {
+        proposals: vector<Proposal>,
+        next_id: u64,
+        locks: vector<VoteLock>,
+    }
+
+    public fun proposals(self: &GovernanceStore): &vector<Proposal> {
+        &self.proposals
+    }
+
+    public fun next_id(self: &GovernanceStore): u64 {
+        self.next_id
+    }
+
+    public fun locks(self: &GovernanceStore): &vector<VoteLock> {
+        &self.locks
+    }
+
+    public fun voter(self: &VoteLock): address {
+        self.voter
+    }
+
+    public fun amount(self: &VoteLock): u64 {
+        self.amount
+    }
+
+    public fun until_height(self: &VoteLock): u64 {
+        self.until_height
+    }
+
+    public fun id(self: &Proposal): u64 {
+        self.id
+    }
+
+    public fun proposer(self: &Proposal): address {
+        self.proposer
+    }
+
+    public fun action(self: &Proposal): &vector<u8> {
+        &self.action
+    }
+
+    public fun voting_end_height(self: &Proposal): u64 {
+        self.voting_end_height
+    }
+
+    public fun yes_votes(self: &Proposal): u64 {
+        self.yes_votes
+    }
+
+    public fun no_votes(self: &Proposal): u64 {
+        self.no_votes
+    }
+
+    public fun voters(self: &Proposal): &vector<address> {
+        &self.voters
+    }
+
+    public fun status(self: &Proposal): u8 {
+        self.status
+    }
+
+    public fun is_active(self: &Proposal): bool {
+        self.status == STATUS_ACTIVE
+    }
+}
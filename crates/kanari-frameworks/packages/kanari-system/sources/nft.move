@@ -0,0 +1,408 @@
+/// NFT collections. A `Collection` is a shared object whose creator mints
+/// `Nft`s into it, up to an optional supply cap, and which records the royalty
+/// owed to the creator on secondary sales. Each `Nft` is an owned object with
+/// a name, description, `Url` and key/value attributes.
+module kanari_system::nft {
+    use std::string::{Self, String};
+    use std::vector;
+    use kanari_system::event;
+    use kanari_system::object::{Self, UID};
+    use kanari_system::transfer;
+    use kanari_system::tx_context::{Self, TxContext};
+    use kanari_system::url::{Self, Url};
+
+    /// Only the collection's creator can mint
+    const ENotCreator: u64 = 0;
+    /// The collection has minted its maximum supply
+    const ESupplyExhausted: u64 = 1;
+    /// Royalties above 100%
+    const EInvalidRoyalty: u64 = 2;
+    /// The NFT belongs to another collection
+    const EWrongCollection: u64 = 3;
+    /// Attribute keys and values differ in number
+    const EAttributeMismatch: u64 = 4;
+
+    /// Royalties are in basis points of the sale price
+    const MAX_ROYALTY_BPS: u16 = 10_000;
+
+    struct Collection has key, store {
+        id: UID,
+        creator: address,
+        name: String,
+        description: String,
+        url: Url,
+        /// Most NFTs the collection can ever mint; 0 for no cap
+        max_supply: u64,
+        /// NFTs minted so far, burned ones included
+        minted: u64,
+        /// NFTs minted and not burned
+        supply: u64,
+        /// Share of each sale price owed to `royalty_recipient`, in basis points
+        royalty_bps: u16,
+        royalty_recipient: address,
+    }
+
+    struct Attribute has store, copy, drop {
+        key: String,
+        value: String,
+    }
+
+    struct Nft has key, store {
+        id: UID,
+        /// Id of the collection the NFT was minted in
+        collection: address,
+        /// Position in the collection's mint order, starting at 1
+        number: u64,
+        name: String,
+        description: String,
+        url: Url,
+        attributes: vector<Attribute>,
+    }
+
+    // --- Events ---
+
+    struct CollectionCreated has copy, drop {
+        collection: address,
+        creator: address,
+        name: String,
+        max_supply: u64,
+    }
+
+    struct NftMinted has copy, drop {
+        collection: address,
+        nft: address,
+        number: u64,
+        recipient: address,
+    }
+
+    struct NftTransferred has copy, drop {
+        nft: address,
+        from: address,
+        to: address,
+    }
+
+    struct NftBurned has copy, drop {
+        collection: address,
+        nft: address,
+        owner: address,
+    }
+
+    // --- Entry functions ---
+
+    /// Create a collection owned by the sender and share it
+    public entry fun create_collection(
+        name: vector<u8>,
+        description: vector<u8>,
+        url: vector<u8>,
+        max_supply: u64,
+        royalty_bps: u16,
+        royalty_recipient: address,
+        ctx: &mut TxContext,
+    ) {
+        let collection = new_collection(
+            string::utf8(name),
+            string::utf8(description),
+            url::new_unsafe_from_bytes(url),
+            max_supply,
+            royalty_bps,
+            royalty_recipient,
+            ctx,
+        );
+        transfer::share_object(collection);
+    }
+
+    /// Mint an NFT into `collection` and send it to `recipient`. Attribute
+    /// `i` is `attribute_keys[i]` = `attribute_values[i]`.
+    public entry fun mint_to(
+        collection: &mut Collection,
+        name: vector<u8>,
+        description: vector<u8>,
+        url: vector<u8>,
+        attribute_keys: vector<vector<u8>>,
+        attribute_values: vector<vector<u8>>,
+        recipient: address,
+        ctx: &mut TxContext,
+    ) {
+        let count = vector::length(&attribute_keys);
+        assert!(count == vector::length(&attribute_values), EAttributeMismatch);
+        let attributes = vector::empty<Attribute>();
+        let i = 0;
+        while (i < count) {
+            vector::push_back(&mut attributes, new_attribute(
+                string::utf8(*vector::borrow(&attribute_keys, i)),
+                string::utf8(*vector::borrow(&attribute_values, i)),
+            ));
+            i = i + 1;
+        };
+        let nft = mint(
+            collection,
+            string::utf8(name),
+            string::utf8(description),
+            url::new_unsafe_from_bytes(url),
+            attributes,
+            ctx,
+        );
+        event::emit(NftMinted {
+            collection: nft.collection,
+            nft: object::uid_address(&nft.id),
+            number: nft.number,
+            recipient,
+        });
+        transfer::public_transfer(nft, recipient);
+    }
+
+    /// Send `nft` to `recipient`
+    public entry fun transfer(nft: Nft, recipient: address, ctx: &mut TxContext) {
+        event::emit(NftTransferred {
+            nft: object::uid_address(&nft.id),
+            from: tx_context::sender(ctx),
+            to: recipient,
+        });
+        transfer::public_transfer(nft, recipient);
+    }
+
+    /// Destroy `nft`. Burned NFTs still count towards the collection's cap.
+    public entry fun burn(collection: &mut Collection, nft: Nft, ctx: &mut TxContext) {
+        let Nft {
+            id, collection: collection_id, number: _, name: _, description: _, url: _,
+            attributes: _,
+        } = nft;
+        assert!(collection_id == object::uid_address(&collection.id), EWrongCollection);
+        collection.supply = collection.supply - 1;
+        event::emit(NftBurned {
+            collection: collection_id,
+            nft: object::uid_address(&id),
+            owner: tx_context::sender(ctx),
+        });
+    }
+
+    // --- Public functions ---
+
+    /// Create a collection whose creator is the sender. Aborts with
+    /// `EInvalidRoyalty` if `royalty_bps` is above 10000.
+    public fun new_collection(
+        name: String,
+        description: String,
+        url: Url,
+        max_supply: u64,
+        royalty_bps: u16,
+        royalty_recipient: address,
+        ctx: &mut TxContext,
+    ): Collection {
+        assert!(royalty_bps <= MAX_ROYALTY_BPS, EInvalidRoyalty);
+        let creator = tx_context::sender(ctx);
+        let collection = Collection {
+            id: object::new(ctx),
+            creator,
+            name,
+            description,
+            url,
+            max_supply,
+            minted: 0,
+            supply: 0,
+            royalty_bps,
+            royalty_recipient,
+        };
+        event::emit(CollectionCreated {
+            collection: object::uid_address(&collection.id),
+            creator,
+            name,
+            max_supply,
+        });
+        collection
+    }
+
+    /// Mint an NFT into `collection`. Aborts with `ENotCreator` unless the
+    /// sender created the collection, and with `ESupplyExhausted` once it has
+    /// minted `max_supply` NFTs.
+    public fun mint(
+        collection: &mut Collection,
+        name: String,
+        description: String,
+        url: Url,
+        attributes: vector<Attribute>,
+        ctx: &mut TxContext,
+    ): Nft {
+        assert!(tx_context::sender(ctx) == collection.creator, ENotCreator);
+        assert!(
+            collection.max_supply == 0 || collection.minted < collection.max_supply,
+            ESupplyExhausted
+        );
+        collection.minted = collection.minted + 1;
+        collection.supply = collection.supply + 1;
+        Nft {
+            id: object::new(ctx),
+            collection: object::uid_address(&collection.id),
+            number: collection.minted,
+            name,
+            description,
+            url,
+            attributes,
+        }
+    }
+
+    public fun new_attribute(key: String, value: String): Attribute {
+        Attribute { key, value }
+    }
+
+    /// Recipient and amount of the royalty owed on a sale at `price`
+    public fun royalty(collection: &Collection, price: u64): (address, u64) {
+        let amount = (price as u128) * (collection.royalty_bps as u128) / (MAX_ROYALTY_BPS as u128);
+        (collection.royalty_recipient, (amount as u64))
+    }
+
+    // --- Getters ---
+
+    public fun collection_id(collection: &Collection): address {
+        object::uid_address(&collection.id)
+    }
+
+    public fun creator(collection: &Collection): address {
+        collection.creator
+    }
+
+    public fun collection_name(collection: &Collection): &String {
+        &collection.name
+    }
+
+    public fun collection_description(collection: &Collection): &String {
+        &collection.description
+    }
+
+    public fun collection_url(collection: &Collection): &Url {
+        &collection.url
+    }
+
+    public fun max_supply(collection: &Collection): u64 {
+        collection.max_supply
+    }
+
+    public fun minted(collection: &Collection): u64 {
+        collection.minted
+    }
+
+    public fun supply(collection: &Collection): u64 {
+        collection.supply
+    }
+
+    public fun royalty_bps(collection: &Collection): u16 {
+        collection.royalty_bps
+    }
+
+    public fun id(nft: &Nft): address {
+        object::uid_address(&nft.id)
+    }
+
+    public fun collection(nft: &Nft): address {
+        nft.collection
+    }
+
+    public fun number(nft: &Nft): u64 {
+        nft.number
+    }
+
+    public fun name(nft: &Nft): &String {
+        &nft.name
+    }
+
+    public fun description(nft: &Nft): &String {
+        &nft.description
+    }
+
+    public fun url(nft: &Nft): &Url {
+        &nft.url
+    }
+
+    public fun attributes(nft: &Nft): &vector<Attribute> {
+        &nft.attributes
+    }
+
+    public fun attribute_key(attribute: &Attribute): &String {
+        &attribute.key
+    }
+
+    public fun attribute_value(attribute: &Attribute): &String {
+        &attribute.value
+    }
+
+    #[test_only]
+    fun test_collection(max_supply: u64, ctx: &mut TxContext): Collection {
+        new_collection(
+            string::utf8(b"Birds"),
+            string::utf8(b"Pixel birds"),
+            url::new_unsafe_from_bytes(b"https://example.com/birds"),
+            max_supply,
+            250,
+            @0xB0B,
+            ctx,
+        )
+    }
+
+    #[test_only]
+    fun test_mint(collection: &mut Collection, ctx: &mut TxContext): Nft {
+        let attributes = vector[new_attribute(string::utf8(b"color"), string::utf8(b"red"))];
+        mint(
+            collection,
+            string::utf8(b"Robin"),
+            string::utf8(b"A red bird"),
+            url::new_unsafe_from_bytes(b"https://example.com/robin.png"),
+            attributes,
+            ctx,
+        )
+    }
+
+    #[test_only]
+    fun destroy_collection(collection: Collection) {
+        let Collection {
+            id: _, creator: _, name: _, description: _, url: _, max_supply: _,
+            minted: _, supply: _, royalty_bps: _, royalty_recipient: _,
+        } = collection;
+    }
+
+    #[test]
+    fun test_mint_and_burn() {
+        let ctx = tx_context::dummy();
+        let collection = test_collection(0, &mut ctx);
+        let first = test_mint(&mut collection, &mut ctx);
+        let second = test_mint(&mut collection, &mut ctx);
+        assert!(number(&second) == 2, 0);
+        assert!(collection(&first) == collection_id(&collection), 1);
+        assert!(*attribute_value(vector::borrow(attributes(&first), 0)) == string::utf8(b"red"), 2);
+
+        burn(&mut collection, first, &mut ctx);
+        assert!(supply(&collection) == 1 && minted(&collection) == 2, 3);
+        let (recipient, amount) = royalty(&collection, 10_000);
+        assert!(recipient == @0xB0B && amount == 250, 4);
+
+        burn(&mut collection, second, &mut ctx);
+        destroy_collection(collection);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = ESupplyExhausted)]
+    fun test_supply_cap() {
+        let ctx = tx_context::dummy();
+        let collection = test_collection(1, &mut ctx);
+        let first = test_mint(&mut collection, &mut ctx);
+        let second = test_mint(&mut collection, &mut ctx);
+        burn(&mut collection, first, &mut ctx);
+        burn(&mut collection, second, &mut ctx);
+        destroy_collection(collection);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = EInvalidRoyalty)]
+    fun test_invalid_royalty() {
+        let ctx = tx_context::dummy();
+        let collection = new_collection(
+            string::utf8(b"Birds"),
+            string::utf8(b""),
+            url::new_unsafe_from_bytes(b""),
+            0,
+            10_001,
+            @0xB0B,
+            &mut ctx,
+        );
+        destroy_collection(collection);
+    }
+}
@@ -11,6 +11,46 @@ module kanari_system::object {
         addr: address,
     }
 
+    #[allow(unused_const)]
+    /// Owned by the address in `owner`; only its owner can pass it to a transaction
+    const OWNERSHIP_ADDRESS: u8 = 0;
+    #[allow(unused_const)]
+    /// Any transaction can pass it by reference
+    const OWNERSHIP_SHARED: u8 = 1;
+    #[allow(unused_const)]
+    /// Frozen; any transaction can pass it by immutable reference
+    const OWNERSHIP_IMMUTABLE: u8 = 2;
+
+    /// An object persisted by the node after `transfer`, `freeze_object` or
+    /// `share_object`. Each object is a separate storage entry keyed by the
+    /// address of its `UID`.
+    struct Object has store, copy, drop {
+        id: address,
+        /// Owning address; @0x0 for shared and immutable objects
+        owner: address,
+        ownership: u8,
+        /// Type of the object, e.g. `0x2::coin::Coin<0x2::kanari::KANARI>`
+        type_tag: std::string::String,
+        /// BCS-encoded object value
+        contents: vector<u8>,
+    }
+
+    public fun id(self: &Object): address {
+        self.id
+    }
+
+    public fun owner(self: &Object): address {
+        self.owner
+    }
+
+    public fun ownership(self: &Object): u8 {
+        self.ownership
+    }
+
+    public fun contents(self: &Object): &vector<u8> {
+        &self.contents
+    }
+
     // --- Public Creator ---
 
     /// Create a new UID by deriving a fresh object address from the
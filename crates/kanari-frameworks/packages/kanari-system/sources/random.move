@@ -0,0 +1,132 @@
+/// Randomness for contracts. Each block header commits a beacon derived from
+/// the producer's signature over the previous beacon. A call into a module
+/// that uses this one is executed at the start of the block after the one that
+/// included it, drawing from a stream seeded by that block's beacon and the
+/// transaction hash, so nobody knows the seed while choosing transactions.
+/// Chains whose blocks are not signed have no unpredictable beacon, and the
+/// natives abort with `ERandomnessUnavailable`.
+///
+/// Draw randomness in a private `entry fun`. A public function can be called
+/// by another contract that inspects the outcome and aborts when it doesn't
+/// like it, retrying until it wins.
+module kanari_system::random {
+    use std::vector;
+
+    #[allow(unused_const)]
+    /// `min` is greater than `max` (raised by the natives)
+    const EInvalidRange: u64 = 0;
+    #[allow(unused_const)]
+    /// More than 4096 bytes requested at once (raised by the natives)
+    const ETooManyBytes: u64 = 1;
+    #[allow(unused_const)]
+    /// Blocks are not signed, so there is no beacon to draw from (raised by
+    /// the natives)
+    const ERandomnessUnavailable: u64 = 2;
+
+    /// Calls included in the latest block that wait for the next block's
+    /// beacon, as BCS-encoded signed transactions. Kept under @kanari_system
+    /// by the node.
+    struct DeferredCalls has store, copy, drop {
+        transactions: vector<vector<u8>>,
+    }
+
+    /// Number of calls waiting for the next block
+    public fun pending(self: &DeferredCalls): u64 {
+        vector::length(&self.transactions)
+    }
+
+    /// `n` random bytes. Aborts with `ETooManyBytes` above 4096.
+    public native fun bytes(n: u64): vector<u8>;
+
+    /// Uniform integer in `[min, max]`. Aborts with `EInvalidRange` if
+    /// `min > max`.
+    native fun u128_in_range(min: u128, max: u128): u128;
+
+    public fun generate_bool(): bool {
+        *vector::borrow(&bytes(1), 0) % 2 == 1
+    }
+
+    public fun generate_u8(): u8 {
+        (u128_in_range(0, 0xff) as u8)
+    }
+
+    public fun generate_u16(): u16 {
+        (u128_in_range(0, 0xffff) as u16)
+    }
+
+    public fun generate_u32(): u32 {
+        (u128_in_range(0, 0xffff_ffff) as u32)
+    }
+
+    public fun generate_u64(): u64 {
+        (u128_in_range(0, 0xffff_ffff_ffff_ffff) as u64)
+    }
+
+    public fun generate_u128(): u128 {
+        u128_in_range(0, 0xffff_ffff_ffff_ffff_ffff_ffff_ffff_ffff)
+    }
+
+    public fun generate_u8_in_range(min: u8, max: u8): u8 {
+        (u128_in_range((min as u128), (max as u128)) as u8)
+    }
+
+    public fun generate_u16_in_range(min: u16, max: u16): u16 {
+        (u128_in_range((min as u128), (max as u128)) as u16)
+    }
+
+    public fun generate_u32_in_range(min: u32, max: u32): u32 {
+        (u128_in_range((min as u128), (max as u128)) as u32)
+    }
+
+    public fun generate_u64_in_range(min: u64, max: u64): u64 {
+        (u128_in_range((min as u128), (max as u128)) as u64)
+    }
+
+    public fun generate_u128_in_range(min: u128, max: u128): u128 {
+        u128_in_range(min, max)
+    }
+
+    /// Shuffle `v` in place, each permutation being equally likely
+    public fun shuffle<T>(v: &mut vector<T>) {
+        let n = vector::length(v);
+        if (n < 2) return;
+        let i = n - 1;
+        while (i > 0) {
+            let j = generate_u64_in_range(0, i);
+            vector::swap(v, i, j);
+            i = i - 1;
+        }
+    }
+
+    #[test]
+    fun test_ranges() {
+        let i = 0;
+        while (i < 50) {
+            let value = generate_u64_in_range(10, 12);
+            assert!(value >= 10 && value <= 12, 0);
+            assert!(generate_u8_in_range(7, 7) == 7, 1);
+            i = i + 1;
+        };
+        assert!(vector::length(&bytes(100)) == 100, 2);
+        // The full range needs no rejection
+        generate_u128();
+    }
+
+    #[test]
+    fun test_shuffle() {
+        let v = vector[1, 2, 3, 4, 5];
+        shuffle(&mut v);
+        assert!(vector::length(&v) == 5, 0);
+        let i = 1;
+        while (i <= 5) {
+            assert!(vector::contains(&v, &i), 1);
+            i = i + 1;
+        }
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0, location = Self)]
+    fun test_invalid_range() {
+        generate_u64_in_range(2, 1);
+    }
+}
@@ -0,0 +1,110 @@
+/// Validator staking and fee rewards.
+/// The node keeps a `StakingPool` under @kanari_system and updates it when
+/// accounts stake, unstake or withdraw, and at every epoch boundary. Staked
+/// KANARI is held by @kanari_system until withdrawn.
+///
+/// Stake added during an epoch becomes active at the next one. At each epoch
+/// boundary the fees collected during the ending epoch are paid pro-rata to the
+/// active validators, which keep a commission and share the rest with their
+/// stakers, and the validator set is elected from the active stake.
+/// Unstaked KANARI can be withdrawn after an unbonding delay.
+module kanari_system::staking {
+
+    #[allow(unused_const)]
+    /// Self-stake a validator needs to be elected, in Mist (1,000 KANARI)
+    const MIN_VALIDATOR_STAKE: u64 = 1_000_000_000_000;
+
+    #[allow(unused_const)]
+    /// Maximum number of validators elected per epoch
+    const MAX_VALIDATORS: u64 = 100;
+
+    #[allow(unused_const)]
+    /// Epochs between unstaking and withdrawal
+    const UNBONDING_EPOCHS: u64 = 2;
+
+    #[allow(unused_const)]
+    /// Share of a validator's rewards it keeps, in basis points
+    const VALIDATOR_COMMISSION_BPS: u64 = 1_000;
+
+    /// KANARI staked by `staker` to `validator`, earning rewards from
+    /// `activation_epoch` on
+    struct Stake has store, copy, drop {
+        staker: address,
+        validator: address,
+        amount: u64,
+        activation_epoch: u64,
+    }
+
+    /// Unstaked KANARI waiting for its unbonding delay
+    struct Unbonding has store, copy, drop {
+        staker: address,
+        amount: u64,
+        withdrawable_epoch: u64,
+    }
+
+    /// Validator elected for the current epoch and its stake at election
+    struct ActiveValidator has store, copy, drop {
+        validator: address,
+        stake: u64,
+    }
+
+    struct StakingPool has store, copy, drop {
+        stakes: vector<Stake>,
+        unbonding: vector<Unbonding>,
+        active_validators: vector<ActiveValidator>,
+        /// Fees collected in the current epoch, paid out at its end
+        rewards: u64,
+    }
+
+    public fun stakes(self: &StakingPool): &vector<Stake> {
+        &self.stakes
+    }
+
+    public fun unbonding(self: &StakingPool): &vector<Unbonding> {
+        &self.unbonding
+    }
+
+    public fun active_validators(self: &StakingPool): &vector<ActiveValidator> {
+        &self.active_validators
+    }
+
+    public fun rewards(self: &StakingPool): u64 {
+        self.rewards
+    }
+
+    public fun staker(self: &Stake): address {
+        self.staker
+    }
+
+    public fun validator(self: &Stake): address {
+        self.validator
+    }
+
+    public fun amount(self: &Stake): u64 {
+        self.amount
+    }
+
+    public fun activation_epoch(self: &Stake): u64 {
+        self.activation_epoch
+    }
+
+    public fun unbonding_staker(self: &Unbonding): address {
+        self.staker
+    }
+
+    public fun unbonding_amount(self: &Unbonding): u64 {
+        self.amount
+    }
+
+    public fun withdrawable_epoch(self: &Unbonding): u64 {
+        self.withdrawable_epoch
+    }
+
+    public fun active_validator(self: &ActiveValidator): address {
+        self.validator
+    }
+
+    public fun active_stake(self: &ActiveValidator): u64 {
+        self.stake
+    }
+}
@@ -0,0 +1,90 @@
+/// A map from `K` to `V` whose entries are stored as dynamic fields of the
+/// table's `UID`, so it can grow without limit and each access only touches
+/// the entry it needs.
+module kanari_system::table {
+    use kanari_system::dynamic_field as field;
+    use kanari_system::object::{Self, UID};
+    use kanari_system::tx_context::TxContext;
+
+    /// The table still has entries
+    const ETableNotEmpty: u64 = 0;
+
+    struct Table<phantom K: copy + drop + store, phantom V: store> has key, store {
+        id: UID,
+        /// Number of entries
+        size: u64,
+    }
+
+    /// Create an empty table
+    public fun new<K: copy + drop + store, V: store>(ctx: &mut TxContext): Table<K, V> {
+        Table { id: object::new(ctx), size: 0 }
+    }
+
+    /// Add entry `k` -> `v`. Aborts if the table already has key `k`.
+    public fun add<K: copy + drop + store, V: store>(table: &mut Table<K, V>, k: K, v: V) {
+        field::add(&mut table.id, k, v);
+        table.size = table.size + 1;
+    }
+
+    /// Borrow the value of key `k`. Aborts if the table has no key `k`.
+    public fun borrow<K: copy + drop + store, V: store>(table: &Table<K, V>, k: K): &V {
+        field::borrow(&table.id, k)
+    }
+
+    /// Mutably borrow the value of key `k`. Aborts if the table has no key `k`.
+    public fun borrow_mut<K: copy + drop + store, V: store>(table: &mut Table<K, V>, k: K): &mut V {
+        field::borrow_mut(&mut table.id, k)
+    }
+
+    /// Remove key `k` and return its value. Aborts if the table has no key `k`.
+    public fun remove<K: copy + drop + store, V: store>(table: &mut Table<K, V>, k: K): V {
+        let v = field::remove(&mut table.id, k);
+        table.size = table.size - 1;
+        v
+    }
+
+    public fun contains<K: copy + drop + store, V: store>(table: &Table<K, V>, k: K): bool {
+        field::exists_with_type<K, V>(&table.id, k)
+    }
+
+    public fun length<K: copy + drop + store, V: store>(table: &Table<K, V>): u64 {
+        table.size
+    }
+
+    public fun is_empty<K: copy + drop + store, V: store>(table: &Table<K, V>): bool {
+        table.size == 0
+    }
+
+    /// Destroy an empty table. Aborts with `ETableNotEmpty` otherwise.
+    public fun destroy_empty<K: copy + drop + store, V: store>(table: Table<K, V>) {
+        let Table { id: _, size } = table;
+        assert!(size == 0, ETableNotEmpty);
+    }
+
+    #[test]
+    fun test_add_borrow_remove() {
+        let ctx = kanari_system::tx_context::dummy();
+        let table = new<u64, u64>(&mut ctx);
+        add(&mut table, 1, 10);
+        add(&mut table, 2, 20);
+        *borrow_mut(&mut table, 2) = 21;
+        assert!(length(&table) == 2, 0);
+        assert!(*borrow(&table, 2) == 21, 1);
+        assert!(remove(&mut table, 1) == 10, 2);
+        assert!(!contains(&table, 1), 3);
+        remove(&mut table, 2);
+        assert!(is_empty(&table), 4);
+        destroy_empty(table);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0, location = kanari_system::dynamic_field)]
+    fun test_add_twice() {
+        let ctx = kanari_system::tx_context::dummy();
+        let table = new<u64, u64>(&mut ctx);
+        add(&mut table, 1, 10);
+        add(&mut table, 1, 11);
+        remove(&mut table, 1);
+        destroy_empty(table);
+    }
+}
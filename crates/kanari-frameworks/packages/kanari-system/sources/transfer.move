@@ -6,6 +6,10 @@ module kanari_system::transfer {
     /// Error codes
     const ERR_INVALID_AMOUNT: u64 = 1;
     const ERR_SAME_ADDRESS: u64 = 2;
+    #[allow(unused_const)]
+    /// Raised by the ownership natives for an object whose id the transaction
+    /// neither received by value nor created with `object::new`
+    const ERR_OBJECT_ID_UNAVAILABLE: u64 = 3;
 
     /// Transfer record
     struct Transfer has copy, drop {
@@ -65,22 +69,46 @@ module kanari_system::transfer {
         assert!(get_amount(&t) == 500, 2);
     }
 
-    /// Minimal helper to 'freeze' a metadata object returned by currency creation.
-    /// This implementation is a no-op placeholder that consumes the object.
-    public fun public_freeze_object<T: drop>(_obj: T) {
-        // In a full implementation this would mark the metadata as immutable
-        // or store it in a global registry. Here we simply accept the object.
+    // --- Object ownership ---
+
+    /// Transfer ownership of `obj` to `recipient`. The object is persisted in the
+    /// node's object store under its `UID` once the transaction commits.
+    public fun transfer<T: key>(obj: T, recipient: address) {
+        transfer_impl(obj, recipient)
+    }
+
+    /// Transfer an object with `store` to `recipient`
+    public fun public_transfer<T: key + store>(obj: T, recipient: address) {
+        transfer_impl(obj, recipient)
+    }
+
+    /// Make `obj` immutable: anyone can read it, no one can mutate, transfer or
+    /// delete it again
+    public fun freeze_object<T: key>(obj: T) {
+        freeze_object_impl(obj)
+    }
+
+    /// Freeze an object with `store`
+    public fun public_freeze_object<T: key + store>(obj: T) {
+        freeze_object_impl(obj)
+    }
+
+    /// Make `obj` shared: any transaction can pass it by reference
+    public fun share_object<T: key>(obj: T) {
+        share_object_impl(obj)
     }
 
-    /// Transfer an object with store ability to a recipient
-    /// This is a simplified implementation for testing
-    public fun public_transfer<T: store + drop>(obj: T, recipient: address) {
-        // In a full implementation this would handle object ownership transfer
-        // For now, we consume the object and ignore the recipient
-        let _ = obj;
-        let _ = recipient;
+    /// Share an object with `store`
+    public fun public_share_object<T: key + store>(obj: T) {
+        share_object_impl(obj)
     }
 
+    native fun transfer_impl<T: key>(obj: T, recipient: address);
+
+    native fun freeze_object_impl<T: key>(obj: T);
+
+    native fun share_object_impl<T: key>(obj: T);
+
     #[test]
     fun test_total_amount() {
         let transfers = vector::empty<Transfer>();
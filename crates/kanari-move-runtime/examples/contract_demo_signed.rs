@@ -55,6 +55,7 @@ fn main() -> Result<()> {
         module_name: "my_token".to_string(),
         gas_limit: 1_000_000,
         gas_price: 1500,
+        priority_fee: 0,
         sequence_number: 0,
     };
 
@@ -124,8 +125,10 @@ fn main() -> Result<()> {
         function: "mint".to_string(),
         type_args: vec![],
         args: vec![recipient, amount],
+        objects: vec![],
         gas_limit: 200_000,
         gas_price: 1500,
+        priority_fee: 0,
         sequence_number: 0,
     };
 
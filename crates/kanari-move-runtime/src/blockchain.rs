@@ -1,44 +1,102 @@
 use crate::changeset::Event;
-use anyhow::Result;
+use crate::consensus::{BlockSigner, ValidatorSet};
+use crate::gas::GasConfig;
+use crate::governance::GovernanceAction;
+use crate::staking::StakingAction;
+use anyhow::{Context, Result};
 use kanari_crypto::hash_data_blake3;
 use kanari_crypto::keys::CurveType;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::language_storage::TypeTag;
 use serde::{Deserialize, Serialize};
 use std::time::{SystemTime, UNIX_EPOCH};
 
+/// Proof that a transaction was authorized: the signature together with the
+/// scheme and public key that produced it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransactionAuthenticator {
+    /// Signature scheme flag, see `CurveType::scheme_flag`
+    pub scheme: u8,
+    /// Hex public key of the signer; hybrid keys are `classical:pqc`
+    pub public_key: String,
+    pub signature: Vec<u8>,
+}
+
 /// Signed transaction wrapper
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SignedTransaction {
     pub transaction: Transaction,
-    pub signature: Option<Vec<u8>>,
+    pub authenticator: Option<TransactionAuthenticator>,
 }
 
 impl SignedTransaction {
     pub fn new(transaction: Transaction) -> Self {
         Self {
             transaction,
-            signature: None,
+            authenticator: None,
         }
     }
 
+    /// Sign the transaction hash and attach the resulting authenticator
     pub fn sign(&mut self, private_key: &str, curve_type: CurveType) -> Result<()> {
+        let keypair = kanari_crypto::keys::keypair_from_private_key(private_key, curve_type)
+            .map_err(|e| anyhow::anyhow!("Failed to derive public key: {}", e))?;
+
         let tx_hash = self.transaction.hash();
         let signature = kanari_crypto::sign_message(private_key, &tx_hash, curve_type)
             .map_err(|e| anyhow::anyhow!("Failed to sign transaction: {}", e))?;
-        self.signature = Some(signature);
+        self.authenticator = Some(TransactionAuthenticator {
+            scheme: curve_type.scheme_flag(),
+            public_key: keypair.public_key,
+            signature,
+        });
         Ok(())
     }
 
-    pub fn verify_signature(&self) -> Result<bool> {
-        let signature = self
-            .signature
+    /// Verify the authenticator with its declared scheme.
+    ///
+    /// The public key must control the sender: it has to match the sender's
+    /// on-chain authentication key, or, if the account never rotated its key,
+    /// derive the sender address (see `keys::account_address`). Hybrid signatures
+    /// are only valid if both halves verify.
+    pub fn verify_authentication(&self, authentication_key: Option<&[u8]>) -> Result<bool> {
+        let authenticator = self
+            .authenticator
             .as_ref()
             .ok_or_else(|| anyhow::anyhow!("Transaction not signed"))?;
+        let curve_type = CurveType::from_scheme_flag(authenticator.scheme)
+            .ok_or_else(|| anyhow::anyhow!("Unknown signature scheme: {}", authenticator.scheme))?;
+
+        let public_key = &authenticator.public_key;
+        let Ok(key_bytes) = kanari_crypto::keys::public_key_bytes(curve_type, public_key) else {
+            return Ok(false);
+        };
+        match authentication_key {
+            Some(expected) => {
+                if kanari_crypto::keys::authentication_key(curve_type, &key_bytes) != expected {
+                    return Ok(false);
+                }
+            }
+            None => {
+                let Ok(owner) = kanari_crypto::keys::account_address(curve_type, public_key) else {
+                    return Ok(false);
+                };
+                let sender = AccountAddress::from_hex_literal(self.transaction.sender_address())
+                    .context("Invalid sender address")?;
+                if owner.to_vec() != sender.to_vec() {
+                    return Ok(false);
+                }
+            }
+        }
 
-        let tx_hash = self.transaction.hash();
-        let sender = self.transaction.sender_address();
-
-        kanari_crypto::verify_signature(sender, &tx_hash, signature)
-            .map_err(|e| anyhow::anyhow!("Signature verification failed: {}", e))
+        // Keys or signatures malformed for the scheme simply don't verify
+        Ok(kanari_crypto::verify_signature_with_curve(
+            public_key,
+            &self.transaction.hash(),
+            &authenticator.signature,
+            curve_type,
+        )
+        .unwrap_or(false))
     }
 
     pub fn hash(&self) -> Vec<u8> {
@@ -55,6 +113,27 @@ pub struct BlockHeader {
     pub prev_hash: Vec<u8>,
     pub state_root: Vec<u8>,
     pub tx_count: usize,
+    /// Commitment to the block's transactions, so competing blocks at the same
+    /// height never share a hash
+    #[serde(default)]
+    pub tx_root: Vec<u8>,
+    /// Address of the validator that produced the block (proof-of-authority)
+    #[serde(default)]
+    pub producer: Option<String>,
+    /// Fee per gas unit burned by every transaction in the block
+    #[serde(default)]
+    pub base_fee: u64,
+    /// Total gas used by the block's transactions
+    #[serde(default)]
+    pub gas_used: u64,
+    /// Randomness beacon committed by the producer, seeding `0x2::random`
+    /// for the block's transactions (see `RandomBeacon`)
+    #[serde(default)]
+    pub random_beacon: Vec<u8>,
+    /// Producer's signature the beacon is derived from; empty for blocks
+    /// produced without a signer
+    #[serde(default)]
+    pub random_proof: Vec<u8>,
 }
 
 impl BlockHeader {
@@ -70,6 +149,12 @@ impl BlockHeader {
             prev_hash,
             state_root,
             tx_count,
+            tx_root: Vec::new(),
+            producer: None,
+            base_fee: 0,
+            gas_used: 0,
+            random_beacon: Vec::new(),
+            random_proof: Vec::new(),
         }
     }
 
@@ -88,7 +173,11 @@ pub enum Transaction {
         module_bytes: Vec<u8>,
         module_name: String,
         gas_limit: u64,
+        /// Maximum fee per gas unit, covering the block base fee and the tip
         gas_price: u64,
+        /// Tip per gas unit for the block producer, paid on top of the base fee
+        #[serde(default)]
+        priority_fee: u64,
         sequence_number: u64,
     },
     /// Execute a Move function
@@ -98,8 +187,15 @@ pub enum Transaction {
         function: String,
         type_args: Vec<String>,
         args: Vec<Vec<u8>>,
+        /// Ids of the objects passed as the function's leading parameters
+        #[serde(default)]
+        objects: Vec<String>,
         gas_limit: u64,
+        /// Maximum fee per gas unit, covering the block base fee and the tip
         gas_price: u64,
+        /// Tip per gas unit for the block producer, paid on top of the base fee
+        #[serde(default)]
+        priority_fee: u64,
         sequence_number: u64,
     },
     /// Transfer coins
@@ -107,8 +203,15 @@ pub enum Transaction {
         from: String,
         to: String,
         amount: u64,
+        /// Coin to transfer; KANARI when `None`
+        #[serde(default)]
+        coin_type: Option<TypeTag>,
         gas_limit: u64,
+        /// Maximum fee per gas unit, covering the block base fee and the tip
         gas_price: u64,
+        /// Tip per gas unit for the block producer, paid on top of the base fee
+        #[serde(default)]
+        priority_fee: u64,
         sequence_number: u64,
     },
     /// Burn coins (remove from total supply)
@@ -116,7 +219,47 @@ pub enum Transaction {
         from: String,
         amount: u64,
         gas_limit: u64,
+        /// Maximum fee per gas unit, covering the block base fee and the tip
+        gas_price: u64,
+        /// Tip per gas unit for the block producer, paid on top of the base fee
+        #[serde(default)]
+        priority_fee: u64,
+        sequence_number: u64,
+    },
+    /// Replace the sender's authentication key. Must be signed by the current key.
+    RotateAuthenticationKey {
+        sender: String,
+        new_authentication_key: Vec<u8>,
+        gas_limit: u64,
+        /// Maximum fee per gas unit, covering the block base fee and the tip
         gas_price: u64,
+        /// Tip per gas unit for the block producer, paid on top of the base fee
+        #[serde(default)]
+        priority_fee: u64,
+        sequence_number: u64,
+    },
+    /// Stake, unstake or withdraw KANARI through the staking pool
+    Staking {
+        sender: String,
+        action: StakingAction,
+        gas_limit: u64,
+        /// Maximum fee per gas unit, covering the block base fee and the tip
+        gas_price: u64,
+        /// Tip per gas unit for the block producer, paid on top of the base fee
+        #[serde(default)]
+        priority_fee: u64,
+        sequence_number: u64,
+    },
+    /// Submit or vote on a governance proposal
+    Governance {
+        sender: String,
+        action: GovernanceAction,
+        gas_limit: u64,
+        /// Maximum fee per gas unit, covering the block base fee and the tip
+        gas_price: u64,
+        /// Tip per gas unit for the block producer, paid on top of the base fee
+        #[serde(default)]
+        priority_fee: u64,
         sequence_number: u64,
     },
 }
@@ -133,6 +276,9 @@ impl Transaction {
             Transaction::ExecuteFunction { sender, .. } => sender,
             Transaction::Transfer { from, .. } => from,
             Transaction::Burn { from, .. } => from,
+            Transaction::RotateAuthenticationKey { sender, .. } => sender,
+            Transaction::Staking { sender, .. } => sender,
+            Transaction::Governance { sender, .. } => sender,
         }
     }
 
@@ -154,6 +300,15 @@ impl Transaction {
             Transaction::Burn {
                 sequence_number, ..
             } => *sequence_number,
+            Transaction::RotateAuthenticationKey {
+                sequence_number, ..
+            } => *sequence_number,
+            Transaction::Staking {
+                sequence_number, ..
+            } => *sequence_number,
+            Transaction::Governance {
+                sequence_number, ..
+            } => *sequence_number,
         }
     }
 
@@ -163,6 +318,9 @@ impl Transaction {
             Transaction::ExecuteFunction { gas_limit, .. } => *gas_limit,
             Transaction::Transfer { gas_limit, .. } => *gas_limit,
             Transaction::Burn { gas_limit, .. } => *gas_limit,
+            Transaction::RotateAuthenticationKey { gas_limit, .. } => *gas_limit,
+            Transaction::Staking { gas_limit, .. } => *gas_limit,
+            Transaction::Governance { gas_limit, .. } => *gas_limit,
         }
     }
 
@@ -172,17 +330,56 @@ impl Transaction {
             Transaction::ExecuteFunction { gas_price, .. } => *gas_price,
             Transaction::Transfer { gas_price, .. } => *gas_price,
             Transaction::Burn { gas_price, .. } => *gas_price,
+            Transaction::RotateAuthenticationKey { gas_price, .. } => *gas_price,
+            Transaction::Staking { gas_price, .. } => *gas_price,
+            Transaction::Governance { gas_price, .. } => *gas_price,
+        }
+    }
+
+    pub fn priority_fee(&self) -> u64 {
+        match self {
+            Transaction::PublishModule { priority_fee, .. }
+            | Transaction::ExecuteFunction { priority_fee, .. }
+            | Transaction::Transfer { priority_fee, .. }
+            | Transaction::Burn { priority_fee, .. }
+            | Transaction::RotateAuthenticationKey { priority_fee, .. }
+            | Transaction::Staking { priority_fee, .. }
+            | Transaction::Governance { priority_fee, .. } => *priority_fee,
         }
     }
 
+    /// Price per gas unit actually paid in a block with `base_fee`: the base fee
+    /// plus as much of the tip as `gas_price` leaves room for. `None` if the
+    /// transaction can't pay the base fee.
+    pub fn effective_gas_price(&self, base_fee: u64) -> Option<u64> {
+        let headroom = self.gas_price().checked_sub(base_fee)?;
+        Some(base_fee + self.priority_fee().min(headroom))
+    }
+
     /// Create a transfer transaction with default gas settings
     pub fn new_transfer(from: String, to: String, amount: u64) -> Self {
         Self::Transfer {
             from,
             to,
             amount,
+            coin_type: None,
             gas_limit: 100_000, // Default gas limit
-            gas_price: 1000,    // Default gas price (1000 Mist)
+            gas_price: 1000,    // Default max fee per gas (1000 Mist)
+            priority_fee: 0,
+            sequence_number: 0,
+        }
+    }
+
+    /// Create a transfer of `coin_type` with default gas settings
+    pub fn new_coin_transfer(from: String, to: String, coin_type: TypeTag, amount: u64) -> Self {
+        Self::Transfer {
+            from,
+            to,
+            amount,
+            coin_type: Some(coin_type),
+            gas_limit: 100_000,
+            gas_price: 1000,
+            priority_fee: 0,
             sequence_number: 0,
         }
     }
@@ -194,6 +391,43 @@ impl Transaction {
             amount,
             gas_limit: 100_000,
             gas_price: 1000,
+            priority_fee: 0,
+            sequence_number: 0,
+        }
+    }
+
+    /// Create an authentication key rotation with default gas settings
+    pub fn new_rotate_authentication_key(sender: String, new_authentication_key: Vec<u8>) -> Self {
+        Self::RotateAuthenticationKey {
+            sender,
+            new_authentication_key,
+            gas_limit: 100_000,
+            gas_price: 1000,
+            priority_fee: 0,
+            sequence_number: 0,
+        }
+    }
+
+    /// Create a staking transaction with default gas settings
+    pub fn new_staking(sender: String, action: StakingAction) -> Self {
+        Self::Staking {
+            sender,
+            action,
+            gas_limit: 100_000,
+            gas_price: 1000,
+            priority_fee: 0,
+            sequence_number: 0,
+        }
+    }
+
+    /// Create a governance transaction with default gas settings
+    pub fn new_governance(sender: String, action: GovernanceAction) -> Self {
+        Self::Governance {
+            sender,
+            action,
+            gas_limit: 100_000,
+            gas_price: 1000,
+            priority_fee: 0,
             sequence_number: 0,
         }
     }
@@ -203,37 +437,111 @@ impl Transaction {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Block {
     pub header: BlockHeader,
-    pub transactions: Vec<Transaction>,
+    /// Transactions with their authenticators, so importing nodes can check
+    /// that every sender authorized its transaction
+    pub transactions: Vec<SignedTransaction>,
     pub events: Vec<Event>,
+    /// Set when the body (transactions and events) has been dropped by pruning
+    /// or was never available locally. The header stays valid for linkage.
+    #[serde(default)]
+    pub body_pruned: bool,
+    /// Producer's signature over the header hash
+    #[serde(default)]
+    pub signature: Option<Vec<u8>>,
 }
 
 impl Block {
     pub fn new(
         height: u64,
         prev_hash: Vec<u8>,
-        transactions: Vec<Transaction>,
+        transactions: Vec<SignedTransaction>,
         events: Vec<Event>,
     ) -> Self {
         let state_root = vec![0u8; 32]; // Placeholder, compute from state
         let tx_count = transactions.len();
-        let header = BlockHeader::new(height, prev_hash, state_root, tx_count);
+        let mut header = BlockHeader::new(height, prev_hash, state_root, tx_count);
+        header.tx_root = Self::compute_tx_root(&transactions);
 
         Self {
             header,
             transactions,
             events,
+            body_pruned: false,
+            signature: None,
         }
     }
 
+    /// Genesis uses a fixed timestamp so every node derives the same genesis hash
     pub fn genesis() -> Self {
-        Self::new(0, vec![0u8; 32], vec![], vec![])
+        let mut genesis = Self::new(0, vec![0u8; 32], vec![], vec![]);
+        genesis.header.timestamp = 0;
+        genesis.header.base_fee = GasConfig::default().base_price;
+        genesis
     }
 
     pub fn hash(&self) -> Vec<u8> {
         self.header.hash()
     }
 
-    pub fn verify(&self, prev_block: &Block) -> Result<()> {
+    pub fn compute_tx_root(transactions: &[SignedTransaction]) -> Vec<u8> {
+        let hashes: Vec<Vec<u8>> = transactions.iter().map(|tx| tx.hash()).collect();
+        hash_data_blake3(&serde_json::to_vec(&hashes).unwrap())
+    }
+
+    /// Drop transactions and events, keeping only the header.
+    /// Returns false if the body was already pruned.
+    pub fn prune_body(&mut self) -> bool {
+        if self.body_pruned {
+            return false;
+        }
+        self.transactions = Vec::new();
+        self.events = Vec::new();
+        self.body_pruned = true;
+        true
+    }
+
+    /// Set the producer and sign the header with the producer's key
+    pub fn sign(&mut self, signer: &BlockSigner) -> Result<()> {
+        self.header.producer = Some(signer.address.clone());
+        self.signature = Some(signer.sign(&self.hash())?);
+        Ok(())
+    }
+
+    /// Check that the block was produced and signed by the scheduled validator
+    pub fn verify_producer(&self, validators: &ValidatorSet) -> Result<()> {
+        let producer = self
+            .header
+            .producer
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("Block has no producer"))?;
+        let Some(validator) = validators.get(producer) else {
+            anyhow::bail!("Unknown block producer {}", producer);
+        };
+        if !validators.is_leader(producer, self.header.height) {
+            anyhow::bail!(
+                "Wrong producer for height {}: expected {}, got {}",
+                self.header.height,
+                validators.leader_for(self.header.height),
+                producer
+            );
+        }
+
+        let signature = self
+            .signature
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Block is not signed"))?;
+        let valid = validator
+            .verify(&self.hash(), signature)
+            .context("Block signature verification failed")?;
+        if !valid {
+            anyhow::bail!("Invalid block signature from {}", producer);
+        }
+        Ok(())
+    }
+
+    /// Verify linkage to `prev_block`, the body against the header and, when a
+    /// validator set is given, the producer and signature.
+    pub fn verify(&self, prev_block: &Block, validators: Option<&ValidatorSet>) -> Result<()> {
         // Verify height
         if self.header.height != prev_block.header.height + 1 {
             anyhow::bail!("Invalid block height");
@@ -244,19 +552,37 @@ impl Block {
             anyhow::bail!("Invalid previous hash");
         }
 
+        // Verify the body matches the header
+        if !self.body_pruned
+            && (self.header.tx_count != self.transactions.len()
+                || self.header.tx_root != Self::compute_tx_root(&self.transactions))
+        {
+            anyhow::bail!("Transactions do not match block header");
+        }
+
         // Verify timestamp (allow some leeway for genesis)
         if self.header.height > 1 && self.header.timestamp < prev_block.header.timestamp {
             anyhow::bail!("Invalid timestamp");
         }
 
+        if let Some(validators) = validators {
+            self.verify_producer(validators)?;
+        }
+
         Ok(())
     }
 }
 
 /// Blockchain state
+///
+/// `blocks` is always a contiguous run of heights, but may start above genesis
+/// when old blocks were pruned or the chain was started from a snapshot.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Blockchain {
     pub blocks: Vec<Block>,
+    /// Proof-of-authority validator set. When unset, blocks are not checked for a producer.
+    #[serde(default)]
+    pub validators: Option<ValidatorSet>,
 }
 
 impl Blockchain {
@@ -264,6 +590,22 @@ impl Blockchain {
         let genesis = Block::genesis();
         Self {
             blocks: vec![genesis],
+            validators: None,
+        }
+    }
+
+    /// Start a chain from a trusted header (e.g. an imported snapshot) instead of genesis.
+    /// Blocks below the header height are not available locally.
+    pub fn from_header(header: BlockHeader) -> Self {
+        Self {
+            blocks: vec![Block {
+                header,
+                transactions: vec![],
+                events: vec![],
+                body_pruned: true,
+                signature: None,
+            }],
+            validators: None,
         }
     }
 
@@ -275,19 +617,50 @@ impl Blockchain {
         self.latest_block().header.height
     }
 
+    /// Verify `block` as the next block on top of the current tip
+    pub fn verify_next(&self, block: &Block) -> Result<()> {
+        block.verify(self.latest_block(), self.validators.as_ref())
+    }
+
     pub fn add_block(&mut self, block: Block) -> Result<()> {
-        let prev_block = self.latest_block();
-        block.verify(prev_block)?;
+        self.verify_next(&block)?;
         self.blocks.push(block);
         Ok(())
     }
 
+    /// Height of the oldest block still held locally
+    pub fn base_height(&self) -> u64 {
+        self.blocks[0].header.height
+    }
+
+    /// O(1) lookup: heights are contiguous from `base_height`
     pub fn get_block(&self, height: u64) -> Option<&Block> {
-        self.blocks.iter().find(|b| b.header.height == height)
+        let offset = height.checked_sub(self.base_height())?;
+        self.blocks.get(usize::try_from(offset).ok()?)
     }
 
+    /// Counts from headers so pruned bodies are still included
     pub fn get_transaction_count(&self) -> usize {
-        self.blocks.iter().map(|b| b.transactions.len()).sum()
+        self.blocks.iter().map(|b| b.header.tx_count).sum()
+    }
+
+    /// Remove whole blocks below `height`. The latest block is never removed.
+    pub fn prune_blocks_below(&mut self, height: u64) -> usize {
+        let base = self.base_height();
+        let count = height
+            .saturating_sub(base)
+            .min(self.blocks.len() as u64 - 1) as usize;
+        self.blocks.drain(..count);
+        count
+    }
+
+    /// Drop bodies of blocks below `height`, keeping their headers
+    pub fn prune_bodies_below(&mut self, height: u64) -> usize {
+        self.blocks
+            .iter_mut()
+            .take_while(|b| b.header.height < height)
+            .filter_map(|b| b.prune_body().then_some(()))
+            .count()
     }
 }
 
@@ -327,10 +700,125 @@ mod tests {
         let prev_block = chain.latest_block();
 
         let valid_block = Block::new(1, prev_block.hash(), vec![], vec![]);
-        assert!(valid_block.verify(prev_block).is_ok());
+        assert!(valid_block.verify(prev_block, None).is_ok());
 
         let invalid_block = Block::new(2, prev_block.hash(), vec![], vec![]);
-        assert!(invalid_block.verify(prev_block).is_err());
+        assert!(invalid_block.verify(prev_block, None).is_err());
+    }
+
+    #[test]
+    fn test_chain_from_header() {
+        let mut source = Blockchain::new();
+        let block = Block::new(1, source.latest_block().hash(), vec![], vec![]);
+        source.add_block(block).unwrap();
+
+        let mut chain = Blockchain::from_header(source.latest_block().header.clone());
+        assert_eq!(chain.height(), 1);
+        assert_eq!(chain.latest_block().hash(), source.latest_block().hash());
+        assert!(chain.get_block(0).is_none());
+
+        let next = Block::new(2, chain.latest_block().hash(), vec![], vec![]);
+        chain.add_block(next).unwrap();
+        assert_eq!(chain.height(), 2);
+    }
+
+    #[test]
+    fn test_pruning_keeps_lookup_consistent() {
+        let mut chain = Blockchain::new();
+        for height in 1..=10 {
+            let tx = SignedTransaction::new(Transaction::new_transfer(
+                "0x1".to_string(),
+                "0x2".to_string(),
+                height,
+            ));
+            let block = Block::new(height, chain.latest_block().hash(), vec![tx], vec![]);
+            chain.add_block(block).unwrap();
+        }
+        assert_eq!(chain.get_transaction_count(), 10);
+
+        assert_eq!(chain.prune_bodies_below(4), 4);
+        assert_eq!(chain.prune_bodies_below(4), 0);
+        assert!(chain.get_block(3).unwrap().body_pruned);
+        assert!(chain.get_block(3).unwrap().transactions.is_empty());
+        assert!(!chain.get_block(4).unwrap().body_pruned);
+        assert_eq!(chain.get_transaction_count(), 10);
+
+        assert_eq!(chain.prune_blocks_below(6), 6);
+        assert_eq!(chain.base_height(), 6);
+        assert!(chain.get_block(5).is_none());
+        assert_eq!(chain.get_block(8).unwrap().header.height, 8);
+        assert!(chain.get_block(11).is_none());
+
+        // The tip always survives
+        assert_eq!(chain.prune_blocks_below(100), 4);
+        assert_eq!(chain.blocks.len(), 1);
+        assert_eq!(chain.height(), 10);
+    }
+
+    #[test]
+    fn test_block_commits_to_transactions() {
+        let chain = Blockchain::new();
+        let prev = chain.latest_block();
+        let tx_a = SignedTransaction::new(Transaction::new_transfer(
+            "0x1".to_string(),
+            "0x2".to_string(),
+            1,
+        ));
+        let tx_b = SignedTransaction::new(Transaction::new_transfer(
+            "0x1".to_string(),
+            "0x2".to_string(),
+            2,
+        ));
+
+        let block_a = Block::new(1, prev.hash(), vec![tx_a], vec![]);
+        let mut block_b = Block::new(1, prev.hash(), vec![tx_b.clone()], vec![]);
+        block_b.header.timestamp = block_a.header.timestamp;
+        assert_ne!(block_a.hash(), block_b.hash());
+
+        let mut tampered = block_a.clone();
+        tampered.transactions = vec![tx_b];
+        assert!(tampered.verify(prev, None).is_err());
+        assert!(block_a.verify(prev, None).is_ok());
+    }
+
+    #[test]
+    fn test_block_producer_verification() {
+        use kanari_crypto::keys::generate_keypair;
+
+        let alice = generate_keypair(CurveType::Ed25519).unwrap();
+        let bob = generate_keypair(CurveType::Ed25519).unwrap();
+        let carol = generate_keypair(CurveType::Ed25519).unwrap();
+        let alice_signer = BlockSigner::new(&alice.private_key, CurveType::Ed25519).unwrap();
+        let bob_signer = BlockSigner::new(&bob.private_key, CurveType::Ed25519).unwrap();
+        let carol_signer = BlockSigner::new(&carol.private_key, CurveType::Ed25519).unwrap();
+        let validators =
+            ValidatorSet::new(vec![alice_signer.validator(), bob_signer.validator()]).unwrap();
+
+        let mut chain = Blockchain::new();
+        chain.validators = Some(validators);
+        let prev_hash = chain.latest_block().hash();
+
+        // Height 1 belongs to bob
+        let mut unsigned = Block::new(1, prev_hash.clone(), vec![], vec![]);
+        assert!(chain.verify_next(&unsigned).is_err());
+
+        let mut wrong_leader = unsigned.clone();
+        wrong_leader.sign(&alice_signer).unwrap();
+        assert!(chain.verify_next(&wrong_leader).is_err());
+
+        let mut unknown = unsigned.clone();
+        unknown.sign(&carol_signer).unwrap();
+        assert!(chain.verify_next(&unknown).is_err());
+
+        // Signature by alice claiming to be bob
+        let mut forged = unsigned.clone();
+        forged.header.producer = Some(bob_signer.address.clone());
+        forged.signature = Some(alice_signer.sign(&forged.hash()).unwrap());
+        assert!(chain.verify_next(&forged).is_err());
+
+        unsigned.sign(&bob_signer).unwrap();
+        chain.add_block(unsigned).unwrap();
+        assert_eq!(chain.height(), 1);
     }
 
     #[test]
@@ -341,4 +829,31 @@ mod tests {
         let hash2 = tx.hash();
         assert_eq!(hash1, hash2);
     }
+
+    #[test]
+    fn test_transaction_authenticator() {
+        use kanari_crypto::keys::generate_keypair;
+
+        let keypair = generate_keypair(CurveType::K256).unwrap();
+        let other = generate_keypair(CurveType::K256).unwrap();
+        let mut signed = SignedTransaction::new(Transaction::new_transfer(
+            keypair.address.clone(),
+            "0x2".to_string(),
+            1000,
+        ));
+        assert!(signed.verify_authentication(None).is_err());
+
+        signed.sign(&keypair.private_key, CurveType::K256).unwrap();
+        assert!(signed.verify_authentication(None).unwrap());
+
+        // The declared scheme is used as is, never guessed
+        let mut wrong_scheme = signed.clone();
+        wrong_scheme.authenticator.as_mut().unwrap().scheme = CurveType::P256.scheme_flag();
+        assert!(!wrong_scheme.verify_authentication(None).unwrap());
+
+        // A valid signature by a key that doesn't control the sender is rejected
+        let mut wrong_key = signed.clone();
+        wrong_key.sign(&other.private_key, CurveType::K256).unwrap();
+        assert!(!wrong_key.verify_authentication(None).unwrap());
+    }
 }
@@ -0,0 +1,184 @@
+use crate::gas::{GasConfig, GasSchedule};
+use crate::move_runtime::MoveRuntime;
+use crate::move_storage::MoveStorage;
+use anyhow::{Context, Result};
+use kanari_types::address::Address as KanariAddress;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::StructTag;
+use serde::{Deserialize, Serialize};
+
+/// Number of blocks per epoch at genesis
+pub const DEFAULT_EPOCH_LENGTH: u64 = 100;
+
+/// Gas schedule, gas limits and epoch length in force for a block.
+/// BCS layout matches `0x2::chain_params::ChainParameters`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainParameters {
+    /// Number of blocks per epoch; staged parameters take effect at epoch starts
+    pub epoch_length: u64,
+    pub gas_config: GasConfig,
+    pub gas_schedule: GasSchedule,
+}
+
+impl Default for ChainParameters {
+    fn default() -> Self {
+        Self {
+            epoch_length: DEFAULT_EPOCH_LENGTH,
+            gas_config: GasConfig::default(),
+            gas_schedule: GasSchedule::default(),
+        }
+    }
+}
+
+impl ChainParameters {
+    /// Reject parameters that would halt the chain
+    pub fn validate(&self) -> Result<()> {
+        let gas = &self.gas_config;
+        if self.epoch_length == 0 {
+            anyhow::bail!("Epoch length must be at least one block");
+        }
+        if gas.min_gas_price == 0 {
+            anyhow::bail!("Minimum gas price must be positive");
+        }
+        if gas.base_price < gas.min_gas_price {
+            anyhow::bail!(
+                "Base price {} is below the minimum gas price {}",
+                gas.base_price,
+                gas.min_gas_price
+            );
+        }
+        if gas.max_gas_per_tx == 0 || gas.max_gas_per_tx > gas.max_gas_per_block {
+            anyhow::bail!(
+                "Maximum gas per transaction {} must be positive and at most the block maximum {}",
+                gas.max_gas_per_tx,
+                gas.max_gas_per_block
+            );
+        }
+        Ok(())
+    }
+
+    /// Epoch that the block at `height` belongs to
+    pub fn epoch(&self, height: u64) -> u64 {
+        height / self.epoch_length.max(1)
+    }
+
+    /// Whether the block at `height` is the first of its epoch
+    pub fn is_epoch_start(&self, height: u64) -> bool {
+        height.is_multiple_of(self.epoch_length.max(1))
+    }
+}
+
+/// The `0x2::chain_params::ChainParamsStore` resource: parameters in force and
+/// those staged by governance for the next epoch
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainParamsStore {
+    pub current: ChainParameters,
+    pub pending: Option<ChainParameters>,
+}
+
+impl ChainParamsStore {
+    pub const MODULE_NAME: &'static str = "chain_params";
+    pub const STRUCT_NAME: &'static str = "ChainParamsStore";
+
+    /// Store written into Move storage at genesis
+    pub fn genesis() -> Self {
+        Self {
+            current: ChainParameters::default(),
+            pending: None,
+        }
+    }
+
+    /// Account holding the resource, `0x2`
+    pub fn address() -> AccountAddress {
+        AccountAddress::from_hex_literal(KanariAddress::KANARI_SYSTEM_ADDRESS).unwrap()
+    }
+
+    pub fn struct_tag() -> StructTag {
+        StructTag {
+            address: Self::address(),
+            module: Identifier::new(Self::MODULE_NAME).unwrap(),
+            name: Identifier::new(Self::STRUCT_NAME).unwrap(),
+            type_params: vec![],
+        }
+    }
+
+    /// Read the resource from Move storage
+    pub fn load(storage: &MoveStorage) -> Result<Self> {
+        let bytes = storage
+            .resource(&Self::address(), &Self::struct_tag())
+            .context("Chain parameters are missing from Move storage")?;
+        bcs::from_bytes(bytes).context("Failed to decode chain parameters")
+    }
+
+    /// Write the resource into the runtime's Move storage
+    pub fn save(&self, runtime: &mut MoveRuntime) -> Result<()> {
+        runtime.set_resource(Self::address(), Self::struct_tag(), bcs::to_bytes(self)?);
+        Ok(())
+    }
+
+    /// The store as of the block at `height`: staged parameters become current
+    /// at the first block of an epoch. Returns `None` if nothing changes.
+    pub fn activate_at(&self, height: u64) -> Option<Self> {
+        let pending = self.pending.as_ref()?;
+        if !self.current.is_epoch_start(height) {
+            return None;
+        }
+        Some(Self {
+            current: pending.clone(),
+            pending: None,
+        })
+    }
+}
+
+/// Only transactions sent by this account may stage new chain parameters
+pub fn governance_address() -> AccountAddress {
+    AccountAddress::from_hex_literal(KanariAddress::DAO_ADDRESS).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_chain_parameters() {
+        assert!(ChainParameters::default().validate().is_ok());
+
+        let params = ChainParameters {
+            epoch_length: 0,
+            ..Default::default()
+        };
+        assert!(params.validate().is_err());
+
+        let mut params = ChainParameters::default();
+        params.gas_config.max_gas_per_tx = params.gas_config.max_gas_per_block + 1;
+        assert!(params.validate().is_err());
+
+        let mut params = ChainParameters::default();
+        params.gas_config.base_price = params.gas_config.min_gas_price - 1;
+        assert!(params.validate().is_err());
+    }
+
+    #[test]
+    fn test_pending_parameters_activate_at_epoch_start() {
+        let mut store = ChainParamsStore::genesis();
+        assert_eq!(store.activate_at(DEFAULT_EPOCH_LENGTH), None);
+
+        let mut next = ChainParameters::default();
+        next.gas_schedule.transfer = 30_000;
+        store.pending = Some(next.clone());
+
+        assert_eq!(store.activate_at(DEFAULT_EPOCH_LENGTH - 1), None);
+        let activated = store.activate_at(DEFAULT_EPOCH_LENGTH).unwrap();
+        assert_eq!(activated.current, next);
+        assert_eq!(activated.pending, None);
+    }
+
+    #[test]
+    fn test_store_round_trips_through_bcs() {
+        let mut store = ChainParamsStore::genesis();
+        store.pending = Some(ChainParameters::default());
+        let bytes = bcs::to_bytes(&store).unwrap();
+        assert_eq!(bcs::from_bytes::<ChainParamsStore>(&bytes).unwrap(), store);
+    }
+}
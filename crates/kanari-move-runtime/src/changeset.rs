@@ -1,6 +1,8 @@
+use crate::coin::{coin_key, is_kanari};
 use move_core_types::account_address::AccountAddress;
+use move_core_types::language_storage::{StructTag, TypeTag};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// Move VM Event representation
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,6 +20,12 @@ pub struct AccountChange {
     pub balance_delta: i64, // Positive = credit, Negative = debit
     pub sequence_increment: u64,
     pub modules_added: Vec<String>,
+    /// New authentication key, if the account rotated its key
+    #[serde(default)]
+    pub authentication_key: Option<Vec<u8>>,
+    /// Deltas of coins other than KANARI, keyed by coin type
+    #[serde(default)]
+    pub coin_deltas: BTreeMap<String, i64>,
 }
 
 impl AccountChange {
@@ -27,6 +35,8 @@ impl AccountChange {
             balance_delta: 0,
             sequence_increment: 0,
             modules_added: vec![],
+            authentication_key: None,
+            coin_deltas: BTreeMap::new(),
         }
     }
 
@@ -38,6 +48,24 @@ impl AccountChange {
         self.balance_delta += amount as i64;
     }
 
+    /// Debit `amount` of `coin_type`; KANARI goes through `debit`
+    pub fn debit_coin(&mut self, coin_type: &TypeTag, amount: u64) {
+        if is_kanari(coin_type) {
+            self.debit(amount);
+        } else {
+            *self.coin_deltas.entry(coin_key(coin_type)).or_default() -= amount as i64;
+        }
+    }
+
+    /// Credit `amount` of `coin_type`; KANARI goes through `credit`
+    pub fn credit_coin(&mut self, coin_type: &TypeTag, amount: u64) {
+        if is_kanari(coin_type) {
+            self.credit(amount);
+        } else {
+            *self.coin_deltas.entry(coin_key(coin_type)).or_default() += amount as i64;
+        }
+    }
+
     pub fn increment_sequence(&mut self) {
         self.sequence_increment += 1;
     }
@@ -45,6 +73,19 @@ impl AccountChange {
     pub fn add_module(&mut self, module_name: String) {
         self.modules_added.push(module_name);
     }
+
+    pub fn rotate_authentication_key(&mut self, new_key: Vec<u8>) {
+        self.authentication_key = Some(new_key);
+    }
+}
+
+/// Write of a whole resource in Move storage, used for the system stores
+/// (staking pool, governance) that transactions update
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceWrite {
+    pub address: AccountAddress,
+    pub struct_tag: StructTag,
+    pub blob: Vec<u8>,
 }
 
 /// ChangeSet represents all state changes from Move VM execution
@@ -56,6 +97,9 @@ pub struct ChangeSet {
     pub gas_used: u64,
     pub success: bool,
     pub error_message: Option<String>,
+    /// Move storage resources written by the transaction
+    #[serde(default)]
+    pub resource_writes: Vec<ResourceWrite>,
 }
 
 impl ChangeSet {
@@ -66,6 +110,7 @@ impl ChangeSet {
             gas_used: 0,
             success: true,
             error_message: None,
+            resource_writes: Vec::new(),
         }
     }
 
@@ -76,6 +121,7 @@ impl ChangeSet {
             gas_used,
             success: true,
             error_message: None,
+            resource_writes: Vec::new(),
         }
     }
 
@@ -86,6 +132,7 @@ impl ChangeSet {
             gas_used,
             success: false,
             error_message: Some(error),
+            resource_writes: Vec::new(),
         }
     }
 
@@ -105,6 +152,21 @@ impl ChangeSet {
         receiver.credit(amount);
     }
 
+    /// `transfer` of any coin type
+    pub fn transfer_coin(
+        &mut self,
+        from: AccountAddress,
+        to: AccountAddress,
+        coin_type: &TypeTag,
+        amount: u64,
+    ) {
+        let sender = self.get_or_create_change(from);
+        sender.debit_coin(coin_type, amount);
+        sender.increment_sequence();
+
+        self.get_or_create_change(to).credit_coin(coin_type, amount);
+    }
+
     /// Mint operation: create new tokens
     pub fn mint(&mut self, to: AccountAddress, amount: u64) {
         let receiver = self.get_or_create_change(to);
@@ -124,6 +186,13 @@ impl ChangeSet {
         account.increment_sequence();
     }
 
+    /// Authentication key rotation
+    pub fn rotate_authentication_key(&mut self, account: AccountAddress, new_key: Vec<u8>) {
+        let change = self.get_or_create_change(account);
+        change.rotate_authentication_key(new_key);
+        change.increment_sequence();
+    }
+
     /// Collect gas fees to DAO
     pub fn collect_gas(&mut self, dao_address: AccountAddress, gas_amount: u64) {
         let dao = self.get_or_create_change(dao_address);
@@ -134,6 +203,20 @@ impl ChangeSet {
         self.gas_used = gas;
     }
 
+    /// Replace the resource at `address` with `blob`
+    pub fn write_resource(
+        &mut self,
+        address: AccountAddress,
+        struct_tag: StructTag,
+        blob: Vec<u8>,
+    ) {
+        self.resource_writes.push(ResourceWrite {
+            address,
+            struct_tag,
+            blob,
+        });
+    }
+
     pub fn mark_failed(&mut self, error: String) {
         self.success = false;
         self.error_message = Some(error);
@@ -155,8 +238,15 @@ impl ChangeSet {
             existing.balance_delta += other_change.balance_delta;
             existing.sequence_increment += other_change.sequence_increment;
             existing.modules_added.extend(other_change.modules_added);
+            if other_change.authentication_key.is_some() {
+                existing.authentication_key = other_change.authentication_key;
+            }
+            for (coin, delta) in other_change.coin_deltas {
+                *existing.coin_deltas.entry(coin).or_default() += delta;
+            }
         }
         self.events.extend(other.events);
+        self.resource_writes.extend(other.resource_writes);
         self.gas_used += other.gas_used;
         if !other.success {
             self.success = false;
@@ -187,6 +277,27 @@ mod tests {
         assert_eq!(cs.account_changes.get(&from).unwrap().sequence_increment, 1);
     }
 
+    #[test]
+    fn test_changeset_coin_transfer() {
+        use crate::coin::kanari_coin_type;
+        use move_core_types::parser::parse_type_tag;
+
+        let mut cs = ChangeSet::new();
+        let from = AccountAddress::from_hex_literal("0x1").unwrap();
+        let to = AccountAddress::from_hex_literal("0x2").unwrap();
+        let usd = parse_type_tag("0x42::usd::USD").unwrap();
+
+        cs.transfer_coin(from, to, &usd, 100);
+        cs.transfer_coin(from, to, &kanari_coin_type(), 7);
+
+        let sender = cs.account_changes.get(&from).unwrap();
+        assert_eq!(sender.coin_deltas["0x42::usd::USD"], -100);
+        assert_eq!(sender.balance_delta, -7);
+        let receiver = cs.account_changes.get(&to).unwrap();
+        assert_eq!(receiver.coin_deltas["0x42::usd::USD"], 100);
+        assert_eq!(receiver.balance_delta, 7);
+    }
+
     #[test]
     fn test_changeset_mint() {
         let mut cs = ChangeSet::new();
@@ -0,0 +1,172 @@
+use crate::move_runtime::MoveRuntime;
+use crate::move_storage::MoveStorage;
+use crate::object::{Object, Ownership};
+use anyhow::{Context, Result};
+use kanari_types::address::Address as KanariAddress;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::{StructTag, TypeTag};
+use move_core_types::parser::parse_type_tag;
+use serde::{Deserialize, Serialize};
+
+/// Decimals of the native coin: 1 KANARI = 10^9 Mist
+pub const KANARI_DECIMALS: u8 = 9;
+
+/// `0x2::kanari::KANARI`, the native coin that pays for gas
+pub fn kanari_coin_type() -> TypeTag {
+    TypeTag::Struct(Box::new(StructTag {
+        address: AccountAddress::from_hex_literal(KanariAddress::KANARI_SYSTEM_ADDRESS).unwrap(),
+        module: Identifier::new("kanari").unwrap(),
+        name: Identifier::new("KANARI").unwrap(),
+        type_params: vec![],
+    }))
+}
+
+pub fn is_kanari(coin_type: &TypeTag) -> bool {
+    *coin_type == kanari_coin_type()
+}
+
+/// Key of `coin_type` in account balance maps, e.g. `0x2::kanari::KANARI`
+pub fn coin_key(coin_type: &TypeTag) -> String {
+    coin_type.to_string()
+}
+
+/// BCS layout matches `0x2::object::UID`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Uid {
+    pub addr: AccountAddress,
+}
+
+/// BCS layout matches `0x2::coin::Coin<T>`, whose `Balance<T>` is a single `u64`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Coin {
+    pub id: Uid,
+    pub value: u64,
+}
+
+impl Coin {
+    /// `0x2::coin::Coin<coin_type>`
+    pub fn struct_tag(coin_type: &TypeTag) -> StructTag {
+        StructTag {
+            address: AccountAddress::from_hex_literal(KanariAddress::KANARI_SYSTEM_ADDRESS)
+                .unwrap(),
+            module: Identifier::new("coin").unwrap(),
+            name: Identifier::new("Coin").unwrap(),
+            type_params: vec![coin_type.clone()],
+        }
+    }
+
+    /// Coin type and value of `object` if it is a `Coin<T>` of a currency other
+    /// than KANARI sent to an address. Such coins are deposited into the
+    /// owner's balance of `T` rather than kept as objects.
+    pub fn deposit(object: &Object) -> Result<Option<(TypeTag, Self)>> {
+        if object.ownership != Ownership::Address {
+            return Ok(None);
+        }
+        let TypeTag::Struct(tag) = parse_type_tag(&object.type_tag)? else {
+            return Ok(None);
+        };
+        let coin_type = match tag.type_params.as_slice() {
+            [coin_type] if *tag == Self::struct_tag(coin_type) && !is_kanari(coin_type) => {
+                coin_type.clone()
+            }
+            _ => return Ok(None),
+        };
+        let coin = bcs::from_bytes(&object.contents)
+            .with_context(|| format!("Failed to decode coin {}", object.id))?;
+        Ok(Some((coin_type, coin)))
+    }
+}
+
+/// BCS layout matches `0x2::url::Url`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Url {
+    pub url: String,
+}
+
+/// The `0x2::coin::CoinMetadata<T>` value of a currency, kept in Move storage
+/// under the address that defines `T`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CoinMetadata {
+    pub id: Uid,
+    pub decimals: u8,
+    pub name: String,
+    pub symbol: String,
+    pub description: String,
+    pub icon_url: Option<Url>,
+}
+
+impl CoinMetadata {
+    pub const MODULE_NAME: &'static str = "coin";
+    pub const STRUCT_NAME: &'static str = "CoinMetadata";
+
+    /// Metadata of the native coin, written at genesis
+    pub fn kanari() -> Self {
+        Self {
+            id: Uid {
+                addr: AccountAddress::from_hex_literal(KanariAddress::KANARI_SYSTEM_ADDRESS)
+                    .unwrap(),
+            },
+            decimals: KANARI_DECIMALS,
+            name: "Kanari".to_string(),
+            symbol: "KANARI".to_string(),
+            description: "Native coin of the Kanari network".to_string(),
+            icon_url: None,
+        }
+    }
+
+    /// `0x2::coin::CoinMetadata<coin_type>`
+    pub fn struct_tag(coin_type: &TypeTag) -> StructTag {
+        StructTag {
+            address: AccountAddress::from_hex_literal(KanariAddress::KANARI_SYSTEM_ADDRESS)
+                .unwrap(),
+            module: Identifier::new(Self::MODULE_NAME).unwrap(),
+            name: Identifier::new(Self::STRUCT_NAME).unwrap(),
+            type_params: vec![coin_type.clone()],
+        }
+    }
+
+    /// Address the metadata of `coin_type` is stored under: the address of the
+    /// module defining it
+    pub fn address(coin_type: &TypeTag) -> Result<AccountAddress> {
+        match coin_type {
+            TypeTag::Struct(tag) => Ok(tag.address),
+            other => anyhow::bail!("{} is not a coin type", other),
+        }
+    }
+
+    /// Read the metadata of `coin_type`, if the currency registered any
+    pub fn load(storage: &MoveStorage, coin_type: &TypeTag) -> Result<Option<Self>> {
+        let address = Self::address(coin_type)?;
+        storage
+            .resource(&address, &Self::struct_tag(coin_type))
+            .map(|bytes| bcs::from_bytes(bytes).context("Failed to decode coin metadata"))
+            .transpose()
+    }
+
+    /// Write the metadata of `coin_type` into the runtime's Move storage
+    pub fn save(&self, runtime: &mut MoveRuntime, coin_type: &TypeTag) -> Result<()> {
+        runtime.set_resource(
+            Self::address(coin_type)?,
+            Self::struct_tag(coin_type),
+            bcs::to_bytes(self)?,
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_core_types::parser::parse_type_tag;
+
+    #[test]
+    fn test_coin_key_round_trips() {
+        let kanari = kanari_coin_type();
+        assert_eq!(coin_key(&kanari), "0x2::kanari::KANARI");
+        assert_eq!(parse_type_tag(&coin_key(&kanari)).unwrap(), kanari);
+        assert!(is_kanari(&parse_type_tag("0x2::kanari::KANARI").unwrap()));
+        assert!(!is_kanari(&parse_type_tag("0x42::usd::USD").unwrap()));
+        assert!(CoinMetadata::address(&TypeTag::U64).is_err());
+    }
+}
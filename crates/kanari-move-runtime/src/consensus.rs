@@ -0,0 +1,231 @@
+use anyhow::{Context, Result};
+use kanari_crypto::keys::{CurveType, account_address, keypair_from_private_key, public_key_bytes};
+use serde::{Deserialize, Serialize};
+
+/// A registered validator key. The validator is identified by the account address
+/// its public key controls (see `keys::account_address`), which is what block
+/// headers name as the producer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Validator {
+    pub address: String,
+    pub curve_type: CurveType,
+    /// Hex public key; hybrid keys are `classical:pqc`
+    pub public_key: String,
+}
+
+impl Validator {
+    pub fn new(curve_type: CurveType, public_key: &str) -> Result<Self> {
+        let public_key = public_key.trim().to_lowercase();
+        public_key_bytes(curve_type, &public_key)
+            .map_err(|e| anyhow::anyhow!("Invalid validator public key {}: {}", public_key, e))?;
+        let address = account_address(curve_type, &public_key)
+            .map_err(|e| anyhow::anyhow!("Invalid validator public key {}: {}", public_key, e))?;
+        Ok(Self {
+            address: format!("{:#x}", address),
+            curve_type,
+            public_key,
+        })
+    }
+
+    /// Check a signature by this validator's key
+    pub fn verify(&self, message: &[u8], signature: &[u8]) -> Result<bool> {
+        kanari_crypto::verify_signature_with_curve(
+            &self.public_key,
+            message,
+            signature,
+            self.curve_type,
+        )
+        .map_err(|e| anyhow::anyhow!("{}", e))
+    }
+}
+
+/// Proof-of-authority validator set with a round-robin leader schedule.
+///
+/// Validators are registered with their public keys, which block and randomness
+/// signatures are checked against.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidatorSet {
+    validators: Vec<Validator>,
+}
+
+impl ValidatorSet {
+    pub fn new(validators: Vec<Validator>) -> Result<Self> {
+        let mut unique: Vec<Validator> = Vec::with_capacity(validators.len());
+        for validator in validators {
+            if unique.iter().any(|v| v.address == validator.address) {
+                anyhow::bail!("Duplicate validator: {}", validator.address);
+            }
+            unique.push(validator);
+        }
+        if unique.is_empty() {
+            anyhow::bail!("Validator set must not be empty");
+        }
+        Ok(Self { validators: unique })
+    }
+
+    /// Parse a comma-separated list of `<curve>:<public key>` entries, naming
+    /// curves as `parse_curve` accepts them
+    pub fn from_list(list: &str, parse_curve: impl Fn(&str) -> Result<CurveType>) -> Result<Self> {
+        let validators = list
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(|entry| {
+                let (curve, public_key) = entry.split_once(':').ok_or_else(|| {
+                    anyhow::anyhow!("Expected <curve>:<public key>, got '{}'", entry)
+                })?;
+                Validator::new(parse_curve(curve)?, public_key)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Self::new(validators)
+    }
+
+    pub fn validators(&self) -> &[Validator] {
+        &self.validators
+    }
+
+    pub fn len(&self) -> usize {
+        self.validators.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.validators.is_empty()
+    }
+
+    pub fn contains(&self, address: &str) -> bool {
+        self.get(address).is_some()
+    }
+
+    /// The registered validator with `address`
+    pub fn get(&self, address: &str) -> Option<&Validator> {
+        let address = normalize_address(address);
+        self.validators.iter().find(|v| v.address == address)
+    }
+
+    /// Validator expected to produce the block at `height`
+    pub fn leader_for(&self, height: u64) -> &str {
+        let index = (height % self.validators.len() as u64) as usize;
+        &self.validators[index].address
+    }
+
+    pub fn is_leader(&self, address: &str, height: u64) -> bool {
+        self.leader_for(height) == normalize_address(address)
+    }
+}
+
+/// Local validator key used to sign produced blocks
+#[derive(Clone)]
+pub struct BlockSigner {
+    pub address: String,
+    private_key: String,
+    pub curve_type: CurveType,
+    pub public_key: String,
+}
+
+impl BlockSigner {
+    pub fn new(private_key: &str, curve_type: CurveType) -> Result<Self> {
+        let keypair = keypair_from_private_key(private_key, curve_type)
+            .map_err(|e| anyhow::anyhow!("Invalid validator key: {}", e))?;
+        let validator = Validator::new(curve_type, &keypair.public_key)?;
+        Ok(Self {
+            address: validator.address,
+            private_key: keypair.private_key,
+            curve_type,
+            public_key: validator.public_key,
+        })
+    }
+
+    /// Registration of this key in a `ValidatorSet`
+    pub fn validator(&self) -> Validator {
+        Validator {
+            address: self.address.clone(),
+            curve_type: self.curve_type,
+            public_key: self.public_key.clone(),
+        }
+    }
+
+    pub fn sign(&self, message: &[u8]) -> Result<Vec<u8>> {
+        kanari_crypto::sign_message(&self.private_key, message, self.curve_type)
+            .map_err(|e| anyhow::anyhow!("{}", e))
+            .context("Failed to sign block")
+    }
+}
+
+impl std::fmt::Debug for BlockSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Never print the private key
+        f.debug_struct("BlockSigner")
+            .field("address", &self.address)
+            .field("curve_type", &self.curve_type)
+            .finish()
+    }
+}
+
+fn normalize_address(address: &str) -> String {
+    let address = address.trim().to_lowercase();
+    if address.starts_with("0x") {
+        address
+    } else {
+        format!("0x{}", address)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_curve(name: &str) -> Result<CurveType> {
+        match name {
+            "ed25519" => Ok(CurveType::Ed25519),
+            "dilithium3" => Ok(CurveType::Dilithium3),
+            other => anyhow::bail!("Unsupported curve '{}'", other),
+        }
+    }
+
+    fn key(curve_type: CurveType) -> String {
+        kanari_crypto::keys::generate_keypair(curve_type)
+            .unwrap()
+            .public_key
+    }
+
+    #[test]
+    fn test_round_robin_leader() {
+        let keys = [key(CurveType::Ed25519), key(CurveType::Ed25519)];
+        let set = ValidatorSet::from_list(
+            &format!("ed25519:{}, ED25519:{}", keys[0], keys[1].to_uppercase()),
+            |name| parse_curve(&name.to_lowercase()),
+        )
+        .unwrap();
+        let [a, b] = [&set.validators()[0].address, &set.validators()[1].address];
+        assert_eq!(set.len(), 2);
+        assert_eq!(set.leader_for(0), a);
+        assert_eq!(set.leader_for(5), b);
+        assert!(set.is_leader(&a.to_uppercase().replace("0X", "0x"), 2));
+        assert!(set.contains(b.trim_start_matches("0x")));
+        assert!(!set.contains("0xdd"));
+    }
+
+    #[test]
+    fn test_post_quantum_validator() {
+        let keypair = kanari_crypto::keys::generate_keypair(CurveType::Dilithium3).unwrap();
+        let signer = BlockSigner::new(&keypair.private_key, CurveType::Dilithium3).unwrap();
+        let set =
+            ValidatorSet::from_list(&format!("dilithium3:{}", keypair.public_key), parse_curve)
+                .unwrap();
+
+        let validator = set.get(&signer.address).unwrap();
+        assert_eq!(validator, &signer.validator());
+        let signature = signer.sign(b"block").unwrap();
+        assert!(validator.verify(b"block", &signature).unwrap());
+        assert!(!validator.verify(b"other", &signature).unwrap());
+    }
+
+    #[test]
+    fn test_invalid_validator_sets() {
+        let a = key(CurveType::Ed25519);
+        assert!(ValidatorSet::from_list("", parse_curve).is_err());
+        assert!(ValidatorSet::from_list(&format!("ed25519:{a},ed25519:{a}"), parse_curve).is_err());
+        assert!(ValidatorSet::from_list("ed25519:zz", parse_curve).is_err());
+        assert!(ValidatorSet::from_list(&a, parse_curve).is_err());
+    }
+}
@@ -1,4 +1,11 @@
 use anyhow::Result;
+use move_binary_format::{
+    CompiledModule,
+    file_format::{
+        Ability, AbilitySet, SignatureToken, StructFieldInformation, StructHandleIndex,
+        StructTypeParameter, Visibility,
+    },
+};
 use move_core_types::{
     account_address::AccountAddress,
     identifier::Identifier,
@@ -19,7 +26,7 @@ pub struct ContractInfo {
     /// Module bytecode
     pub bytecode: Vec<u8>,
 
-    /// Deployment transaction hash
+    /// Hash of the signed deployment transaction, as returned by `submit_transaction`
     pub deployment_tx: Vec<u8>,
 
     /// Block height when deployed
@@ -64,6 +71,195 @@ impl ContractABI {
     pub fn list_functions(&self) -> Vec<String> {
         self.functions.iter().map(|f| f.name.clone()).collect()
     }
+
+    /// Derive the ABI from module bytecode
+    pub fn from_bytecode(bytecode: &[u8]) -> Result<Self> {
+        let module = CompiledModule::deserialize_with_defaults(bytecode)
+            .map_err(|e| anyhow::anyhow!("Invalid module bytecode: {:?}", e))?;
+        Ok(Self::from_module(&module))
+    }
+
+    /// Derive the ABI from a compiled module.
+    ///
+    /// Only functions callable from outside the module (public or entry) are
+    /// listed; all struct definitions are included since their layouts are
+    /// needed to decode resources and events.
+    pub fn from_module(module: &CompiledModule) -> Self {
+        let mut abi = Self::new();
+
+        for def in module.function_defs() {
+            if def.visibility != Visibility::Public && !def.is_entry {
+                continue;
+            }
+            let handle = module.function_handle_at(def.function);
+            let parameters = module
+                .signature_at(handle.parameters)
+                .0
+                .iter()
+                .enumerate()
+                .map(|(i, token)| ParameterInfo {
+                    name: format!("arg{}", i),
+                    type_name: format_type(module, token),
+                })
+                .collect();
+            let returns = module
+                .signature_at(handle.return_)
+                .0
+                .iter()
+                .map(|token| format_type(module, token))
+                .collect();
+            let type_params = handle
+                .type_parameters
+                .iter()
+                .enumerate()
+                .map(|(i, constraints)| format_type_param(i, *constraints, false))
+                .collect();
+
+            abi.add_function(FunctionSignature {
+                name: module.identifier_at(handle.name).to_string(),
+                is_entry: def.is_entry,
+                is_view: is_view_function(module, def.visibility, handle),
+                visibility: format_visibility(def.visibility).to_string(),
+                type_params,
+                parameters,
+                returns,
+                doc: None,
+            });
+        }
+
+        for def in module.struct_defs() {
+            let handle = module.struct_handle_at(def.struct_handle);
+            let fields = match &def.field_information {
+                StructFieldInformation::Native => Vec::new(),
+                StructFieldInformation::Declared(fields) => fields
+                    .iter()
+                    .map(|field| FieldInfo {
+                        name: module.identifier_at(field.name).to_string(),
+                        type_name: format_type(module, &field.signature.0),
+                    })
+                    .collect(),
+            };
+            let type_params = handle
+                .type_parameters
+                .iter()
+                .enumerate()
+                .map(
+                    |(
+                        i,
+                        StructTypeParameter {
+                            constraints,
+                            is_phantom,
+                        },
+                    )| { format_type_param(i, *constraints, *is_phantom) },
+                )
+                .collect();
+
+            abi.structs.push(StructSignature {
+                name: module.identifier_at(handle.name).to_string(),
+                type_params,
+                fields,
+                abilities: format_abilities(handle.abilities),
+            });
+        }
+
+        abi
+    }
+
+    /// Find struct by name
+    pub fn get_struct(&self, name: &str) -> Option<&StructSignature> {
+        self.structs.iter().find(|s| s.name == name)
+    }
+}
+
+/// A view function is public, returns at least one value, and cannot modify state
+/// through its arguments: it takes no `signer` and no mutable references
+fn is_view_function(
+    module: &CompiledModule,
+    visibility: Visibility,
+    handle: &move_binary_format::file_format::FunctionHandle,
+) -> bool {
+    visibility == Visibility::Public
+        && !module.signature_at(handle.return_).0.is_empty()
+        && module.signature_at(handle.parameters).0.iter().all(|token| {
+            !matches!(
+                token,
+                SignatureToken::Signer | SignatureToken::MutableReference(_)
+            ) && !matches!(token, SignatureToken::Reference(inner) if **inner == SignatureToken::Signer)
+        })
+}
+
+/// Render a signature token the way it is written in Move source, with
+/// struct types fully qualified (e.g. `0x2::coin::Coin<T0>`)
+fn format_type(module: &CompiledModule, token: &SignatureToken) -> String {
+    match token {
+        SignatureToken::Bool => "bool".to_string(),
+        SignatureToken::U8 => "u8".to_string(),
+        SignatureToken::U16 => "u16".to_string(),
+        SignatureToken::U32 => "u32".to_string(),
+        SignatureToken::U64 => "u64".to_string(),
+        SignatureToken::U128 => "u128".to_string(),
+        SignatureToken::U256 => "u256".to_string(),
+        SignatureToken::Address => "address".to_string(),
+        SignatureToken::Signer => "signer".to_string(),
+        SignatureToken::Vector(inner) => format!("vector<{}>", format_type(module, inner)),
+        SignatureToken::Struct(idx) => format_struct_name(module, *idx),
+        SignatureToken::StructInstantiation(inst) => {
+            let (idx, type_args) = inst.as_ref();
+            let args: Vec<String> = type_args.iter().map(|t| format_type(module, t)).collect();
+            format!("{}<{}>", format_struct_name(module, *idx), args.join(", "))
+        }
+        SignatureToken::Reference(inner) => format!("&{}", format_type(module, inner)),
+        SignatureToken::MutableReference(inner) => format!("&mut {}", format_type(module, inner)),
+        SignatureToken::TypeParameter(i) => format!("T{}", i),
+    }
+}
+
+fn format_struct_name(module: &CompiledModule, idx: StructHandleIndex) -> String {
+    let handle = module.struct_handle_at(idx);
+    let module_id = module.module_id_for_handle(module.module_handle_at(handle.module));
+    format!(
+        "0x{}::{}::{}",
+        module_id.address().short_str_lossless(),
+        module_id.name(),
+        module.identifier_at(handle.name)
+    )
+}
+
+fn format_type_param(index: usize, constraints: AbilitySet, is_phantom: bool) -> String {
+    let mut param = if is_phantom {
+        format!("phantom T{}", index)
+    } else {
+        format!("T{}", index)
+    };
+    let abilities = format_abilities(constraints);
+    if !abilities.is_empty() {
+        param.push_str(": ");
+        param.push_str(&abilities.join(" + "));
+    }
+    param
+}
+
+fn format_abilities(abilities: AbilitySet) -> Vec<String> {
+    abilities
+        .into_iter()
+        .map(|ability| {
+            match ability {
+                Ability::Copy => "copy",
+                Ability::Drop => "drop",
+                Ability::Store => "store",
+                Ability::Key => "key",
+            }
+            .to_string()
+        })
+        .collect()
+}
+
+fn format_visibility(visibility: Visibility) -> &'static str {
+    match visibility {
+        Visibility::Public => "public",
+        Visibility::Friend => "friend",
+        Visibility::Private => "private",
+    }
 }
 
 impl Default for ContractABI {
@@ -81,6 +277,14 @@ pub struct FunctionSignature {
     /// Is entry function (can be called externally)
     pub is_entry: bool,
 
+    /// Can be called read-only through `kanari_view`
+    #[serde(default)]
+    pub is_view: bool,
+
+    /// Declared visibility: `public`, `friend` or `private`
+    #[serde(default)]
+    pub visibility: String,
+
     /// Type parameters
     pub type_params: Vec<String>,
 
@@ -105,6 +309,9 @@ pub struct ParameterInfo {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StructSignature {
     pub name: String,
+    /// Type parameters with constraints, e.g. `phantom T0: store`
+    #[serde(default)]
+    pub type_params: Vec<String>,
     pub fields: Vec<FieldInfo>,
     pub abilities: Vec<String>,
 }
@@ -183,6 +390,11 @@ pub struct ContractRegistry {
 
     /// Map: address -> list of module names
     address_modules: HashMap<String, Vec<String>>,
+
+    /// Earlier versions of republished modules, oldest first, restored when
+    /// the blocks that replaced them are reverted
+    #[serde(default)]
+    superseded: HashMap<(String, String), Vec<ContractInfo>>,
 }
 
 impl ContractRegistry {
@@ -190,6 +402,7 @@ impl ContractRegistry {
         Self {
             contracts: HashMap::new(),
             address_modules: HashMap::new(),
+            superseded: HashMap::new(),
         }
     }
 
@@ -197,14 +410,19 @@ impl ContractRegistry {
     pub fn register(&mut self, contract: ContractInfo) {
         let key = (contract.address.clone(), contract.module_name.clone());
 
-        // Add to address_modules index
-        self.address_modules
+        // Add to address_modules index (a republished module replaces its entry)
+        let modules = self
+            .address_modules
             .entry(contract.address.clone())
-            .or_insert_with(Vec::new)
-            .push(contract.module_name.clone());
+            .or_insert_with(Vec::new);
+        if !modules.contains(&contract.module_name) {
+            modules.push(contract.module_name.clone());
+        }
 
-        // Add to contracts
-        self.contracts.insert(key, contract);
+        // Add to contracts, remembering the version it replaces
+        if let Some(previous) = self.contracts.insert(key.clone(), contract) {
+            self.superseded.entry(key).or_default().push(previous);
+        }
     }
 
     /// Get contract info
@@ -238,6 +456,33 @@ impl ContractRegistry {
             .collect()
     }
 
+    /// Drop contracts deployed above `height`, e.g. when their blocks are reverted.
+    /// A republished module falls back to the version it replaced.
+    pub fn remove_deployed_after(&mut self, height: u64) {
+        let reverted: Vec<_> = self
+            .contracts
+            .iter()
+            .filter(|(_, c)| c.deployed_at > height)
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in reverted {
+            self.contracts.remove(&key);
+            let versions = self.superseded.entry(key.clone()).or_default();
+            while let Some(previous) = versions.pop() {
+                if previous.deployed_at <= height {
+                    self.contracts.insert(key.clone(), previous);
+                    break;
+                }
+            }
+        }
+        self.superseded.retain(|_, versions| !versions.is_empty());
+        for (address, modules) in self.address_modules.iter_mut() {
+            modules.retain(|m| self.contracts.contains_key(&(address.clone(), m.clone())));
+        }
+        self.address_modules
+            .retain(|_, modules| !modules.is_empty());
+    }
+
     /// Get total number of contracts
     pub fn count(&self) -> usize {
         self.contracts.len()
@@ -394,6 +639,8 @@ mod tests {
         let func = FunctionSignature {
             name: "transfer".to_string(),
             is_entry: true,
+            is_view: false,
+            visibility: "public".to_string(),
             type_params: vec![],
             parameters: vec![
                 ParameterInfo {
@@ -416,6 +663,49 @@ mod tests {
         assert_eq!(abi.list_functions(), vec!["transfer"]);
     }
 
+    #[test]
+    fn test_abi_from_bytecode() -> Result<()> {
+        let package: serde_json::Value = serde_json::from_str(include_str!(
+            "../../kanari-frameworks/released/latest/0x2/package.rpd"
+        ))?;
+        let coin = package["modules"]
+            .as_array()
+            .and_then(|modules| modules.iter().find(|m| m["name"] == "coin"))
+            .and_then(|m| m["bytecode"].as_str())
+            .expect("coin module in framework package");
+        let abi = ContractABI::from_bytecode(&hex::decode(coin)?)?;
+
+        let create = abi.get_function("create_currency").unwrap();
+        assert_eq!(create.visibility, "public");
+        assert!(!create.is_view);
+        assert!(abi.get_function("value").unwrap().is_view);
+        assert_eq!(create.type_params, vec!["T0: drop"]);
+        assert_eq!(create.parameters[0].type_name, "T0");
+        assert_eq!(create.parameters[1].type_name, "u8");
+        assert_eq!(
+            create.parameters.last().unwrap().type_name,
+            "&mut 0x2::tx_context::TxContext"
+        );
+        assert_eq!(
+            create.returns,
+            vec!["0x2::coin::TreasuryCap<T0>", "0x2::coin::CoinMetadata<T0>"]
+        );
+        assert!(
+            abi.functions
+                .iter()
+                .all(|f| f.visibility == "public" || f.is_entry)
+        );
+
+        let coin = abi.get_struct("Coin").unwrap();
+        assert_eq!(coin.type_params, vec!["phantom T0"]);
+        assert_eq!(coin.abilities, vec!["drop", "store"]);
+        assert_eq!(coin.fields[0].name, "balance");
+        assert_eq!(coin.fields[0].type_name, "0x2::balance::Balance<T0>");
+
+        assert!(ContractABI::from_bytecode(&[1, 2, 3]).is_err());
+        Ok(())
+    }
+
     #[test]
     fn test_contract_registry() {
         let mut registry = ContractRegistry::new();
@@ -434,11 +724,31 @@ mod tests {
             ),
         };
 
-        registry.register(contract);
+        registry.register(contract.clone());
 
         assert_eq!(registry.count(), 1);
         assert!(registry.get_contract("0x1", "coin").is_some());
         assert_eq!(registry.get_contracts_by_address("0x1").len(), 1);
+
+        // Reverting a republish restores the earlier version
+        registry.register(ContractInfo {
+            bytecode: vec![7, 8, 9],
+            deployed_at: 120,
+            ..contract
+        });
+        assert_eq!(
+            registry.get_contract("0x1", "coin").unwrap().deployed_at,
+            120
+        );
+        registry.remove_deployed_after(110);
+        let restored = registry.get_contract("0x1", "coin").unwrap();
+        assert_eq!(
+            (restored.deployed_at, restored.bytecode.clone()),
+            (100, vec![1, 2, 3])
+        );
+        registry.remove_deployed_after(50);
+        assert_eq!(registry.count(), 0);
+        assert!(registry.get_contracts_by_address("0x1").is_empty());
     }
 
     #[test]
@@ -1,57 +1,320 @@
-use crate::blockchain::{Block, Blockchain, SignedTransaction, Transaction};
+use crate::blockchain::{Block, BlockHeader, Blockchain, SignedTransaction, Transaction};
+use crate::chain_params::{ChainParameters, ChainParamsStore, governance_address};
 use crate::changeset::{ChangeSet, Event};
-use crate::contract::{ContractCall, ContractDeployment, ContractInfo, ContractRegistry};
-use crate::gas::{GasMeter, GasOperation};
+use crate::coin::{CoinMetadata, is_kanari, kanari_coin_type};
+use crate::consensus::{BlockSigner, ValidatorSet};
+use crate::contract::{
+    ContractABI, ContractCall, ContractDeployment, ContractInfo, ContractMetadata, ContractRegistry,
+};
+use crate::fork_choice::BlockTree;
+use crate::gas::{GasError, GasMeter, GasOperation, GasSchedule, MoveGasMeter};
+use crate::governance::{GovernanceAction, GovernanceStore, PROPOSAL_THRESHOLD, ProposalAction};
 use crate::move_runtime::MoveRuntime;
+use crate::move_storage::MoveStorage;
+use crate::nft::{Collection, Nft};
+use crate::object::{CallInputs, Object, Ownership, TxContext};
+use crate::pruning::{DEFAULT_STATE_HISTORY, PruneStats, RetentionMode};
+use crate::random::{self, DeferredCalls, RandomBeacon};
+use crate::snapshot::{StateSnapshot, compute_state_root};
+use crate::staking::{StakingAction, StakingPool, staker_tip_share};
 use crate::state::StateManager;
+use crate::view::{MoveResource, ViewResult};
 use anyhow::{Context, Result};
+use kanari_crypto::keys::{AUTHENTICATION_KEY_LENGTH, CurveType};
 use kanari_types::address::Address as KanariAddress;
-use move_core_types::{account_address::AccountAddress, language_storage::ModuleId};
+use move_core_types::{
+    account_address::AccountAddress,
+    language_storage::ModuleId,
+    parser::{parse_struct_tag, parse_type_tag},
+};
 use serde::{Deserialize, Serialize};
-use std::sync::{Arc, RwLock};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::mpsc::{Receiver, Sender, channel};
+use std::sync::{Arc, Mutex, RwLock};
 
 /// Complete blockchain engine with Move VM integration
 pub struct BlockchainEngine {
     pub blockchain: Arc<RwLock<Blockchain>>,
     pub state: Arc<RwLock<StateManager>>,
     pub move_runtime: Arc<RwLock<MoveRuntime>>,
-    pub pending_txs: Arc<RwLock<Vec<Transaction>>>,
+    pub pending_txs: Arc<RwLock<Vec<SignedTransaction>>>,
     pub contract_registry: Arc<RwLock<ContractRegistry>>,
+    /// Metadata supplied to `deploy_contract`, keyed by transaction hash until the publish commits
+    pending_contract_metadata: Arc<RwLock<HashMap<Vec<u8>, ContractMetadata>>>,
+    /// State as of recent block heights, used for snapshot export, historical
+    /// views and reorgs. Shared so readers don't copy whole states.
+    pub state_history: Arc<RwLock<BTreeMap<u64, Arc<StateSnapshot>>>>,
+    /// Most entries `state_history` keeps, independently of block retention
+    pub state_history_limit: Arc<RwLock<usize>>,
+    /// How much block and state history `prune` keeps
+    pub retention: Arc<RwLock<RetentionMode>>,
+    /// Known blocks on non-canonical branches, for fork choice
+    pub block_tree: Arc<RwLock<BlockTree>>,
+    /// Local validator key for proof-of-authority block production
+    pub block_signer: Arc<RwLock<Option<BlockSigner>>>,
+    /// Listeners notified of accepted transactions and blocks (e.g. P2P gossip)
+    subscribers: Arc<Mutex<Vec<Sender<ChainEvent>>>>,
+    /// Receipts of transactions in canonical blocks, keyed by hex transaction hash
+    receipts: Arc<RwLock<HashMap<String, TransactionReceipt>>>,
 }
 
 impl BlockchainEngine {
     pub fn new() -> Result<Self> {
+        Self::genesis(None)
+    }
+
+    /// Start a chain whose dev account, holding the genesis supply, is controlled
+    /// by the key with `authentication_key`, e.g. for local networks and tests
+    pub fn with_dev_authentication_key(authentication_key: Vec<u8>) -> Result<Self> {
+        Self::genesis(Some(authentication_key))
+    }
+
+    fn genesis(dev_authentication_key: Option<Vec<u8>>) -> Result<Self> {
         let blockchain = Arc::new(RwLock::new(Blockchain::new()));
         let state = Arc::new(RwLock::new(StateManager::new()));
         let move_runtime = Arc::new(RwLock::new(MoveRuntime::new()?));
         let pending_txs = Arc::new(RwLock::new(Vec::new()));
         let contract_registry = Arc::new(RwLock::new(ContractRegistry::new()));
 
-        Ok(Self {
+        let engine = Self {
             blockchain,
             state,
             move_runtime,
             pending_txs,
             contract_registry,
-        })
+            pending_contract_metadata: Arc::new(RwLock::new(HashMap::new())),
+            state_history: Arc::new(RwLock::new(BTreeMap::new())),
+            state_history_limit: Arc::new(RwLock::new(DEFAULT_STATE_HISTORY)),
+            retention: Arc::new(RwLock::new(RetentionMode::default())),
+            block_tree: Arc::new(RwLock::new(BlockTree::default())),
+            block_signer: Arc::new(RwLock::new(None)),
+            subscribers: Arc::new(Mutex::new(Vec::new())),
+            receipts: Arc::new(RwLock::new(HashMap::new())),
+        };
+
+        // Chain parameters live in Move storage, so they are covered by the state root
+        {
+            let mut runtime = engine.move_runtime.write().unwrap();
+            ChainParamsStore::genesis().save(&mut runtime)?;
+            CoinMetadata::kanari().save(&mut runtime, &kanari_coin_type())?;
+            StakingPool::default().save(&mut runtime)?;
+            GovernanceStore::default().save(&mut runtime)?;
+        }
+        if let Some(authentication_key) = dev_authentication_key {
+            let dev = AccountAddress::from_hex_literal(KanariAddress::DEV_ADDRESS)?;
+            engine
+                .state
+                .write()
+                .unwrap()
+                .get_or_create_account(dev)
+                .authentication_key = Some(authentication_key);
+        }
+
+        // Commit genesis state into the genesis header
+        let genesis_root = engine.compute_state_root();
+        {
+            let mut chain = engine.blockchain.write().unwrap();
+            chain.blocks[0].header.state_root = genesis_root;
+        }
+        engine.record_state_snapshot();
+
+        Ok(engine)
+    }
+
+    /// Root over the current account state and Move storage
+    pub fn compute_state_root(&self) -> Vec<u8> {
+        let state = self.state.read().unwrap();
+        let runtime = self.move_runtime.read().unwrap();
+        compute_state_root(&state, runtime.storage())
+    }
+
+    /// Remember the current state under the latest block height, forgetting the
+    /// oldest states beyond `state_history_limit`
+    fn record_state_snapshot(&self) {
+        let header = self
+            .blockchain
+            .read()
+            .unwrap()
+            .latest_block()
+            .header
+            .clone();
+        let snapshot = {
+            let state = self.state.read().unwrap();
+            let runtime = self.move_runtime.read().unwrap();
+            StateSnapshot::capture(header, &state, runtime.storage())
+        };
+        let limit = (*self.state_history_limit.read().unwrap()).max(1);
+        let mut history = self.state_history.write().unwrap();
+        history.insert(snapshot.height(), Arc::new(snapshot));
+        while history.len() > limit {
+            history.pop_first();
+        }
+    }
+
+    /// Enable proof-of-authority: only scheduled validators may produce blocks
+    pub fn set_validators(&self, validators: ValidatorSet) {
+        self.blockchain.write().unwrap().validators = Some(validators);
+    }
+
+    /// Key used to sign blocks produced by this node
+    pub fn set_block_signer(&self, signer: BlockSigner) {
+        *self.block_signer.write().unwrap() = Some(signer);
+    }
+
+    /// Whether this node may produce the next block. Always true without a validator set.
+    pub fn is_leader_for_next_block(&self) -> bool {
+        let chain = self.blockchain.read().unwrap();
+        let Some(validators) = &chain.validators else {
+            return true;
+        };
+        let signer = self.block_signer.read().unwrap();
+        signer
+            .as_ref()
+            .is_some_and(|s| validators.is_leader(&s.address, chain.height() + 1))
+    }
+
+    /// Keep at most `limit` historical state versions (at least one). It should
+    /// exceed the finality depth, or deep reorgs can't find their common ancestor.
+    pub fn set_state_history_limit(&self, limit: usize) {
+        *self.state_history_limit.write().unwrap() = limit.max(1);
+    }
+
+    pub fn set_retention_mode(&self, mode: RetentionMode) {
+        *self.retention.write().unwrap() = mode;
+    }
+
+    /// Drop blocks, block bodies and historical state versions that fall outside
+    /// the configured retention mode. Intended to be run periodically in the background.
+    pub fn prune(&self) -> PruneStats {
+        let mode = *self.retention.read().unwrap();
+        let mut stats = PruneStats::default();
+
+        let mut chain = self.blockchain.write().unwrap();
+        let tip = chain.height();
+        let Some(cutoff) = mode.cutoff(tip) else {
+            return stats;
+        };
+
+        match mode {
+            RetentionMode::Archive => {}
+            RetentionMode::KeepLast(_) => {
+                stats.blocks_removed = chain.prune_blocks_below(cutoff);
+            }
+            RetentionMode::Checkpoints { .. } => {
+                stats.bodies_pruned = chain.prune_bodies_below(cutoff);
+            }
+        }
+        drop(chain);
+
+        let mut history = self.state_history.write().unwrap();
+        let before = history.len();
+        history.retain(|height, _| mode.keeps_state(*height, tip));
+        stats.states_removed = before - history.len();
+
+        stats
+    }
+
+    /// Export the state as of block `height`
+    pub fn export_snapshot(&self, height: u64) -> Result<Arc<StateSnapshot>> {
+        self.state_history
+            .read()
+            .unwrap()
+            .get(&height)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("No state available at height {}", height))
+    }
+
+    /// Replace the local chain and state with a verified snapshot.
+    /// The node continues producing/following blocks from the snapshot height.
+    pub fn import_snapshot(&self, snapshot: StateSnapshot) -> Result<()> {
+        snapshot.verify().context("Snapshot verification failed")?;
+
+        self.restore_state(&snapshot)?;
+        *self.block_tree.write().unwrap() = BlockTree::default();
+        {
+            let mut chain = self.blockchain.write().unwrap();
+            let validators = chain.validators.take();
+            *chain = Blockchain::from_header(snapshot.header.clone());
+            chain.validators = validators;
+        }
+        self.pending_txs.write().unwrap().clear();
+
+        let mut history = self.state_history.write().unwrap();
+        history.clear();
+        history.insert(snapshot.height(), Arc::new(snapshot));
+        Ok(())
+    }
+
+    /// Reset account state and Move storage to a recorded snapshot
+    fn restore_state(&self, snapshot: &StateSnapshot) -> Result<()> {
+        self.move_runtime
+            .write()
+            .unwrap()
+            .restore_storage(snapshot.to_move_storage())
+            .context("Failed to restore Move storage from snapshot")?;
+        *self.state.write().unwrap() = snapshot.to_state();
+        Ok(())
+    }
+
+    /// Receive a `ChainEvent` for every transaction accepted into the mempool and
+    /// every new block, whether produced locally or imported from another node
+    pub fn subscribe(&self) -> Receiver<ChainEvent> {
+        let (sender, receiver) = channel();
+        self.subscribers.lock().unwrap().push(sender);
+        receiver
+    }
+
+    fn notify(&self, event: ChainEvent) {
+        // Drop listeners whose receiver has gone away
+        self.subscribers
+            .lock()
+            .unwrap()
+            .retain(|subscriber| subscriber.send(event.clone()).is_ok());
     }
 
     /// Add signed transaction to pending pool after verifying signature
     pub fn submit_transaction(&self, signed_tx: SignedTransaction) -> Result<Vec<u8>> {
-        // Verify signature before accepting transaction
-        if !signed_tx.verify_signature()? {
-            anyhow::bail!("Invalid transaction signature");
-        }
+        self.verify_transaction(&signed_tx)?;
+        let params = ChainParamsStore::load(self.move_runtime.read().unwrap().storage())?.current;
+        check_intrinsic_gas(&signed_tx, &params.gas_schedule)?;
 
         let tx_hash = signed_tx.hash();
-        let mut pending = self.pending_txs.write().unwrap();
-        pending.push(signed_tx.transaction);
+        {
+            let mut pending = self.pending_txs.write().unwrap();
+            let inner_hash = signed_tx.transaction.hash();
+            if pending.iter().any(|tx| tx.transaction.hash() == inner_hash) {
+                anyhow::bail!("Transaction {} is already pending", hex::encode(&tx_hash));
+            }
+            pending.push(signed_tx.clone());
+        }
+        self.notify(ChainEvent::Transaction(signed_tx));
         Ok(tx_hash)
     }
 
+    /// Verify the signature against the sender's current authentication key
+    fn verify_transaction(&self, signed_tx: &SignedTransaction) -> Result<()> {
+        let auth_key = {
+            let state = self.state.read().unwrap();
+            state
+                .get_account_by_hex(signed_tx.transaction.sender_address())
+                .and_then(|acc| acc.authentication_key.clone())
+        };
+        if !signed_tx.verify_authentication(auth_key.as_deref())? {
+            anyhow::bail!("Invalid transaction signature");
+        }
+        Ok(())
+    }
+
     /// Execute a single transaction and return ChangeSet
     /// This is the correct way: Move VM produces ChangeSet, StateManager applies it
-    fn execute_transaction(&self, tx: &Transaction) -> Result<ChangeSet> {
+    /// `random_seed` seeds `0x2::random` for a call deferred from the parent block.
+    fn execute_transaction(
+        &self,
+        signed_tx: &SignedTransaction,
+        block: &BlockContext,
+        random_seed: Option<Vec<u8>>,
+    ) -> Result<ChangeSet> {
+        let tx = &signed_tx.transaction;
         // 1. Pre-flight validation: Check sequence number
         let sender_addr = AccountAddress::from_hex_literal(tx.sender_address())?;
         {
@@ -61,9 +324,34 @@ impl BlockchainEngine {
                 .context("Sequence number validation failed")?;
         }
 
-        // 2. Calculate gas and check balance
-        let mut gas_meter = GasMeter::new(tx.gas_limit(), tx.gas_price());
+        // 2. Calculate gas and pre-authorize `gas_limit * gas_price` from the sender.
+        // Only the gas actually used is charged, at the base fee plus tip.
+        let effective_gas_price = tx.effective_gas_price(block.base_fee).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Max fee {} is below the base fee {}",
+                tx.gas_price(),
+                block.base_fee
+            )
+        })?;
+        let max_gas_per_tx = block.params.gas_config.max_gas_per_tx;
+        if tx.gas_limit() > max_gas_per_tx {
+            anyhow::bail!(
+                "Gas limit {} exceeds the maximum of {} per transaction",
+                tx.gas_limit(),
+                max_gas_per_tx
+            );
+        }
+        let schedule = &block.params.gas_schedule;
+        let mut gas_meter = GasMeter::new(tx.gas_limit(), effective_gas_price);
         let mut changeset = ChangeSet::new();
+        let max_gas_cost = tx.gas_limit().saturating_mul(tx.gas_price());
+
+        // The base operation and the signature are paid up front. Larger
+        // (post-quantum) signatures cost more to carry and verify
+        if let Err(e) = gas_meter.consume(intrinsic_gas(signed_tx, schedule)) {
+            fail_out_of_gas(&mut changeset, sender_addr, &mut gas_meter, block, e)?;
+            return Ok(changeset);
+        }
 
         match tx {
             Transaction::PublishModule {
@@ -72,55 +360,39 @@ impl BlockchainEngine {
                 module_name: _,
                 ..
             } => {
-                // Calculate gas for publishing
-                let gas_op = GasOperation::PublishModule {
-                    module_size: module_bytes.len(),
-                };
-                gas_meter.consume(gas_op.gas_units())?;
-
                 let addr = AccountAddress::from_hex_literal(sender)?;
 
-                // Check if sender has enough balance for gas
-                let gas_cost = gas_meter.total_cost();
+                // Check if sender can cover the gas limit
                 {
                     let state = self.state.read().unwrap();
                     let balance = state.get_account(&addr).map(|acc| acc.balance).unwrap_or(0);
-                    if balance < gas_cost {
+                    if balance < max_gas_cost {
                         changeset.mark_failed(format!(
                             "Insufficient balance for gas: need {}, have {}",
-                            gas_cost, balance
+                            max_gas_cost, balance
                         ));
 
                         // CRITICAL: Even pre-flight failures must deduct gas and increment sequence
-                        let sender_change = changeset.get_or_create_change(addr);
-                        sender_change.increment_sequence(); // Prevent replay
-                        sender_change.debit(gas_cost);
-
-                        let dao_addr =
-                            AccountAddress::from_hex_literal(KanariAddress::DAO_ADDRESS)?;
-                        changeset.collect_gas(dao_addr, gas_cost);
-                        changeset.set_gas_used(gas_meter.gas_used);
+                        changeset.get_or_create_change(addr).increment_sequence(); // Prevent replay
+                        charge_gas(&mut changeset, addr, &gas_meter, block)?;
                         return Ok(changeset);
                     }
                 }
 
-                // Execute Move VM
+                // Execute Move VM within the gas left after the base cost
+                let mut move_gas = MoveGasMeter::with_schedule(gas_meter.remaining(), schedule);
                 let mut runtime = self.move_runtime.write().unwrap();
-                let move_changeset = match runtime.publish_module(module_bytes.clone(), addr, None)
-                {
+                let result =
+                    runtime.publish_module_metered(module_bytes.clone(), addr, &mut move_gas);
+                gas_meter.consume(move_gas.gas_used())?;
+                let move_changeset = match result {
                     Ok(cs) => cs,
                     Err(e) => {
                         changeset.mark_failed(format!("Module publish failed: {}", e));
 
                         // CRITICAL: Even for failed transactions, deduct gas and increment sequence
-                        let sender_change = changeset.get_or_create_change(addr);
-                        sender_change.increment_sequence(); // Prevent replay
-                        sender_change.debit(gas_cost);
-
-                        let dao_addr =
-                            AccountAddress::from_hex_literal(KanariAddress::DAO_ADDRESS)?;
-                        changeset.collect_gas(dao_addr, gas_cost);
-                        changeset.set_gas_used(gas_meter.gas_used);
+                        changeset.get_or_create_change(addr).increment_sequence(); // Prevent replay
+                        charge_gas(&mut changeset, addr, &gas_meter, block)?;
                         return Ok(changeset);
                     }
                 };
@@ -129,15 +401,8 @@ impl BlockchainEngine {
                 changeset.merge(move_changeset);
 
                 // CRITICAL: Increment sequence and deduct gas for successful transaction
-                let sender_change = changeset.get_or_create_change(addr);
-                sender_change.increment_sequence(); // Prevent replay attacks
-                sender_change.debit(gas_cost);
-
-                // Credit gas to DAO
-                let dao_addr = AccountAddress::from_hex_literal(KanariAddress::DAO_ADDRESS)?;
-                changeset.collect_gas(dao_addr, gas_cost);
-
-                changeset.set_gas_used(gas_meter.gas_used);
+                changeset.get_or_create_change(addr).increment_sequence(); // Prevent replay attacks
+                charge_gas(&mut changeset, addr, &gas_meter, block)?;
             }
 
             Transaction::ExecuteFunction {
@@ -146,14 +411,10 @@ impl BlockchainEngine {
                 function,
                 type_args,
                 args,
+                objects,
                 ..
             } => {
-                // Calculate gas for function execution
-                let gas_op = GasOperation::ExecuteFunction { complexity: 1 };
-                gas_meter.consume(gas_op.gas_units())?;
-
                 let sender_addr = AccountAddress::from_hex_literal(sender)?;
-                let gas_cost = gas_meter.total_cost();
 
                 // Check balance
                 {
@@ -162,21 +423,17 @@ impl BlockchainEngine {
                         .get_account(&sender_addr)
                         .map(|acc| acc.balance)
                         .unwrap_or(0);
-                    if balance < gas_cost {
+                    if balance < max_gas_cost {
                         changeset.mark_failed(format!(
                             "Insufficient balance for gas: need {}, have {}",
-                            gas_cost, balance
+                            max_gas_cost, balance
                         ));
 
                         // CRITICAL: Even pre-flight failures must deduct gas and increment sequence
-                        let sender_change = changeset.get_or_create_change(sender_addr);
-                        sender_change.increment_sequence(); // Prevent replay
-                        sender_change.debit(gas_cost);
-
-                        let dao_addr =
-                            AccountAddress::from_hex_literal(KanariAddress::DAO_ADDRESS)?;
-                        changeset.collect_gas(dao_addr, gas_cost);
-                        changeset.set_gas_used(gas_meter.gas_used);
+                        changeset
+                            .get_or_create_change(sender_addr)
+                            .increment_sequence(); // Prevent replay
+                        charge_gas(&mut changeset, sender_addr, &gas_meter, block)?;
                         return Ok(changeset);
                     }
                 }
@@ -197,41 +454,48 @@ impl BlockchainEngine {
                     move_core_types::identifier::Identifier::new(parts[1])?,
                 );
 
-                // Parse type args
-                let type_tags: Vec<move_core_types::language_storage::TypeTag> = type_args
+                // Parse type args and object ids; the object inputs themselves are
+                // checked against the object store by the runtime
+                let inputs = type_args
                     .iter()
-                    .filter_map(|s| {
-                        if s == "u64" {
-                            Some(move_core_types::language_storage::TypeTag::U64)
-                        } else {
-                            None
-                        }
-                    })
-                    .collect();
+                    .map(|s| parse_type_tag(s))
+                    .collect::<Result<Vec<_>>>()
+                    .and_then(|type_tags| {
+                        let ids = objects
+                            .iter()
+                            .map(|id| AccountAddress::from_hex_literal(id))
+                            .collect::<Result<Vec<_>, _>>()?;
+                        Ok((type_tags, ids))
+                    });
 
-                // Execute Move VM
+                // Execute Move VM within the gas left after the base cost
+                let mut move_gas = MoveGasMeter::with_schedule(gas_meter.remaining(), schedule);
                 let mut runtime = self.move_runtime.write().unwrap();
-                let move_changeset = match runtime.execute_entry_function(
-                    &module_id,
-                    function,
-                    type_tags,
-                    args.clone(),
-                    None,
-                    None,
-                ) {
+                let result = inputs.and_then(|(type_tags, ids)| {
+                    let tx_hash = signed_tx.hash();
+                    let tx_context = TxContext::new(sender_addr, tx_hash, block.epoch());
+                    runtime.execute_entry_function_metered(
+                        &module_id,
+                        function,
+                        type_tags,
+                        args.clone(),
+                        &CallInputs::new(tx_context)
+                            .with_objects(ids)
+                            .with_random_seed(random_seed),
+                        &mut move_gas,
+                    )
+                });
+                gas_meter.consume(move_gas.gas_used())?;
+                let move_changeset = match result {
                     Ok(cs) => cs,
                     Err(e) => {
                         changeset.mark_failed(format!("Function execution failed: {}", e));
 
                         // CRITICAL: Even for failed transactions, deduct gas and increment sequence
-                        let sender_change = changeset.get_or_create_change(sender_addr);
-                        sender_change.increment_sequence(); // Prevent replay
-                        sender_change.debit(gas_cost);
-
-                        let dao_addr =
-                            AccountAddress::from_hex_literal(KanariAddress::DAO_ADDRESS)?;
-                        changeset.collect_gas(dao_addr, gas_cost);
-                        changeset.set_gas_used(gas_meter.gas_used);
+                        changeset
+                            .get_or_create_change(sender_addr)
+                            .increment_sequence(); // Prevent replay
+                        charge_gas(&mut changeset, sender_addr, &gas_meter, block)?;
                         return Ok(changeset);
                     }
                 };
@@ -240,77 +504,81 @@ impl BlockchainEngine {
                 changeset.merge(move_changeset);
 
                 // Build ChangeSet: increment sequence
-                let sender_change = changeset.get_or_create_change(sender_addr);
-                sender_change.increment_sequence();
-                sender_change.debit(gas_cost);
-
-                // Credit gas to DAO
-                let dao_addr = AccountAddress::from_hex_literal(KanariAddress::DAO_ADDRESS)?;
-                changeset.collect_gas(dao_addr, gas_cost);
-
-                changeset.set_gas_used(gas_meter.gas_used);
+                changeset
+                    .get_or_create_change(sender_addr)
+                    .increment_sequence();
+                charge_gas(&mut changeset, sender_addr, &gas_meter, block)?;
             }
 
             Transaction::Transfer {
-                from, to, amount, ..
+                from,
+                to,
+                amount,
+                coin_type,
+                ..
             } => {
-                // Calculate gas for transfer
-                let gas_op = GasOperation::Transfer;
-                gas_meter.consume(gas_op.gas_units())?;
-
                 let from_addr = AccountAddress::from_hex_literal(from)?;
                 let to_addr = AccountAddress::from_hex_literal(to)?;
-                let gas_cost = gas_meter.total_cost();
-                let total_required = amount.saturating_add(gas_cost);
+                let coin_type = coin_type.clone().unwrap_or_else(kanari_coin_type);
 
-                // Check balance
+                let locked = self.locked_balance(&from_addr, block.height)?;
+
+                // Check balance: gas is always paid in KANARI
                 {
                     let state = self.state.read().unwrap();
                     let balance = state
                         .get_account(&from_addr)
                         .map(|acc| acc.balance)
                         .unwrap_or(0);
-                    if balance < total_required {
-                        changeset.mark_failed(format!(
-                            "Insufficient balance: need {} (amount: {}, gas: {}) but have {}",
-                            total_required, amount, gas_cost, balance
-                        ));
+                    let error = if is_kanari(&coin_type) {
+                        let total_required = amount.saturating_add(max_gas_cost);
+                        if balance < total_required {
+                            Some(format!(
+                                "Insufficient balance: need {} (amount: {}, gas: {}) but have {}",
+                                total_required, amount, max_gas_cost, balance
+                            ))
+                        } else {
+                            locked_error(balance, locked, *amount)
+                        }
+                    } else if balance < max_gas_cost {
+                        Some(format!(
+                            "Insufficient balance for gas: need {}, have {}",
+                            max_gas_cost, balance
+                        ))
+                    } else {
+                        let coin_balance = state.get_coin_balance(&from_addr, &coin_type);
+                        (coin_balance < *amount).then(|| {
+                            format!(
+                                "Insufficient {} balance: need {} but have {}",
+                                coin_type, amount, coin_balance
+                            )
+                        })
+                    };
+                    if let Some(error) = error {
+                        changeset.mark_failed(error);
 
                         // CRITICAL: Even if balance check fails, deduct gas and increment sequence
-                        let sender_change = changeset.get_or_create_change(from_addr);
-                        sender_change.increment_sequence(); // Prevent replay
-                        sender_change.debit(gas_cost); // User still pays for attempt
-
-                        let dao_addr =
-                            AccountAddress::from_hex_literal(KanariAddress::DAO_ADDRESS)?;
-                        changeset.collect_gas(dao_addr, gas_cost);
-                        changeset.set_gas_used(gas_meter.gas_used);
+                        changeset
+                            .get_or_create_change(from_addr)
+                            .increment_sequence(); // Prevent replay
+                        charge_gas(&mut changeset, from_addr, &gas_meter, block)?; // User still pays for attempt
                         return Ok(changeset);
                     }
                 }
 
                 // Build ChangeSet: transfer
-                changeset.transfer(from_addr, to_addr, *amount);
+                changeset.transfer_coin(from_addr, to_addr, &coin_type, *amount);
 
                 // CRITICAL: Increment sequence and deduct gas for successful transfer
-                let sender_change = changeset.get_or_create_change(from_addr);
-                sender_change.increment_sequence(); // Prevent replay attacks
-                sender_change.debit(gas_cost);
-
-                // Credit gas to DAO
-                let dao_addr = AccountAddress::from_hex_literal(KanariAddress::DAO_ADDRESS)?;
-                changeset.collect_gas(dao_addr, gas_cost);
-
-                changeset.set_gas_used(gas_meter.gas_used);
+                changeset
+                    .get_or_create_change(from_addr)
+                    .increment_sequence(); // Prevent replay attacks
+                charge_gas(&mut changeset, from_addr, &gas_meter, block)?;
             }
             Transaction::Burn { from, amount, .. } => {
-                // Calculate gas for burn
-                let gas_op = GasOperation::Transfer; // reuse transfer gas cost for now
-                gas_meter.consume(gas_op.gas_units())?;
-
                 let from_addr = AccountAddress::from_hex_literal(from)?;
-                let gas_cost = gas_meter.total_cost();
-                let total_required = amount.saturating_add(gas_cost);
+                let total_required = amount.saturating_add(max_gas_cost);
+                let locked = self.locked_balance(&from_addr, block.height)?;
 
                 // Check balance for amount + gas
                 {
@@ -319,21 +587,22 @@ impl BlockchainEngine {
                         .get_account(&from_addr)
                         .map(|acc| acc.balance)
                         .unwrap_or(0);
-                    if balance < total_required {
-                        changeset.mark_failed(format!(
+                    let error = if balance < total_required {
+                        Some(format!(
                             "Insufficient balance: need {} (burn: {}, gas: {}) but have {}",
-                            total_required, amount, gas_cost, balance
-                        ));
+                            total_required, amount, max_gas_cost, balance
+                        ))
+                    } else {
+                        locked_error(balance, locked, *amount)
+                    };
+                    if let Some(error) = error {
+                        changeset.mark_failed(error);
 
                         // Deduct gas and increment sequence even on failure
-                        let sender_change = changeset.get_or_create_change(from_addr);
-                        sender_change.increment_sequence();
-                        sender_change.debit(gas_cost);
-
-                        let dao_addr =
-                            AccountAddress::from_hex_literal(KanariAddress::DAO_ADDRESS)?;
-                        changeset.collect_gas(dao_addr, gas_cost);
-                        changeset.set_gas_used(gas_meter.gas_used);
+                        changeset
+                            .get_or_create_change(from_addr)
+                            .increment_sequence();
+                        charge_gas(&mut changeset, from_addr, &gas_meter, block)?;
                         return Ok(changeset);
                     }
                 }
@@ -342,15 +611,153 @@ impl BlockchainEngine {
                 changeset.burn(from_addr, *amount);
 
                 // Increment sequence and deduct gas for successful burn
-                let sender_change = changeset.get_or_create_change(from_addr);
-                sender_change.increment_sequence();
-                sender_change.debit(gas_cost);
+                changeset
+                    .get_or_create_change(from_addr)
+                    .increment_sequence();
+                charge_gas(&mut changeset, from_addr, &gas_meter, block)?;
+            }
+            Transaction::RotateAuthenticationKey {
+                sender,
+                new_authentication_key,
+                ..
+            } => {
+                let addr = AccountAddress::from_hex_literal(sender)?;
+
+                let balance = {
+                    let state = self.state.read().unwrap();
+                    state.get_account(&addr).map(|acc| acc.balance).unwrap_or(0)
+                };
+                let error = if balance < max_gas_cost {
+                    Some(format!(
+                        "Insufficient balance for gas: need {}, have {}",
+                        max_gas_cost, balance
+                    ))
+                } else if new_authentication_key.len() != AUTHENTICATION_KEY_LENGTH {
+                    Some(format!(
+                        "Invalid authentication key length: expected {} bytes, got {}",
+                        AUTHENTICATION_KEY_LENGTH,
+                        new_authentication_key.len()
+                    ))
+                } else {
+                    None
+                };
+
+                if let Some(error) = error {
+                    // Deduct gas and increment sequence even on failure
+                    changeset.mark_failed(error);
+                    changeset.get_or_create_change(addr).increment_sequence();
+                    charge_gas(&mut changeset, addr, &gas_meter, block)?;
+                    return Ok(changeset);
+                }
+
+                changeset.rotate_authentication_key(addr, new_authentication_key.clone());
+                charge_gas(&mut changeset, addr, &gas_meter, block)?;
+            }
+            Transaction::Staking { sender, action, .. } => {
+                let addr = AccountAddress::from_hex_literal(sender)?;
+                let pool_addr = StakingPool::address();
+                let epoch = block.epoch();
+
+                let balance = {
+                    let state = self.state.read().unwrap();
+                    state.get_account(&addr).map(|acc| acc.balance).unwrap_or(0)
+                };
+                let staked_amount = match action {
+                    StakingAction::Stake { amount, .. } => *amount,
+                    _ => 0,
+                };
+                let total_required = staked_amount.saturating_add(max_gas_cost);
+
+                let mut staking = StakingPool::load(self.move_runtime.read().unwrap().storage())?;
+                let result = if balance < total_required {
+                    Err(anyhow::anyhow!(
+                        "Insufficient balance: need {} (stake: {}, gas: {}) but have {}",
+                        total_required,
+                        staked_amount,
+                        max_gas_cost,
+                        balance
+                    ))
+                } else {
+                    match action {
+                        StakingAction::Stake { validator, amount } => {
+                            AccountAddress::from_hex_literal(validator)
+                                .map_err(anyhow::Error::from)
+                                .and_then(|validator| {
+                                    staking.stake(addr, validator, *amount, epoch)
+                                })
+                                .map(|()| {
+                                    changeset.get_or_create_change(addr).debit(*amount);
+                                    changeset.get_or_create_change(pool_addr).credit(*amount);
+                                })
+                        }
+                        StakingAction::Unstake { validator, amount } => {
+                            AccountAddress::from_hex_literal(validator)
+                                .map_err(anyhow::Error::from)
+                                .and_then(|validator| {
+                                    staking.unstake(addr, validator, *amount, epoch)
+                                })
+                        }
+                        StakingAction::Withdraw => staking.withdraw(addr, epoch).map(|amount| {
+                            changeset.get_or_create_change(pool_addr).debit(amount);
+                            changeset.get_or_create_change(addr).credit(amount);
+                        }),
+                    }
+                };
 
-                // Credit gas to DAO
-                let dao_addr = AccountAddress::from_hex_literal(KanariAddress::DAO_ADDRESS)?;
-                changeset.collect_gas(dao_addr, gas_cost);
+                match result {
+                    Ok(()) => staking.write_to(&mut changeset)?,
+                    // Deduct gas and increment sequence even on failure
+                    Err(e) => changeset.mark_failed(format!("Staking failed: {}", e)),
+                }
+                changeset.get_or_create_change(addr).increment_sequence();
+                charge_gas(&mut changeset, addr, &gas_meter, block)?;
+            }
+            Transaction::Governance { sender, action, .. } => {
+                let addr = AccountAddress::from_hex_literal(sender)?;
+
+                let balance = {
+                    let state = self.state.read().unwrap();
+                    state.get_account(&addr).map(|acc| acc.balance).unwrap_or(0)
+                };
+
+                let runtime = self.move_runtime.read().unwrap();
+                // Voting weight is the sender's balance plus everything it has staked
+                let weight =
+                    balance.saturating_add(StakingPool::load(runtime.storage())?.staked_by(&addr));
+                let mut governance = GovernanceStore::load(runtime.storage())?;
+                let result = if balance < max_gas_cost {
+                    Err(anyhow::anyhow!(
+                        "Insufficient balance for gas: need {}, have {}",
+                        max_gas_cost,
+                        balance
+                    ))
+                } else {
+                    match action {
+                        GovernanceAction::Propose(proposal) => {
+                            if weight < PROPOSAL_THRESHOLD {
+                                Err(anyhow::anyhow!(
+                                    "Voting weight {} is below the proposal threshold of {}",
+                                    weight,
+                                    PROPOSAL_THRESHOLD
+                                ))
+                            } else {
+                                governance.propose(addr, proposal, block.height).map(|_| ())
+                            }
+                        }
+                        GovernanceAction::Vote {
+                            proposal_id,
+                            approve,
+                        } => governance.vote(addr, *proposal_id, *approve, weight, block.height),
+                    }
+                };
 
-                changeset.set_gas_used(gas_meter.gas_used);
+                match result {
+                    Ok(()) => governance.write_to(&mut changeset)?,
+                    // Deduct gas and increment sequence even on failure
+                    Err(e) => changeset.mark_failed(format!("Governance failed: {}", e)),
+                }
+                changeset.get_or_create_change(addr).increment_sequence();
+                charge_gas(&mut changeset, addr, &gas_meter, block)?;
             }
         }
 
@@ -365,32 +772,226 @@ impl BlockchainEngine {
     pub fn produce_block(&self) -> Result<BlockInfo> {
         let mut pending = self.pending_txs.write().unwrap();
 
+        // Importing nodes check signatures against the parent state, so drop
+        // transactions whose sender rotated its key since they were accepted
+        pending.retain(|tx| match self.verify_transaction(tx) {
+            Ok(()) => true,
+            Err(e) => {
+                eprintln!("Dropping transaction {}: {}", hex::encode(tx.hash()), e);
+                false
+            }
+        });
+
         if pending.is_empty() {
             anyhow::bail!("No pending transactions");
         }
 
-        let transactions = pending.drain(..).collect::<Vec<_>>();
+        if !self.is_leader_for_next_block() {
+            anyhow::bail!("This node is not the scheduled block producer");
+        }
+
+        let (context, beacon) = {
+            let chain = self.blockchain.read().unwrap();
+            let signer = self.block_signer.read().unwrap();
+            let parent = &chain.latest_block().header;
+            // Beacon proofs are checked against the validator set
+            let beacon_signer = signer.as_ref().filter(|_| chain.validators.is_some());
+            let beacon = RandomBeacon::produce(parent, beacon_signer)?;
+            let producer = signer.as_ref().map(|signer| signer.address.as_str());
+            let seed_beacon = beacon_signer.map(|_| beacon.beacon.clone());
+            let context = self.next_block_context(parent, producer, seed_beacon)?;
+            (context, beacon)
+        };
+
+        // Transactions that can't cover their intrinsic gas would never run
+        pending.retain(
+            |tx| match check_intrinsic_gas(tx, &context.params.gas_schedule) {
+                Ok(()) => true,
+                Err(e) => {
+                    eprintln!("Dropping transaction {}: {}", hex::encode(tx.hash()), e);
+                    false
+                }
+            },
+        );
+
+        // Transactions that can't pay the base fee wait for it to drop
+        let (mut transactions, priced_out): (Vec<_>, Vec<_>) = pending
+            .drain(..)
+            .partition(|tx| tx.transaction.gas_price() >= context.base_fee);
+
+        // Stop filling the block once the gas limits reach the block's budget,
+        // starting from the calls deferred from the parent; the rest waits for
+        // the next block, in order
+        let max_gas = context.params.gas_config.max_gas_per_block;
+        let mut reserved = context
+            .deferred
+            .iter()
+            .map(|tx| tx.transaction.gas_limit())
+            .fold(0u64, u64::saturating_add);
+        let fits = transactions
+            .iter()
+            .take_while(|tx| {
+                reserved = reserved.saturating_add(tx.transaction.gas_limit());
+                reserved <= max_gas
+            })
+            .count()
+            // A transaction over the budget on its own fails its per-transaction limit
+            .max(1);
+        let deferred = transactions.split_off(fits.min(transactions.len()));
+        *pending = deferred;
+        pending.extend(priced_out);
+        if transactions.is_empty() {
+            anyhow::bail!(
+                "No pending transactions pay the base fee of {}",
+                context.base_fee
+            );
+        }
         let tx_count = transactions.len();
 
+        let BlockExecution {
+            executed,
+            failed,
+            events: block_events,
+            published,
+            gas_used,
+            receipts,
+        } = self.apply_transactions(&context, &transactions)?;
+
+        let state_root = self.compute_state_root();
+
+        // Create new block
+        let (height, block_hash) = {
+            let mut chain = self.blockchain.write().unwrap();
+            let prev_hash = chain.latest_block().hash();
+            let height = chain.height() + 1;
+
+            let mut block = Block::new(height, prev_hash, transactions, block_events.clone());
+            block.header.state_root = state_root;
+            block.header.base_fee = context.base_fee;
+            block.header.gas_used = gas_used;
+            block.header.random_beacon = beacon.beacon;
+            block.header.random_proof = beacon.proof;
+            if let Some(signer) = self.block_signer.read().unwrap().as_ref() {
+                block.sign(signer)?;
+            }
+            let block_hash = block.hash();
+
+            chain.add_block(block.clone())?;
+            self.register_published_contracts(&block, &published);
+            self.record_receipts(&receipts);
+            self.notify(ChainEvent::Block(block));
+            (height, block_hash)
+        };
+        self.record_state_snapshot();
+
+        Ok(BlockInfo {
+            height,
+            hash: hex::encode(&block_hash),
+            tx_count,
+            executed,
+            failed,
+            events: block_events,
+            receipts,
+        })
+    }
+
+    /// Execute transactions against the current state and apply ALL resulting
+    /// ChangeSets (both successful and failed), returning the block-level events.
+    /// Shared by local block production and import of blocks from other producers
+    /// so that both derive the same state.
+    fn apply_transactions(
+        &self,
+        block: &BlockContext,
+        transactions: &[SignedTransaction],
+    ) -> Result<BlockExecution> {
+        // Parameters staged in the previous epoch take effect before any transaction
+        if let Some(chain_params) = &block.activated_params {
+            chain_params.save(&mut self.move_runtime.write().unwrap())?;
+        }
+
+        // Pay out the ending epoch's fees and switch to the newly elected validators
+        if let Some((staking, payouts)) = &block.epoch_start {
+            staking.save(&mut self.move_runtime.write().unwrap())?;
+            let mut changeset = ChangeSet::new();
+            for (recipient, amount) in payouts {
+                changeset
+                    .get_or_create_change(StakingPool::address())
+                    .debit(*amount);
+                changeset.get_or_create_change(*recipient).credit(*amount);
+            }
+            self.state
+                .write()
+                .unwrap()
+                .apply_changeset(&changeset)
+                .context("Failed to pay staking rewards")?;
+        }
+
+        self.execute_proposals(block)?;
+
         // Execute all transactions and collect ALL ChangeSets (success + failed)
         let mut all_changesets = Vec::new();
         let mut executed = 0;
         let mut failed = 0;
-        let mut _total_gas_used = 0u64;
+        let mut gas_used = 0u64;
+        let mut published = Vec::new();
+        let mut receipts = Vec::new();
 
-        for tx in &transactions {
-            match self.execute_transaction(tx) {
+        // Calls deferred from the parent run first, seeded by this block's beacon;
+        // the block's own calls that draw randomness wait for the next one
+        let mut deferred = Vec::new();
+        let held_over = block.deferred.iter().map(|tx| (None, tx));
+        let included = transactions.iter().enumerate().map(|(i, tx)| (Some(i), tx));
+        for (index, tx) in held_over.chain(included) {
+            let random_seed = match index {
+                None => block
+                    .random_beacon
+                    .as_ref()
+                    .map(|beacon| random::tx_seed(beacon, &tx.hash())),
+                Some(_) if block.random_beacon.is_some() && self.draws_randomness(tx) => {
+                    deferred.push(tx.clone());
+                    continue;
+                }
+                // Unsigned blocks have no unpredictable beacon: randomness aborts
+                Some(_) => None,
+            };
+            match self.execute_transaction(tx, block, random_seed) {
                 Ok(changeset) => {
+                    // Resource writes land before the next transaction so it
+                    // reads the updated stores; account changes wait for the block end
+                    {
+                        let mut runtime = self.move_runtime.write().unwrap();
+                        for write in &changeset.resource_writes {
+                            runtime.set_resource(
+                                write.address,
+                                write.struct_tag.clone(),
+                                write.blob.clone(),
+                            );
+                        }
+                    }
                     if changeset.success {
                         executed += 1;
+                        if matches!(tx.transaction, Transaction::PublishModule { .. }) {
+                            published.extend(index);
+                        }
                     } else {
                         eprintln!("Transaction failed: {:?}", changeset.error_message);
                         failed += 1;
                     }
                     // CRITICAL: Collect ALL ChangeSets regardless of success status
                     // Failed transactions contain gas deduction and sequence increment
-                    _total_gas_used += changeset.gas_used;
-                    all_changesets.push(changeset);
+                    gas_used += changeset.gas_used;
+                    receipts.push(TransactionReceipt::new(tx, block, &changeset));
+                    if index.is_none() {
+                        // Deferred calls settle before the block's own transactions,
+                        // which may come from the same senders
+                        self.state
+                            .write()
+                            .unwrap()
+                            .apply_changeset(&changeset)
+                            .context("Failed to apply changeset to state")?;
+                    } else {
+                        all_changesets.push(changeset);
+                    }
                 }
                 Err(e) => {
                     eprintln!("Transaction execution error: {:?}", e);
@@ -400,101 +1001,700 @@ impl BlockchainEngine {
             }
         }
 
-        // Apply ALL ChangeSets atomically (both successful and failed) and collect events.
-        let block_events: Vec<Event> = {
-            let mut state = self.state.write().unwrap();
-            for changeset in &all_changesets {
-                state
-                    .apply_changeset(changeset)
-                    .context("Failed to apply changeset to state")?;
-            }
-
-            // Drain events accumulated in state into block-level events
-            state.drain_events()
-        };
-
-        // Create new block
-        let mut chain = self.blockchain.write().unwrap();
-        let prev_hash = chain.latest_block().hash();
-        let height = chain.height() + 1;
+        if !(deferred.is_empty() && block.deferred.is_empty()) {
+            DeferredCalls::new(&deferred)?.save(&mut self.move_runtime.write().unwrap())?;
+        }
 
-        let block = Block::new(height, prev_hash, transactions, block_events.clone());
-        let block_hash = block.hash();
+        // Tips pooled for stakers are distributed at the end of the epoch
+        if block.pool_tips {
+            let tips: u64 = receipts
+                .iter()
+                .map(|receipt| staker_tip_share(receipt.gas_charged - receipt.gas_burned))
+                .sum();
+            if tips > 0 {
+                let mut runtime = self.move_runtime.write().unwrap();
+                let mut staking = StakingPool::load(runtime.storage())?;
+                staking.rewards += tips;
+                staking.save(&mut runtime)?;
+            }
+        }
 
-        chain.add_block(block)?;
+        // Apply ALL ChangeSets atomically (both successful and failed) and collect events.
+        let mut state = self.state.write().unwrap();
+        for changeset in &all_changesets {
+            state
+                .apply_changeset(changeset)
+                .context("Failed to apply changeset to state")?;
+        }
 
-        Ok(BlockInfo {
-            height,
-            hash: hex::encode(&block_hash),
-            tx_count,
+        // Drain events accumulated in state into block-level events
+        Ok(BlockExecution {
             executed,
             failed,
-            events: block_events,
+            events: state.drain_events(),
+            published,
+            gas_used,
+            receipts,
         })
     }
 
-    /// Get blockchain stats
-    pub fn get_stats(&self) -> BlockchainStats {
-        let chain = self.blockchain.read().unwrap();
-        let state = self.state.read().unwrap();
-        let pending = self.pending_txs.read().unwrap();
+    /// Close the proposals whose voting period has ended and carry out the ones
+    /// that passed. A passed proposal that cannot be executed is marked failed.
+    fn execute_proposals(&self, block: &BlockContext) -> Result<()> {
+        let total_supply = self.state.read().unwrap().total_supply;
+        let mut governance = GovernanceStore::load(self.move_runtime.read().unwrap().storage())?;
+        let passed = governance.tally(block.height, total_supply);
 
-        BlockchainStats {
-            height: chain.height(),
-            total_blocks: chain.blocks.len(),
-            total_transactions: chain.get_transaction_count(),
-            pending_transactions: pending.len(),
-            total_accounts: state.account_count(),
-            total_supply: state.total_supply,
+        for (id, action) in passed {
+            let result = match action {
+                ProposalAction::TreasurySpend { recipient, amount } => {
+                    let treasury = governance_address();
+                    let mut state = self.state.write().unwrap();
+                    let available = state
+                        .get_account(&treasury)
+                        .map(|acc| acc.balance)
+                        .unwrap_or(0);
+                    AccountAddress::from_hex_literal(&recipient)
+                        .map_err(anyhow::Error::from)
+                        .and_then(|recipient| {
+                            // Checked up front so a short treasury leaves state untouched
+                            if available < amount {
+                                anyhow::bail!(
+                                    "Treasury holds {} but the proposal spends {}",
+                                    available,
+                                    amount
+                                );
+                            }
+                            let mut changeset = ChangeSet::new();
+                            changeset.get_or_create_change(treasury).debit(amount);
+                            changeset.get_or_create_change(recipient).credit(amount);
+                            state.apply_changeset(&changeset)
+                        })
+                }
+                ProposalAction::ChangeParameters(parameters) => {
+                    let mut runtime = self.move_runtime.write().unwrap();
+                    ChainParamsStore::load(runtime.storage()).and_then(|mut store| {
+                        store.pending = Some(parameters);
+                        store.save(&mut runtime)
+                    })
+                }
+                ProposalAction::UpgradeFramework { modules } => self
+                    .move_runtime
+                    .write()
+                    .unwrap()
+                    .upgrade_framework(modules),
+            };
+            if let Err(e) = result {
+                eprintln!("Proposal {} failed to execute: {:?}", id, e);
+                governance.mark_failed(id);
+            }
         }
+
+        governance.save(&mut self.move_runtime.write().unwrap())
     }
 
-    /// Get account info
-    pub fn get_account_info(&self, address: &str) -> Option<AccountInfo> {
-        let state = self.state.read().unwrap();
-        state.get_account_by_hex(address).map(|acc| AccountInfo {
-            address: format!("{:#x}", acc.address),
-            balance: acc.balance,
-            sequence_number: acc.sequence_number,
-            modules: acc.modules.iter().cloned().collect(),
-        })
+    /// Context of the block following `parent`, under the chain parameters
+    /// currently in Move storage. `random_beacon` is the block's beacon if
+    /// blocks are signed.
+    fn next_block_context(
+        &self,
+        parent: &BlockHeader,
+        producer: Option<&str>,
+        random_beacon: Option<Vec<u8>>,
+    ) -> Result<BlockContext> {
+        let runtime = self.move_runtime.read().unwrap();
+        let chain_params = ChainParamsStore::load(runtime.storage())?;
+        let staking = StakingPool::load(runtime.storage())?;
+        let deferred = DeferredCalls::load(runtime.storage())?.decode()?;
+        Ok(BlockContext::next(
+            parent,
+            producer,
+            random_beacon,
+            deferred,
+            &chain_params,
+            &staking,
+        ))
     }
 
-    /// Deploy a contract (publish Move module)
-    pub fn deploy_contract(&self, deployment: ContractDeployment) -> Result<Vec<u8>> {
-        let tx = Transaction::PublishModule {
-            sender: deployment.publisher_address(),
-            module_bytes: deployment.bytecode.clone(),
-            module_name: deployment.module_name.clone(),
-            gas_limit: deployment.gas_limit,
-            gas_price: deployment.gas_price,
-            sequence_number: 0,
+    /// Whether `tx` calls a module that uses `0x2::random`, directly or through
+    /// its dependencies
+    fn draws_randomness(&self, tx: &SignedTransaction) -> bool {
+        let Transaction::ExecuteFunction { module, .. } = &tx.transaction else {
+            return false;
         };
-
-        // Create unsigned transaction for now (in production, should be signed)
-        let signed_tx = SignedTransaction::new(tx.clone());
-        let tx_hash = self.submit_transaction(signed_tx)?;
-
-        // Register contract in registry
-        let block_height = self.blockchain.read().unwrap().height();
-        let contract_info = ContractInfo {
-            address: deployment.publisher_address(),
-            module_name: deployment.module_name,
-            bytecode: deployment.bytecode,
-            deployment_tx: tx_hash.clone(),
-            deployed_at: block_height,
-            abi: crate::contract::ContractABI::new(),
-            metadata: deployment.metadata,
+        let Some((address, name)) = module.split_once("::") else {
+            return false;
         };
-
-        self.contract_registry
+        let (Ok(address), Ok(name)) = (
+            AccountAddress::from_hex_literal(address),
+            move_core_types::identifier::Identifier::new(name),
+        ) else {
+            return false;
+        };
+        self.move_runtime
+            .read()
+            .unwrap()
+            .depends_on(&ModuleId::new(address, name), &random::random_module())
+    }
+
+    /// Balance `address` must keep at `height` while its votes are open: the
+    /// locked voting weight not covered by its stake
+    fn locked_balance(&self, address: &AccountAddress, height: u64) -> Result<u64> {
+        let runtime = self.move_runtime.read().unwrap();
+        let locked = GovernanceStore::load(runtime.storage())?.locked(address, height);
+        let staked = StakingPool::load(runtime.storage())?.staked_by(address);
+        Ok(locked.saturating_sub(staked))
+    }
+
+    /// Governance proposals and their votes
+    pub fn governance(&self) -> Result<GovernanceStore> {
+        GovernanceStore::load(self.move_runtime.read().unwrap().storage())
+    }
+
+    /// Stakes, unbonding withdrawals, elected validators and undistributed fees
+    pub fn staking_pool(&self) -> Result<StakingPool> {
+        StakingPool::load(self.move_runtime.read().unwrap().storage())
+    }
+
+    /// Chain parameters in force and any staged for the next epoch
+    pub fn chain_parameters(&self) -> Result<ChainParamsStore> {
+        ChainParamsStore::load(self.move_runtime.read().unwrap().storage())
+    }
+
+    fn record_receipts(&self, receipts: &[TransactionReceipt]) {
+        let mut stored = self.receipts.write().unwrap();
+        for receipt in receipts {
+            stored.insert(receipt.tx_hash.clone(), receipt.clone());
+        }
+    }
+
+    /// Receipt of a transaction in a canonical block, by hex transaction hash
+    pub fn get_transaction_receipt(&self, tx_hash: &str) -> Option<TransactionReceipt> {
+        let tx_hash = tx_hash.trim_start_matches("0x");
+        self.receipts.read().unwrap().get(tx_hash).cloned()
+    }
+
+    /// Record contracts from the successful `PublishModule` transactions of a
+    /// committed block, so the registry only lists modules that exist on-chain
+    fn register_published_contracts(&self, block: &Block, published: &[usize]) {
+        if published.is_empty() {
+            return;
+        }
+        let mut pending_metadata = self.pending_contract_metadata.write().unwrap();
+        let mut registry = self.contract_registry.write().unwrap();
+        for &index in published {
+            let signed_tx = &block.transactions[index];
+            let Transaction::PublishModule {
+                sender,
+                module_bytes,
+                module_name,
+                ..
+            } = &signed_tx.transaction
+            else {
+                continue;
+            };
+            let tx_hash = signed_tx.hash();
+            let metadata = pending_metadata.remove(&tx_hash).unwrap_or_else(|| {
+                ContractMetadata::new(module_name.clone(), String::new(), sender.clone())
+            });
+            registry.register(ContractInfo {
+                address: sender.clone(),
+                module_name: module_name.clone(),
+                bytecode: module_bytes.clone(),
+                deployment_tx: tx_hash,
+                deployed_at: block.header.height,
+                // The VM accepted the module, so it deserializes
+                abi: ContractABI::from_bytecode(module_bytes).unwrap_or_default(),
+                metadata,
+            });
+        }
+    }
+
+    /// Import a block produced by another node.
+    ///
+    /// Blocks extending the tip are re-executed and must reproduce the header's state
+    /// root. Blocks on another branch are kept in the block tree; if that branch becomes
+    /// longer than the canonical chain, state is reverted to the common ancestor, the
+    /// branch is re-applied, and transactions only present in the abandoned blocks are
+    /// returned to the mempool.
+    pub fn import_block(&self, block: Block) -> Result<BlockImport> {
+        let announcement = block.clone();
+        let import = self.import_block_inner(block)?;
+        if import != BlockImport::Known {
+            self.notify(ChainEvent::Block(announcement));
+        }
+        Ok(import)
+    }
+
+    fn import_block_inner(&self, block: Block) -> Result<BlockImport> {
+        // Serialize with local block production
+        let mut pending = self.pending_txs.write().unwrap();
+        let block_hash = block.hash();
+        let height = block.header.height;
+
+        let (tip_hash, tip_height, known) = {
+            let chain = self.blockchain.read().unwrap();
+            let known = chain
+                .get_block(height)
+                .is_some_and(|b| b.hash() == block_hash)
+                || self.block_tree.read().unwrap().contains(&block_hash);
+            (chain.latest_block().hash(), chain.height(), known)
+        };
+        if known {
+            return Ok(BlockImport::Known);
+        }
+
+        if block.header.prev_hash == tip_hash {
+            self.apply_block(&block)?;
+            let included: HashSet<Vec<u8>> = block
+                .transactions
+                .iter()
+                .map(|tx| tx.transaction.hash())
+                .collect();
+            pending.retain(|tx| !included.contains(&tx.transaction.hash()));
+            self.block_tree.write().unwrap().prune_finalized(height);
+            return Ok(BlockImport::Extended { height });
+        }
+
+        // Competing block: keep it in the tree and check whether its branch wins
+        let (ancestor, branch) = {
+            let mut tree = self.block_tree.write().unwrap();
+            let finalized = tree.finalized_height(tip_height);
+            if height <= finalized {
+                anyhow::bail!(
+                    "Block {} at height {} is below finalized height {}",
+                    hex::encode(&block_hash),
+                    height,
+                    finalized
+                );
+            }
+            let chain = self.blockchain.read().unwrap();
+            let parent_known = tree.contains(&block.header.prev_hash)
+                || height
+                    .checked_sub(1)
+                    .and_then(|h| chain.get_block(h))
+                    .is_some_and(|parent| parent.hash() == block.header.prev_hash);
+            if !parent_known {
+                // Keep a bounded number of blocks until their ancestors arrive
+                tree.insert_orphan(block);
+                return Ok(BlockImport::SideChain { height });
+            }
+            tree.insert(block);
+
+            let Some((ancestor, branch)) = tree.branch_from(&block_hash, &chain) else {
+                // An ancestor is still missing
+                return Ok(BlockImport::SideChain { height });
+            };
+            if !tree.prefers(height, tip_height) {
+                return Ok(BlockImport::SideChain { height });
+            }
+            if ancestor < finalized {
+                anyhow::bail!(
+                    "Refusing to reorg below finalized height {} (common ancestor {})",
+                    finalized,
+                    ancestor
+                );
+            }
+            (ancestor, branch)
+        };
+
+        let included: HashSet<Vec<u8>> = branch
+            .iter()
+            .flat_map(|b| b.transactions.iter().map(|tx| tx.transaction.hash()))
+            .collect();
+        let orphaned = self.reorg(ancestor, branch, &included)?;
+
+        // Drop what the winning branch included and return orphaned transactions
+        // to the mempool, ahead of newer submissions
+        pending.retain(|tx| !included.contains(&tx.transaction.hash()));
+        let reverted = tip_height - ancestor;
+        let orphaned_count = orphaned.len();
+        pending.splice(0..0, orphaned);
+
+        Ok(BlockImport::Reorg {
+            common_ancestor: ancestor,
+            reverted,
+            applied: height - ancestor,
+            orphaned_txs: orphaned_count,
+        })
+    }
+
+    /// Switch the canonical chain to `branch`, which forks off at `ancestor`.
+    /// Returns transactions from the abandoned blocks that are not in `included`,
+    /// the branch's transaction hashes.
+    /// On failure the previous canonical chain and state are restored.
+    fn reorg(
+        &self,
+        ancestor: u64,
+        branch: Vec<Block>,
+        included: &HashSet<Vec<u8>>,
+    ) -> Result<Vec<SignedTransaction>> {
+        let ancestor_state = self
+            .export_snapshot(ancestor)
+            .context("State at common ancestor is not available (pruned?)")?;
+        let tip_state = self.export_snapshot(self.blockchain.read().unwrap().height())?;
+
+        // Detach the old canonical blocks above the ancestor
+        let abandoned: Vec<Block> = {
+            let mut chain = self.blockchain.write().unwrap();
+            let keep = (ancestor - chain.base_height() + 1) as usize;
+            chain.blocks.split_off(keep)
+        };
+        let abandoned_history = self
+            .state_history
+            .write()
+            .unwrap()
+            .split_off(&(ancestor + 1));
+        let registry = self.contract_registry.read().unwrap().clone();
+        self.restore_state(&ancestor_state)?;
+        self.contract_registry
+            .write()
+            .unwrap()
+            .remove_deployed_after(ancestor);
+
+        for block in &branch {
+            if let Err(e) = self.apply_block(block) {
+                // Roll back to the previous canonical chain
+                let mut chain = self.blockchain.write().unwrap();
+                let keep = (ancestor - chain.base_height() + 1) as usize;
+                chain.blocks.truncate(keep);
+                chain.blocks.extend(abandoned);
+                drop(chain);
+                let mut history = self.state_history.write().unwrap();
+                history.retain(|h, _| *h <= ancestor);
+                history.extend(abandoned_history);
+                drop(history);
+                self.restore_state(&tip_state)?;
+                *self.contract_registry.write().unwrap() = registry;
+                self.block_tree.write().unwrap().remove(&block.hash());
+                return Err(e.context("Reorg aborted: branch block failed validation"));
+            }
+        }
+
+        let mut tree = self.block_tree.write().unwrap();
+        for block in &branch {
+            tree.remove(&block.hash());
+        }
+        let mut orphaned = Vec::new();
+        for block in abandoned {
+            orphaned.extend(
+                block
+                    .transactions
+                    .iter()
+                    .filter(|tx| !included.contains(&tx.transaction.hash()))
+                    .cloned(),
+            );
+            tree.insert(block);
+        }
+        let mut receipts = self.receipts.write().unwrap();
+        for tx in &orphaned {
+            receipts.remove(&hex::encode(tx.hash()));
+        }
+        tree.prune_finalized(self.blockchain.read().unwrap().height());
+
+        Ok(orphaned)
+    }
+
+    /// Re-execute a block on top of the current tip and append it if it reproduces
+    /// the header's base fee, gas used and state root. State is restored if
+    /// validation or execution fails.
+    fn apply_block(&self, block: &Block) -> Result<()> {
+        let (parent_state, context) = {
+            let chain = self.blockchain.read().unwrap();
+            chain.verify_next(block)?;
+            let parent = &chain.latest_block().header;
+            // Under proof-of-authority the beacon must be signed by the producer
+            RandomBeacon::verify(parent, &block.header, chain.validators.as_ref())?;
+            let context = self.next_block_context(
+                parent,
+                block.header.producer.as_deref(),
+                chain
+                    .validators
+                    .as_ref()
+                    .map(|_| block.header.random_beacon.clone()),
+            )?;
+            (self.export_snapshot(chain.height())?, context)
+        };
+        let max_gas = context.params.gas_config.max_gas_per_block;
+        if block.header.gas_used > max_gas {
+            anyhow::bail!(
+                "Block {} uses {} gas, over the limit of {}",
+                block.header.height,
+                block.header.gas_used,
+                max_gas
+            );
+        }
+        if block.header.base_fee != context.base_fee {
+            anyhow::bail!(
+                "Base fee mismatch for block {}: header {}, expected {}",
+                block.header.height,
+                block.header.base_fee,
+                context.base_fee
+            );
+        }
+        // Every sender must have authorized its transaction with the key that
+        // controls the account in the parent state, and left gas to run it
+        for tx in &block.transactions {
+            self.verify_transaction(tx)
+                .and_then(|()| check_intrinsic_gas(tx, &context.params.gas_schedule))
+                .with_context(|| {
+                    format!(
+                        "Transaction {} in block {}",
+                        hex::encode(tx.hash()),
+                        block.header.height
+                    )
+                })?;
+        }
+
+        let execution = match self.execute_block(block, &context) {
+            Ok(execution) => execution,
+            Err(e) => {
+                self.restore_state(&parent_state)?;
+                return Err(e);
+            }
+        };
+        self.register_published_contracts(block, &execution.published);
+        self.record_receipts(&execution.receipts);
+        self.record_state_snapshot();
+        Ok(())
+    }
+
+    /// Execute `block` on the current state, check the result against its
+    /// header and append it to the chain. Leaves state half-applied on error.
+    fn execute_block(&self, block: &Block, context: &BlockContext) -> Result<BlockExecution> {
+        let execution = self.apply_transactions(context, &block.transactions)?;
+        if execution.gas_used != block.header.gas_used {
+            anyhow::bail!(
+                "Gas used mismatch for block {}: header {}, computed {}",
+                block.header.height,
+                block.header.gas_used,
+                execution.gas_used
+            );
+        }
+        let state_root = self.compute_state_root();
+        if state_root != block.header.state_root {
+            anyhow::bail!(
+                "State root mismatch for block {}: header {}, computed {}",
+                block.header.height,
+                hex::encode(&block.header.state_root),
+                hex::encode(&state_root)
+            );
+        }
+
+        self.blockchain.write().unwrap().add_block(block.clone())?;
+        Ok(execution)
+    }
+
+    /// Get blockchain stats
+    pub fn get_stats(&self) -> BlockchainStats {
+        let chain = self.blockchain.read().unwrap();
+        let state = self.state.read().unwrap();
+        let pending = self.pending_txs.read().unwrap();
+
+        BlockchainStats {
+            height: chain.height(),
+            total_blocks: chain.blocks.len(),
+            total_transactions: chain.get_transaction_count(),
+            pending_transactions: pending.len(),
+            total_accounts: state.account_count(),
+            total_supply: state.total_supply,
+        }
+    }
+
+    /// Get account info
+    pub fn get_account_info(&self, address: &str) -> Option<AccountInfo> {
+        let state = self.state.read().unwrap();
+        state.get_account_by_hex(address).map(|acc| AccountInfo {
+            address: format!("{:#x}", acc.address),
+            balance: acc.balance,
+            sequence_number: acc.sequence_number,
+            modules: acc.modules.iter().cloned().collect(),
+            authentication_key: acc.authentication_key.as_ref().map(hex::encode),
+            balances: self.coin_balances(&acc.balances()),
+        })
+    }
+
+    /// Every non-zero coin balance of `address`, with the metadata each currency
+    /// registered. Empty for unknown accounts.
+    pub fn get_balances(&self, address: &str) -> Vec<CoinBalance> {
+        let state = self.state.read().unwrap();
+        state
+            .get_account_by_hex(address)
+            .map(|acc| self.coin_balances(&acc.balances()))
+            .unwrap_or_default()
+    }
+
+    fn coin_balances(&self, balances: &[(String, u64)]) -> Vec<CoinBalance> {
+        let runtime = self.move_runtime.read().unwrap();
+        balances
+            .iter()
+            .map(|(coin, balance)| {
+                let metadata = parse_type_tag(coin)
+                    .ok()
+                    .and_then(|coin_type| CoinMetadata::load(runtime.storage(), &coin_type).ok())
+                    .flatten();
+                CoinBalance {
+                    coin_type: coin.clone(),
+                    balance: *balance,
+                    symbol: metadata.as_ref().map(|m| m.symbol.clone()),
+                    name: metadata.as_ref().map(|m| m.name.clone()),
+                    decimals: metadata.as_ref().map(|m| m.decimals),
+                }
+            })
+            .collect()
+    }
+
+    /// Deploy a contract (publish Move module)
+    pub fn deploy_contract(&self, deployment: ContractDeployment) -> Result<Vec<u8>> {
+        // Reject malformed bytecode before submission
+        ContractABI::from_bytecode(&deployment.bytecode)?;
+
+        let tx = Transaction::PublishModule {
+            sender: deployment.publisher_address(),
+            module_bytes: deployment.bytecode.clone(),
+            module_name: deployment.module_name.clone(),
+            gas_limit: deployment.gas_limit,
+            gas_price: deployment.gas_price,
+            priority_fee: 0,
+            sequence_number: 0,
+        };
+
+        // Create unsigned transaction for now (in production, should be signed)
+        let signed_tx = SignedTransaction::new(tx);
+        let tx_hash = self.submit_transaction(signed_tx)?;
+
+        // The contract is registered once the publish commits in a block; keep the
+        // off-chain metadata until then
+        self.pending_contract_metadata
             .write()
             .unwrap()
-            .register(contract_info);
+            .insert(tx_hash.clone(), deployment.metadata);
 
         Ok(tx_hash)
     }
 
+    /// Call a view function read-only against the latest state, or the state at
+    /// `height` if it is still retained. No transaction is created and no gas is paid,
+    /// but the call is metered and fails past the per-transaction gas limit.
+    /// `module` is `address::module_name` and `type_args` are Move type tags.
+    pub fn view_function(
+        &self,
+        module: &str,
+        function: &str,
+        type_args: &[String],
+        args: Vec<Vec<u8>>,
+        height: Option<u64>,
+    ) -> Result<ViewResult> {
+        let (address, name) = module
+            .split_once("::")
+            .context("Invalid module format. Expected: address::module")?;
+        let module_id = ModuleId::new(
+            AccountAddress::from_hex_literal(address)?,
+            move_core_types::identifier::Identifier::new(name)?,
+        );
+        let type_tags = type_args
+            .iter()
+            .map(|t| parse_type_tag(t))
+            .collect::<Result<Vec<_>>>()?;
+
+        let latest = self.blockchain.read().unwrap().height();
+        let (height, storage) = match height {
+            Some(height) if height != latest => (
+                height,
+                Some(self.export_snapshot(height)?.to_move_storage()),
+            ),
+            _ => (latest, None),
+        };
+
+        let runtime = self.move_runtime.read().unwrap();
+        let params = ChainParamsStore::load(runtime.storage())?.current;
+        let mut gas =
+            MoveGasMeter::with_schedule(params.gas_config.max_gas_per_tx, &params.gas_schedule);
+        let return_values = runtime
+            .execute_view_function(storage, &module_id, function, type_tags, args, &mut gas)?;
+        Ok(ViewResult {
+            height,
+            return_values,
+        })
+    }
+
+    /// All Move resources stored under an account
+    pub fn get_resources(&self, address: &str) -> Result<Vec<MoveResource>> {
+        let address = AccountAddress::from_hex_literal(address)?;
+        Ok(self
+            .move_runtime
+            .read()
+            .unwrap()
+            .get_resources(&address, None))
+    }
+
+    /// A single Move resource, e.g. `0x2::coin::CoinMetadata<0x2::kanari::KANARI>`
+    pub fn get_resource(&self, address: &str, struct_tag: &str) -> Result<Option<MoveResource>> {
+        let address = AccountAddress::from_hex_literal(address)?;
+        let struct_tag = parse_struct_tag(struct_tag)?;
+        Ok(self
+            .move_runtime
+            .read()
+            .unwrap()
+            .get_resources(&address, Some(&struct_tag))
+            .pop())
+    }
+
+    /// Objects owned by `address`
+    pub fn get_owned_objects(&self, address: &str) -> Result<Vec<ObjectInfo>> {
+        let address = AccountAddress::from_hex_literal(address)?;
+        let runtime = self.move_runtime.read().unwrap();
+        Ok(runtime
+            .storage()
+            .owned_objects(&address)
+            .map(|object| object_info(&runtime, object))
+            .collect())
+    }
+
+    /// A single object by id, whoever owns it
+    pub fn get_object(&self, id: &str) -> Result<Option<ObjectInfo>> {
+        let id = AccountAddress::from_hex_literal(id)?;
+        let runtime = self.move_runtime.read().unwrap();
+        Ok(runtime
+            .storage()
+            .object(&id)
+            .map(|object| object_info(&runtime, object)))
+    }
+
+    /// NFTs owned by `address`, with their collections
+    pub fn get_owned_nfts(&self, address: &str) -> Result<Vec<NftInfo>> {
+        let address = AccountAddress::from_hex_literal(address)?;
+        let runtime = self.move_runtime.read().unwrap();
+        let storage = runtime.storage();
+        storage
+            .owned_objects(&address)
+            .filter_map(|object| nft_info(storage, object).transpose())
+            .collect()
+    }
+
+    /// A single NFT by id, whoever owns it; `None` if the object is not an NFT
+    pub fn get_nft(&self, id: &str) -> Result<Option<NftInfo>> {
+        let id = AccountAddress::from_hex_literal(id)?;
+        let runtime = self.move_runtime.read().unwrap();
+        match runtime.storage().object(&id) {
+            Some(object) => nft_info(runtime.storage(), object),
+            None => Ok(None),
+        }
+    }
+
+    /// Decode an event payload with the layout of the struct named by its `type_tag`;
+    /// `null` if the type or its module is unknown
+    pub fn decode_event(&self, event: &Event) -> serde_json::Value {
+        match parse_type_tag(&event.type_tag) {
+            Ok(type_tag) => self
+                .move_runtime
+                .read()
+                .unwrap()
+                .decode_json(&type_tag, &event.event_data),
+            Err(_) => serde_json::Value::Null,
+        }
+    }
+
     /// Call a contract function
     pub fn call_contract(&self, call: ContractCall) -> Result<Vec<u8>> {
         let tx = Transaction::ExecuteFunction {
@@ -503,8 +1703,10 @@ impl BlockchainEngine {
             function: call.function.clone(),
             type_args: call.type_args.iter().map(|t| format!("{}", t)).collect(),
             args: call.args.clone(),
+            objects: vec![],
             gas_limit: call.gas_limit,
             gas_price: call.gas_price,
+            priority_fee: 0,
             sequence_number: 0,
         };
 
@@ -559,6 +1761,15 @@ impl BlockchainEngine {
         self.contract_registry.read().unwrap().count()
     }
 
+    /// Up to `limit` consecutive canonical blocks starting at `from`, for serving sync
+    /// requests. Stops at the tip; empty if `from` has already been pruned.
+    pub fn get_blocks(&self, from: u64, limit: u64) -> Vec<Block> {
+        let chain = self.blockchain.read().unwrap();
+        (from..from.saturating_add(limit))
+            .map_while(|height| chain.get_block(height).cloned())
+            .collect()
+    }
+
     /// Get block by height
     pub fn get_block(&self, height: u64) -> Option<BlockData> {
         let chain = self.blockchain.read().unwrap();
@@ -567,12 +1778,200 @@ impl BlockchainEngine {
             timestamp: block.header.timestamp,
             hash: hex::encode(&block.hash()),
             prev_hash: hex::encode(&block.header.prev_hash),
-            tx_count: block.transactions.len(),
+            state_root: hex::encode(&block.header.state_root),
+            tx_count: block.header.tx_count,
+            producer: block.header.producer.clone(),
+            base_fee: block.header.base_fee,
+            gas_used: block.header.gas_used,
             events: block.events.clone(),
         })
     }
 }
 
+/// Parameters shared by every transaction executed in a block
+struct BlockContext {
+    height: u64,
+    base_fee: u64,
+    /// Receives the tips; the block producer, or the DAO without one
+    fee_recipient: AccountAddress,
+    /// Whether validators are elected, so part of each tip goes to the
+    /// staking pool instead
+    pool_tips: bool,
+    /// Chain parameters in force for this block
+    params: ChainParameters,
+    /// Store to write before executing the block when staged parameters
+    /// become current at its height
+    activated_params: Option<ChainParamsStore>,
+    /// Staking pool and reward payouts to apply before executing the first
+    /// block of an epoch
+    epoch_start: Option<(StakingPool, BTreeMap<AccountAddress, u64>)>,
+    /// Beacon committed in the block header if blocks are signed, seeding
+    /// `0x2::random` for the calls deferred from the parent
+    random_beacon: Option<Vec<u8>>,
+    /// Calls included in the parent that draw randomness, run before the
+    /// block's own transactions
+    deferred: Vec<SignedTransaction>,
+}
+
+impl BlockContext {
+    /// Context of the block following `parent`, produced by `producer` with
+    /// `random_beacon`, given the deferred calls, chain parameters and staking
+    /// pool stored after `parent`
+    fn next(
+        parent: &BlockHeader,
+        producer: Option<&str>,
+        random_beacon: Option<Vec<u8>>,
+        deferred: Vec<SignedTransaction>,
+        chain_params: &ChainParamsStore,
+        staking: &StakingPool,
+    ) -> Self {
+        let height = parent.height + 1;
+        let activated_params = chain_params.activate_at(height);
+        let params = activated_params
+            .as_ref()
+            .map_or(&chain_params.current, |store| &store.current)
+            .clone();
+
+        let epoch_start = params
+            .is_epoch_start(height)
+            .then(|| staking.begin_epoch(params.epoch(height)));
+        let staking = epoch_start.as_ref().map_or(staking, |(pool, _)| pool);
+
+        let fee_recipient = producer
+            .and_then(|producer| AccountAddress::from_hex_literal(producer).ok())
+            .unwrap_or_else(|| {
+                AccountAddress::from_hex_literal(KanariAddress::DAO_ADDRESS).unwrap()
+            });
+
+        Self {
+            height,
+            base_fee: params
+                .gas_config
+                .next_base_fee(parent.base_fee, parent.gas_used),
+            fee_recipient,
+            // Once validators are staked, part of the fees is pooled and paid out per epoch
+            pool_tips: !staking.active_validators.is_empty(),
+            params,
+            activated_params,
+            epoch_start,
+            random_beacon,
+            deferred,
+        }
+    }
+
+    fn epoch(&self) -> u64 {
+        self.params.epoch(self.height)
+    }
+}
+
+/// Error for moving `amount` out of `balance` when `locked` of it backs open votes
+fn locked_error(balance: u64, locked: u64, amount: u64) -> Option<String> {
+    (balance.saturating_sub(locked) < amount).then(|| {
+        format!(
+            "Only {} of the balance is unlocked; {} is locked by open governance votes",
+            balance.saturating_sub(locked),
+            locked
+        )
+    })
+}
+
+/// Gas a transaction burns before it runs: its base operation plus the
+/// signature bytes beyond a classical one
+fn intrinsic_gas(signed_tx: &SignedTransaction, schedule: &GasSchedule) -> u64 {
+    base_gas_operation(&signed_tx.transaction)
+        .gas_units_with(schedule)
+        .saturating_add(signature_gas(signed_tx, schedule))
+}
+
+/// Fixed cost of each kind of transaction; the Move VM meters calls and
+/// publishes beyond it
+fn base_gas_operation(tx: &Transaction) -> GasOperation {
+    match tx {
+        Transaction::PublishModule { module_bytes, .. } => GasOperation::PublishModule {
+            module_size: module_bytes.len(),
+        },
+        Transaction::ExecuteFunction { .. } => GasOperation::ExecuteFunction { complexity: 0 },
+        Transaction::Transfer { .. } | Transaction::Burn { .. } => GasOperation::Transfer,
+        Transaction::RotateAuthenticationKey { .. }
+        | Transaction::Staking { .. }
+        | Transaction::Governance { .. } => GasOperation::UpdateAccount,
+    }
+}
+
+/// Gas for carrying and verifying the transaction's signature
+fn signature_gas(signed_tx: &SignedTransaction, schedule: &GasSchedule) -> u64 {
+    signed_tx
+        .authenticator
+        .as_ref()
+        .and_then(|authenticator| CurveType::from_scheme_flag(authenticator.scheme))
+        .map_or(0, |scheme| {
+            GasOperation::SignatureVerification {
+                signature_size: scheme.signature_size(),
+            }
+            .gas_units_with(schedule)
+        })
+}
+
+/// Refuse a transaction whose gas limit can't cover its intrinsic gas
+fn check_intrinsic_gas(signed_tx: &SignedTransaction, schedule: &GasSchedule) -> Result<()> {
+    let intrinsic = intrinsic_gas(signed_tx, schedule);
+    let gas_limit = signed_tx.transaction.gas_limit();
+    if gas_limit < intrinsic {
+        anyhow::bail!(
+            "Gas limit {} is below the intrinsic gas of {}",
+            gas_limit,
+            intrinsic
+        );
+    }
+    Ok(())
+}
+
+/// Fail a transaction that ran out of gas: like a Move VM out-of-gas, the
+/// sender pays the whole `gas_limit` and its sequence number advances
+fn fail_out_of_gas(
+    changeset: &mut ChangeSet,
+    sender: AccountAddress,
+    gas_meter: &mut GasMeter,
+    block: &BlockContext,
+    error: GasError,
+) -> Result<()> {
+    changeset.mark_failed(error.to_string());
+    changeset.get_or_create_change(sender).increment_sequence(); // Prevent replay
+    gas_meter.consume(gas_meter.remaining())?;
+    charge_gas(changeset, sender, gas_meter, block)
+}
+
+/// Debit the sender for the gas actually used. The base fee part is burned and
+/// the tip goes to the block's fee recipient, less the stakers' share once
+/// validators are elected. The unused part of the pre-authorized
+/// `gas_limit * gas_price` is never debited, which refunds it.
+fn charge_gas(
+    changeset: &mut ChangeSet,
+    sender: AccountAddress,
+    gas_meter: &GasMeter,
+    block: &BlockContext,
+) -> Result<()> {
+    let gas_cost = gas_meter.total_cost();
+    let burned = gas_meter.gas_used.saturating_mul(block.base_fee);
+    let tip = gas_cost - burned;
+    let pooled = if block.pool_tips {
+        staker_tip_share(tip)
+    } else {
+        0
+    };
+    changeset.get_or_create_change(sender).debit(gas_cost);
+    changeset
+        .get_or_create_change(block.fee_recipient)
+        .credit(tip - pooled);
+    if pooled > 0 {
+        changeset
+            .get_or_create_change(StakingPool::address())
+            .credit(pooled);
+    }
+    changeset.set_gas_used(gas_meter.gas_used);
+    Ok(())
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BlockchainStats {
     pub height: u64,
@@ -589,6 +1988,73 @@ pub struct AccountInfo {
     pub balance: u64,
     pub sequence_number: u64,
     pub modules: Vec<String>,
+    /// Hex authentication key, if the account rotated its key
+    pub authentication_key: Option<String>,
+    /// Every non-zero coin balance, KANARI included
+    pub balances: Vec<CoinBalance>,
+}
+
+/// Balance of one coin type with the currency's `CoinMetadata`, when registered
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CoinBalance {
+    pub coin_type: String,
+    pub balance: u64,
+    pub symbol: Option<String>,
+    pub name: Option<String>,
+    pub decimals: Option<u8>,
+}
+
+/// An object from the object store, with its contents decoded
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObjectInfo {
+    pub id: String,
+    /// Owning address; `None` for shared and immutable objects
+    pub owner: Option<String>,
+    pub ownership: Ownership,
+    pub type_tag: String,
+    pub bcs: Vec<u8>,
+    pub json: serde_json::Value,
+}
+
+fn object_info(runtime: &MoveRuntime, object: &Object) -> ObjectInfo {
+    let json = parse_type_tag(&object.type_tag)
+        .map(|type_tag| runtime.decode_json(&type_tag, &object.contents))
+        .unwrap_or(serde_json::Value::Null);
+    ObjectInfo {
+        id: format!("{:#x}", object.id),
+        owner: object.owner().map(|owner| format!("{:#x}", owner)),
+        ownership: object.ownership,
+        type_tag: object.type_tag.clone(),
+        bcs: object.contents.clone(),
+        json,
+    }
+}
+
+/// An NFT and the collection it was minted in
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NftInfo {
+    /// Owning address; `None` for shared and immutable NFTs
+    pub owner: Option<String>,
+    pub nft: Nft,
+    /// `None` if the collection is not in the object store, e.g. when it is
+    /// wrapped in another object
+    pub collection: Option<Collection>,
+}
+
+/// `object` with its collection from `storage`, if it is an NFT
+fn nft_info(storage: &MoveStorage, object: &Object) -> Result<Option<NftInfo>> {
+    let Some(nft) = Nft::from_object(object)? else {
+        return Ok(None);
+    };
+    let collection = match storage.object(&nft.collection) {
+        Some(collection) => Collection::from_object(collection)?,
+        None => None,
+    };
+    Ok(Some(NftInfo {
+        owner: object.owner().map(|owner| format!("{:#x}", owner)),
+        nft,
+        collection,
+    }))
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -597,10 +2063,52 @@ pub struct BlockData {
     pub timestamp: u64,
     pub hash: String,
     pub prev_hash: String,
+    pub state_root: String,
     pub tx_count: usize,
+    pub producer: Option<String>,
+    pub base_fee: u64,
+    pub gas_used: u64,
     pub events: Vec<Event>,
 }
 
+/// Chain activity delivered to `BlockchainEngine::subscribe` listeners
+#[derive(Debug, Clone)]
+pub enum ChainEvent {
+    /// A signed transaction was accepted into the mempool
+    Transaction(SignedTransaction),
+    /// A block was produced locally or imported from a peer
+    Block(Block),
+}
+
+/// Outcome of executing a block's transactions
+struct BlockExecution {
+    executed: usize,
+    failed: usize,
+    events: Vec<Event>,
+    /// Indices of transactions that successfully published a module
+    published: Vec<usize>,
+    gas_used: u64,
+    receipts: Vec<TransactionReceipt>,
+}
+
+/// Outcome of importing a block from another producer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BlockImport {
+    /// Block was already known
+    Known,
+    /// Block extended the canonical tip
+    Extended { height: u64 },
+    /// Block was stored on a side branch that does not (yet) win fork choice
+    SideChain { height: u64 },
+    /// Block's branch became canonical
+    Reorg {
+        common_ancestor: u64,
+        reverted: u64,
+        applied: u64,
+        orphaned_txs: usize,
+    },
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BlockInfo {
     pub height: u64,
@@ -609,11 +2117,65 @@ pub struct BlockInfo {
     pub executed: usize,
     pub failed: usize,
     pub events: Vec<Event>,
+    pub receipts: Vec<TransactionReceipt>,
+}
+
+/// Outcome and gas settlement of an executed transaction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransactionReceipt {
+    /// Hex hash of the signed transaction, as returned by `submit_transaction`
+    pub tx_hash: String,
+    /// Block that executed the transaction; for a call that draws randomness,
+    /// the one after the block that included it
+    pub block_height: u64,
+    pub success: bool,
+    pub error_message: Option<String>,
+    pub gas_limit: u64,
+    /// Maximum fee per gas unit the sender authorized
+    pub gas_price: u64,
+    pub gas_used: u64,
+    /// Base fee of the block, burned per gas unit
+    pub base_fee: u64,
+    /// Price per gas unit actually paid: base fee plus tip
+    pub effective_gas_price: u64,
+    /// Mist charged to the sender, `gas_used * effective_gas_price`
+    pub gas_charged: u64,
+    /// Mist of the charge burned, `gas_used * base_fee`
+    pub gas_burned: u64,
+    /// Mist of the pre-authorized `gas_limit * gas_price` left with the sender
+    pub gas_refunded: u64,
+}
+
+impl TransactionReceipt {
+    fn new(signed_tx: &SignedTransaction, block: &BlockContext, changeset: &ChangeSet) -> Self {
+        let tx = &signed_tx.transaction;
+        // Executed transactions always cover the base fee
+        let effective_gas_price = tx.effective_gas_price(block.base_fee).unwrap_or_default();
+        let gas_charged = changeset.gas_used.saturating_mul(effective_gas_price);
+        Self {
+            tx_hash: hex::encode(signed_tx.hash()),
+            block_height: block.height,
+            success: changeset.success,
+            error_message: changeset.error_message.clone(),
+            gas_limit: tx.gas_limit(),
+            gas_price: tx.gas_price(),
+            gas_used: changeset.gas_used,
+            base_fee: block.base_fee,
+            effective_gas_price,
+            gas_charged,
+            gas_burned: changeset.gas_used.saturating_mul(block.base_fee),
+            gas_refunded: tx
+                .gas_limit()
+                .saturating_mul(tx.gas_price())
+                .saturating_sub(gas_charged),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use kanari_crypto::keys::{KeyPair, authentication_key, generate_keypair};
 
     fn set_unique_move_vm_db_env() {
         // Create a unique temp directory using a small loop that attempts to create a directory
@@ -658,9 +2220,7 @@ mod tests {
 
     #[test]
     fn test_engine_creation() {
-        set_unique_move_vm_db_env();
-
-        let engine = BlockchainEngine::new().unwrap();
+        let engine = new_engine();
         let stats = engine.get_stats();
         assert_eq!(stats.height, 0);
         assert_eq!(stats.total_blocks, 1);
@@ -668,12 +2228,8 @@ mod tests {
 
     #[test]
     fn test_submit_transaction() {
-        use kanari_crypto::keys::{CurveType, generate_keypair};
-
         // Ensure runtime DB is isolated per-test
-        set_unique_move_vm_db_env();
-
-        let engine = BlockchainEngine::new().unwrap();
+        let engine = new_engine();
 
         // Generate keypair and use its address as sender
         let keypair = generate_keypair(CurveType::Ed25519).unwrap();
@@ -697,9 +2253,7 @@ mod tests {
         use crate::changeset::Event;
 
         // Use isolated RocksDB path for this test to avoid conflicts
-        set_unique_move_vm_db_env();
-
-        let engine = BlockchainEngine::new().unwrap();
+        let engine = new_engine();
 
         // Prepare a ChangeSet with one event and apply it to state
         let mut cs = ChangeSet::new();
@@ -737,4 +2291,2126 @@ mod tests {
         assert!(!block_info.events.is_empty());
         assert_eq!(block_info.events[0].type_tag, "kanari::TransferEvent");
     }
+
+    #[test]
+    fn test_prune_with_retention_modes() {
+        let engine = new_engine();
+        for _ in 0..6 {
+            produce(&engine, transfer(&engine, 10));
+        }
+
+        // Archive keeps everything
+        assert!(engine.prune().is_empty());
+        assert_eq!(engine.state_history.read().unwrap().len(), 7);
+
+        engine.set_retention_mode(RetentionMode::Checkpoints {
+            interval: 2,
+            keep_last: 2,
+        });
+        let stats = engine.prune();
+        assert_eq!(stats.blocks_removed, 0);
+        assert_eq!(stats.bodies_pruned, 5);
+        // States at 0, 2, 4 (checkpoints) and 5, 6 (recent) remain
+        assert_eq!(stats.states_removed, 2);
+        assert!(engine.export_snapshot(4).is_ok());
+        assert!(engine.export_snapshot(3).is_err());
+        assert_eq!(engine.get_block(1).unwrap().tx_count, 1);
+        assert!(engine.blockchain.read().unwrap().blocks[1].body_pruned);
+        assert_eq!(engine.get_stats().total_transactions, 6);
+
+        engine.set_retention_mode(RetentionMode::KeepLast(3));
+        let stats = engine.prune();
+        assert_eq!(stats.blocks_removed, 4);
+        assert!(engine.get_block(3).is_none());
+        assert_eq!(engine.get_block(4).unwrap().height, 4);
+        assert_eq!(engine.get_stats().height, 6);
+        assert_eq!(
+            engine
+                .state_history
+                .read()
+                .unwrap()
+                .keys()
+                .copied()
+                .collect::<Vec<_>>(),
+            vec![4, 5, 6]
+        );
+    }
+
+    #[test]
+    fn test_state_history_is_capped() {
+        let engine = new_engine();
+        engine.set_state_history_limit(3);
+        for _ in 0..5 {
+            produce(&engine, transfer(&engine, 10));
+        }
+
+        // Archive retention keeps the blocks, but only the latest states
+        assert_eq!(engine.get_stats().total_blocks, 6);
+        assert_eq!(
+            engine
+                .state_history
+                .read()
+                .unwrap()
+                .keys()
+                .copied()
+                .collect::<Vec<_>>(),
+            vec![3, 4, 5]
+        );
+        assert!(engine.export_snapshot(2).is_err());
+        assert!(
+            engine
+                .view_function("0x1::m", "f", &[], vec![], Some(1))
+                .is_err()
+        );
+    }
+
+    /// Transfer from the dev account using its current on-chain sequence number
+    fn transfer(engine: &BlockchainEngine, amount: u64) -> Transaction {
+        let from = KanariAddress::DEV_ADDRESS.to_string();
+        let sequence_number = engine.get_account_info(&from).unwrap().sequence_number;
+        Transaction::Transfer {
+            from,
+            to: "0x42".to_string(),
+            amount,
+            coin_type: None,
+            gas_limit: 100_000,
+            gas_price: 1000,
+            priority_fee: 0,
+            sequence_number,
+        }
+    }
+
+    /// Ed25519 private keys of the accounts tests sign for, by account address
+    static KEYS: Mutex<BTreeMap<AccountAddress, String>> = Mutex::new(BTreeMap::new());
+
+    /// Key controlling the dev account of engines from `new_engine`
+    fn dev_key() -> &'static KeyPair {
+        static DEV_KEY: std::sync::OnceLock<KeyPair> = std::sync::OnceLock::new();
+        DEV_KEY.get_or_init(|| generate_keypair(CurveType::Ed25519).unwrap())
+    }
+
+    /// Engine with its own Move VM database and a dev account controlled by `dev_key`
+    fn new_engine() -> BlockchainEngine {
+        set_unique_move_vm_db_env();
+        let public_key = hex::decode(&dev_key().public_key).unwrap();
+        BlockchainEngine::with_dev_authentication_key(authentication_key(
+            CurveType::Ed25519,
+            &public_key,
+        ))
+        .unwrap()
+    }
+
+    /// Address of a fresh Ed25519 account that `sign` can sign for
+    fn new_account() -> String {
+        let keypair = generate_keypair(CurveType::Ed25519).unwrap();
+        let address = AccountAddress::from_hex_literal(&keypair.address).unwrap();
+        KEYS.lock().unwrap().insert(address, keypair.private_key);
+        keypair.address
+    }
+
+    /// Sign `tx` with the key of its sender: the dev account's, or one from
+    /// `new_account`
+    fn sign(tx: &Transaction) -> SignedTransaction {
+        let sender = AccountAddress::from_hex_literal(tx.sender_address()).unwrap();
+        let private_key = KEYS
+            .lock()
+            .unwrap()
+            .get(&sender)
+            .cloned()
+            .unwrap_or_else(|| dev_key().private_key.clone());
+        let mut signed = SignedTransaction::new(tx.clone());
+        signed.sign(&private_key, CurveType::Ed25519).unwrap();
+        signed
+    }
+
+    fn produce(engine: &BlockchainEngine, tx: Transaction) -> Block {
+        engine.pending_txs.write().unwrap().push(sign(&tx));
+        let info = engine.produce_block().unwrap();
+        let chain = engine.blockchain.read().unwrap();
+        chain.get_block(info.height).unwrap().clone()
+    }
+
+    fn publish(engine: &BlockchainEngine, module_name: &str, module_bytes: Vec<u8>) -> Transaction {
+        let sender = KanariAddress::DEV_ADDRESS.to_string();
+        let sequence_number = engine.get_account_info(&sender).unwrap().sequence_number;
+        Transaction::PublishModule {
+            sender,
+            module_bytes,
+            module_name: module_name.to_string(),
+            gas_limit: 1_000_000,
+            gas_price: 1000,
+            priority_fee: 0,
+            sequence_number,
+        }
+    }
+
+    #[test]
+    fn test_contracts_registered_from_committed_publishes() {
+        use move_binary_format::file_format::empty_module;
+        use move_core_types::identifier::Identifier;
+
+        let engine = new_engine();
+
+        // A publish the VM rejects is charged but never registered
+        produce(&engine, publish(&engine, "broken", vec![0xde, 0xad]));
+        assert_eq!(engine.get_contract_count(), 0);
+
+        let mut module = empty_module();
+        module.address_identifiers[0] =
+            AccountAddress::from_hex_literal(KanariAddress::DEV_ADDRESS).unwrap();
+        module.identifiers[0] = Identifier::new("counter").unwrap();
+        let mut bytes = vec![];
+        module.serialize(&mut bytes).unwrap();
+
+        let tx = publish(&engine, "counter", bytes.clone());
+        let block = produce(&engine, tx.clone());
+        let contract = engine
+            .get_contract(KanariAddress::DEV_ADDRESS, "counter")
+            .expect("committed module is registered");
+        assert_eq!(contract.deployed_at, block.header.height);
+        assert_eq!(contract.deployment_tx, sign(&tx).hash());
+        assert_eq!(contract.bytecode, bytes);
+        assert_eq!(engine.get_contract_count(), 1);
+    }
+
+    /// Module `DEV_ADDRESS::pair` with a view function
+    /// `public fun make(a: u64): Pair { Pair { a, b: true } }` and an entry function `poke()`
+    fn view_module() -> Vec<u8> {
+        use move_binary_format::file_format::*;
+        use move_core_types::identifier::Identifier;
+
+        let mut module = empty_module();
+        module.address_identifiers[0] =
+            AccountAddress::from_hex_literal(KanariAddress::DEV_ADDRESS).unwrap();
+        module.identifiers = ["pair", "Pair", "a", "b", "make", "poke"]
+            .iter()
+            .map(|name| Identifier::new(*name).unwrap())
+            .collect();
+        module.struct_handles.push(StructHandle {
+            module: ModuleHandleIndex(0),
+            name: IdentifierIndex(1),
+            abilities: AbilitySet::EMPTY | Ability::Copy | Ability::Drop,
+            type_parameters: vec![],
+        });
+        module.struct_defs.push(StructDefinition {
+            struct_handle: StructHandleIndex(0),
+            field_information: StructFieldInformation::Declared(vec![
+                FieldDefinition {
+                    name: IdentifierIndex(2),
+                    signature: TypeSignature(SignatureToken::U64),
+                },
+                FieldDefinition {
+                    name: IdentifierIndex(3),
+                    signature: TypeSignature(SignatureToken::Bool),
+                },
+            ]),
+        });
+        module.signatures.push(Signature(vec![SignatureToken::U64]));
+        module
+            .signatures
+            .push(Signature(vec![SignatureToken::Struct(StructHandleIndex(
+                0,
+            ))]));
+        module.function_handles = vec![
+            FunctionHandle {
+                module: ModuleHandleIndex(0),
+                name: IdentifierIndex(4),
+                parameters: SignatureIndex(1),
+                return_: SignatureIndex(2),
+                type_parameters: vec![],
+            },
+            FunctionHandle {
+                module: ModuleHandleIndex(0),
+                name: IdentifierIndex(5),
+                parameters: SignatureIndex(0),
+                return_: SignatureIndex(0),
+                type_parameters: vec![],
+            },
+        ];
+        module.function_defs = vec![
+            FunctionDefinition {
+                function: FunctionHandleIndex(0),
+                visibility: Visibility::Public,
+                is_entry: false,
+                acquires_global_resources: vec![],
+                code: Some(CodeUnit {
+                    locals: SignatureIndex(0),
+                    code: vec![
+                        Bytecode::MoveLoc(0),
+                        Bytecode::LdTrue,
+                        Bytecode::Pack(StructDefinitionIndex(0)),
+                        Bytecode::Ret,
+                    ],
+                }),
+            },
+            FunctionDefinition {
+                function: FunctionHandleIndex(1),
+                visibility: Visibility::Private,
+                is_entry: true,
+                acquires_global_resources: vec![],
+                code: Some(CodeUnit {
+                    locals: SignatureIndex(0),
+                    code: vec![Bytecode::Ret],
+                }),
+            },
+        ];
+        let mut bytes = vec![];
+        module.serialize(&mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_view_function() {
+        let engine = new_engine();
+        let module = format!("{}::pair", KanariAddress::DEV_ADDRESS);
+        let arg = bcs::to_bytes(&7u64).unwrap();
+
+        produce(&engine, publish(&engine, "pair", view_module()));
+        let stats_before = engine.get_stats();
+        let dev_before = engine.get_account_info(KanariAddress::DEV_ADDRESS).unwrap();
+
+        let result = engine
+            .view_function(&module, "make", &[], vec![arg.clone()], None)
+            .unwrap();
+        assert_eq!(result.height, 1);
+        assert_eq!(result.return_values.len(), 1);
+        let value = &result.return_values[0];
+        assert!(value.type_tag.ends_with("::pair::Pair"));
+        assert_eq!(value.bcs, vec![7, 0, 0, 0, 0, 0, 0, 0, 1]);
+        assert_eq!(value.json, serde_json::json!({ "a": 7, "b": true }));
+
+        // No transaction, gas or state change
+        let stats_after = engine.get_stats();
+        assert_eq!(stats_after.pending_transactions, 0);
+        assert_eq!(stats_after.height, stats_before.height);
+        let dev_after = engine.get_account_info(KanariAddress::DEV_ADDRESS).unwrap();
+        assert_eq!(dev_after.balance, dev_before.balance);
+        assert_eq!(dev_after.sequence_number, dev_before.sequence_number);
+
+        // Historical state: the module did not exist at genesis
+        produce(&engine, transfer(&engine, 10));
+        let at_1 = engine
+            .view_function(&module, "make", &[], vec![arg.clone()], Some(1))
+            .unwrap();
+        assert_eq!(at_1.height, 1);
+        assert_eq!(at_1.return_values[0].bcs, value.bcs);
+        assert!(
+            engine
+                .view_function(&module, "make", &[], vec![arg.clone()], Some(0))
+                .is_err()
+        );
+
+        // Entry functions without return values are not views
+        assert!(
+            engine
+                .view_function(&module, "poke", &[], vec![], None)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_view_function_out_of_gas() {
+        let engine = new_engine();
+        let modules = compile_with_framework(
+            r#"
+            module 0x42::spin {
+                public fun count(n: u64): u64 {
+                    let i = 0;
+                    while (i < n) {
+                        i = i + 1;
+                    };
+                    i
+                }
+            }
+            "#,
+        );
+        engine
+            .move_runtime
+            .write()
+            .unwrap()
+            .publish_modules_ordered(modules)
+            .unwrap();
+
+        let small = engine
+            .view_function(
+                "0x42::spin",
+                "count",
+                &[],
+                vec![bcs::to_bytes(&10u64).unwrap()],
+                None,
+            )
+            .unwrap();
+        assert_eq!(small.return_values[0].bcs, bcs::to_bytes(&10u64).unwrap());
+
+        // A view that would run forever stops at the per-transaction gas limit
+        let err = engine
+            .view_function(
+                "0x42::spin",
+                "count",
+                &[],
+                vec![bcs::to_bytes(&u64::MAX).unwrap()],
+                None,
+            )
+            .unwrap_err();
+        assert!(err.to_string().contains("ran out of gas"), "{err}");
+    }
+
+    #[test]
+    fn test_gas_charged_for_actual_usage() {
+        let engine = new_engine();
+        let dev = KanariAddress::DEV_ADDRESS;
+
+        // Native transfer: pre-authorized 100_000 gas at 1000, charged 21_000 at
+        // the base fee, which dropped from genesis since it used no gas
+        let tx = transfer(&engine, 10);
+        let balance_before = engine.get_account_info(dev).unwrap().balance;
+        engine.pending_txs.write().unwrap().push(sign(&tx));
+        let info = engine.produce_block().unwrap();
+        let receipt = &info.receipts[0];
+        assert!(receipt.success);
+        assert_eq!(receipt.gas_limit, 100_000);
+        assert_eq!(receipt.gas_used, 21_000);
+        assert_eq!(receipt.base_fee, 875);
+        assert_eq!(receipt.gas_charged, 21_000 * 875);
+        assert_eq!(receipt.gas_refunded, 100_000 * 1000 - 21_000 * 875);
+        assert_eq!(
+            engine.get_account_info(dev).unwrap().balance,
+            balance_before - 10 - 21_000 * 875
+        );
+        let stored = engine
+            .get_transaction_receipt(&hex::encode(sign(&tx).hash()))
+            .unwrap();
+        assert_eq!(stored.block_height, info.height);
+
+        // Move execution is metered on top of the base cost
+        produce(&engine, publish(&engine, "pair", view_module()));
+        let call = |gas_limit| {
+            let sender = dev.to_string();
+            let sequence_number = engine.get_account_info(dev).unwrap().sequence_number;
+            Transaction::ExecuteFunction {
+                sender,
+                module: format!("{}::pair", dev),
+                function: "poke".to_string(),
+                type_args: vec![],
+                args: vec![],
+                objects: vec![],
+                gas_limit,
+                gas_price: 1000,
+                priority_fee: 0,
+                sequence_number,
+            }
+        };
+        let base = GasOperation::ExecuteFunction { complexity: 0 }.gas_units();
+
+        engine
+            .pending_txs
+            .write()
+            .unwrap()
+            .push(sign(&call(200_000)));
+        let receipt = engine.produce_block().unwrap().receipts.remove(0);
+        assert!(receipt.success);
+        assert!(receipt.gas_used > base);
+        assert_eq!(
+            receipt.gas_refunded,
+            200_000 * receipt.gas_price - receipt.gas_used * receipt.effective_gas_price
+        );
+
+        // Running out of gas fails the call and consumes the whole limit
+        engine
+            .pending_txs
+            .write()
+            .unwrap()
+            .push(sign(&call(base + 1)));
+        let receipt = engine.produce_block().unwrap().receipts.remove(0);
+        assert!(!receipt.success);
+        assert_eq!(receipt.gas_used, base + 1);
+        assert_eq!(
+            receipt.gas_charged,
+            (base + 1) * receipt.effective_gas_price
+        );
+
+        // The whole limit must be covered up front
+        let mut tx = transfer(&engine, 10);
+        if let Transaction::Transfer { gas_price, .. } = &mut tx {
+            *gas_price = u64::MAX / 100_000;
+        }
+        engine.pending_txs.write().unwrap().push(sign(&tx));
+        let receipt = engine.produce_block().unwrap().receipts.remove(0);
+        assert!(!receipt.success);
+        assert_eq!(receipt.gas_used, 21_000);
+    }
+
+    #[test]
+    fn test_base_fee_burn_and_tip() {
+        let engine = new_engine();
+        let dao = KanariAddress::DAO_ADDRESS;
+        let config = crate::gas::GasConfig::default();
+
+        let mut tx = transfer(&engine, 10);
+        if let Transaction::Transfer { priority_fee, .. } = &mut tx {
+            *priority_fee = 100;
+        }
+        let supply_before = engine.get_stats().total_supply;
+        let dao_before = engine.get_account_info(dao).map_or(0, |acc| acc.balance);
+        let block = produce(&engine, tx);
+        let base_fee = config.next_base_fee(config.base_price, 0);
+        assert_eq!(block.header.base_fee, base_fee);
+        assert_eq!(block.header.gas_used, 21_000);
+
+        // Base fee burned, tip to the fee recipient (the DAO without a producer)
+        assert_eq!(
+            engine.get_stats().total_supply,
+            supply_before - 21_000 * base_fee
+        );
+        assert_eq!(
+            engine.get_account_info(dao).unwrap().balance,
+            dao_before + 21_000 * 100
+        );
+
+        // The next base fee follows the parent's gas used
+        let block = produce(&engine, transfer(&engine, 10));
+        assert_eq!(
+            block.header.base_fee,
+            config.next_base_fee(base_fee, 21_000)
+        );
+
+        // Transactions below the base fee stay pending
+        let mut tx = transfer(&engine, 10);
+        if let Transaction::Transfer { gas_price, .. } = &mut tx {
+            *gas_price = config.min_gas_price;
+        }
+        engine.pending_txs.write().unwrap().push(sign(&tx));
+        assert!(engine.produce_block().is_err());
+        assert_eq!(engine.get_stats().pending_transactions, 1);
+    }
+
+    #[test]
+    fn test_block_gas_limit() {
+        let node_a = new_engine();
+        let node_b = new_engine();
+        let config = crate::gas::GasConfig::default();
+        let per_block = (config.max_gas_per_block / config.max_gas_per_tx) as usize;
+
+        // Fill more than a block's budget with transactions at the per-tx maximum
+        let senders: Vec<String> = (0..per_block + 2).map(|_| new_account()).collect();
+        for sender in &senders {
+            let mut fund = transfer(&node_a, 10_000_000_000_000);
+            if let Transaction::Transfer { to, .. } = &mut fund {
+                *to = sender.clone();
+            }
+            produce(&node_a, fund);
+        }
+        for sender in &senders {
+            let mut tx = Transaction::new_transfer(sender.clone(), "0x42".to_string(), 1);
+            if let Transaction::Transfer { gas_limit, .. } = &mut tx {
+                *gas_limit = config.max_gas_per_tx;
+            }
+            node_a.pending_txs.write().unwrap().push(sign(&tx));
+        }
+        let info = node_a.produce_block().unwrap();
+        assert_eq!(info.tx_count, per_block);
+        assert_eq!(node_a.get_stats().pending_transactions, 2);
+        let info = node_a.produce_block().unwrap();
+        assert_eq!((info.tx_count, info.executed), (2, 2));
+
+        // Importers reject headers claiming more gas than a block may use
+        let mut block = node_a.blockchain.read().unwrap().blocks[1].clone();
+        block.header.gas_used = config.max_gas_per_block + 1;
+        let error = node_b.import_block(block).unwrap_err();
+        assert!(error.to_string().contains("over the limit"), "{}", error);
+    }
+
+    #[test]
+    fn test_get_resources() {
+        use move_core_types::language_storage::StructTag;
+
+        let engine = new_engine();
+        produce(&engine, publish(&engine, "pair", view_module()));
+
+        // Store a `pair::Pair` and a resource whose module was never published
+        let owner = AccountAddress::from_hex_literal("0x42").unwrap();
+        let pair =
+            parse_struct_tag(&format!("{}::pair::Pair", KanariAddress::DEV_ADDRESS)).unwrap();
+        let unknown: StructTag = parse_struct_tag("0x99::missing::Thing").unwrap();
+        {
+            let mut runtime = engine.move_runtime.write().unwrap();
+            let mut storage = runtime.storage().clone();
+            storage.set_resource(owner, pair.clone(), bcs::to_bytes(&(5u64, false)).unwrap());
+            storage.set_resource(owner, unknown, vec![1]);
+            runtime.restore_storage(storage).unwrap();
+        }
+
+        let resources = engine.get_resources("0x42").unwrap();
+        assert_eq!(resources.len(), 2);
+
+        let resource = engine
+            .get_resource("0x42", &pair.to_string())
+            .unwrap()
+            .expect("resource exists");
+        assert_eq!(resource.struct_tag, pair.to_string());
+        assert_eq!(resource.bcs, vec![5, 0, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(resource.json, serde_json::json!({ "a": 5, "b": false }));
+
+        // Raw bytes are still returned when the layout cannot be resolved
+        let raw = engine
+            .get_resource("0x42", "0x99::missing::Thing")
+            .unwrap()
+            .unwrap();
+        assert_eq!(raw.bcs, vec![1]);
+        assert!(raw.json.is_null());
+
+        assert!(
+            engine
+                .get_resource("0x43", &pair.to_string())
+                .unwrap()
+                .is_none()
+        );
+        assert!(engine.get_resource("0x42", "not a tag").is_err());
+    }
+
+    #[test]
+    fn test_decode_event() {
+        let engine = new_engine();
+        produce(&engine, publish(&engine, "pair", view_module()));
+
+        let event = Event {
+            key: vec![],
+            sequence_number: 0,
+            type_tag: format!("{}::pair::Pair", KanariAddress::DEV_ADDRESS),
+            event_data: bcs::to_bytes(&(3u64, true)).unwrap(),
+        };
+        assert_eq!(
+            engine.decode_event(&event),
+            serde_json::json!({ "a": 3, "b": true })
+        );
+
+        let unknown = Event {
+            type_tag: "kanari::TransferEvent".to_string(),
+            ..event
+        };
+        assert!(engine.decode_event(&unknown).is_null());
+    }
+
+    #[test]
+    fn test_rotate_authentication_key() {
+        let engine = new_engine();
+        let old_key = generate_keypair(CurveType::Ed25519).unwrap();
+        let new_key = generate_keypair(CurveType::Ed25519).unwrap();
+
+        let mut fund = transfer(&engine, 1_000_000_000);
+        if let Transaction::Transfer { to, .. } = &mut fund {
+            *to = old_key.address.clone();
+        }
+        produce(&engine, fund);
+
+        let new_auth_key = authentication_key(
+            CurveType::Ed25519,
+            &hex::decode(&new_key.public_key).unwrap(),
+        );
+        let rotate = Transaction::new_rotate_authentication_key(
+            old_key.address.clone(),
+            new_auth_key.clone(),
+        );
+        let mut signed = SignedTransaction::new(rotate);
+        signed
+            .sign(&old_key.private_key, CurveType::Ed25519)
+            .unwrap();
+        engine.submit_transaction(signed).unwrap();
+        engine.produce_block().unwrap();
+
+        let account = engine.get_account_info(&old_key.address).unwrap();
+        assert_eq!(account.sequence_number, 1);
+        assert_eq!(account.authentication_key, Some(hex::encode(&new_auth_key)));
+
+        let mut tx = Transaction::new_transfer(old_key.address.clone(), "0x2".to_string(), 10);
+        if let Transaction::Transfer {
+            sequence_number, ..
+        } = &mut tx
+        {
+            *sequence_number = 1;
+        }
+
+        // The old key no longer controls the account
+        let mut signed = SignedTransaction::new(tx.clone());
+        signed
+            .sign(&old_key.private_key, CurveType::Ed25519)
+            .unwrap();
+        assert!(engine.submit_transaction(signed).is_err());
+
+        // Address-based verification is refused once the key was rotated
+        let mut signed = SignedTransaction::new(tx.clone());
+        signed
+            .sign(&old_key.private_key, CurveType::Ed25519)
+            .unwrap();
+        assert!(signed.verify_authentication(None).unwrap());
+        assert!(!signed.verify_authentication(Some(&new_auth_key)).unwrap());
+        assert!(engine.submit_transaction(signed).is_err());
+
+        let mut signed = SignedTransaction::new(tx);
+        signed
+            .sign(&new_key.private_key, CurveType::Ed25519)
+            .unwrap();
+        engine.submit_transaction(signed).unwrap();
+        assert_eq!(engine.get_stats().pending_transactions, 1);
+    }
+
+    #[test]
+    fn test_chain_parameters_update_at_next_epoch() {
+        use crate::chain_params::{ChainParameters, DEFAULT_EPOCH_LENGTH};
+
+        let engine = new_engine();
+        let receipt = |tx: &Transaction| {
+            engine
+                .get_transaction_receipt(&hex::encode(sign(tx).hash()))
+                .unwrap()
+        };
+
+        // A holder with enough of the supply to reach quorum, so the dev
+        // account's weight stays unlocked for the transfers below
+        let holder = new_account();
+        let mut fund = transfer(&engine, engine.state.read().unwrap().total_supply / 5);
+        if let Transaction::Transfer { to, .. } = &mut fund {
+            *to = holder.clone();
+        }
+        produce(&engine, fund);
+        let governance = |action: GovernanceAction| {
+            let mut tx = Transaction::new_governance(holder.clone(), action);
+            if let Transaction::Governance {
+                sequence_number, ..
+            } = &mut tx
+            {
+                *sequence_number = engine.get_account_info(&holder).unwrap().sequence_number;
+            }
+            tx
+        };
+
+        let mut params = ChainParameters::default();
+        params.gas_schedule.transfer = 30_000;
+
+        // Parameters are staged only once a proposal to change them passes
+        let propose = governance(GovernanceAction::Propose(ProposalAction::ChangeParameters(
+            params.clone(),
+        )));
+        produce(&engine, propose.clone());
+        assert!(receipt(&propose).success);
+        let vote = governance(GovernanceAction::Vote {
+            proposal_id: 0,
+            approve: true,
+        });
+        produce(&engine, vote.clone());
+        assert!(receipt(&vote).success);
+
+        let voting_end = engine.governance().unwrap().proposals[0].voting_end_height;
+        while engine.blockchain.read().unwrap().height() < voting_end {
+            produce(&engine, transfer(&engine, 1));
+        }
+        assert_eq!(engine.chain_parameters().unwrap().pending, None);
+        produce(&engine, transfer(&engine, 1));
+        let stored = engine.chain_parameters().unwrap();
+        assert_eq!(stored.current, ChainParameters::default());
+        assert_eq!(stored.pending, Some(params.clone()));
+
+        // The old schedule applies until the epoch ends
+        let next_epoch = (voting_end / DEFAULT_EPOCH_LENGTH + 1) * DEFAULT_EPOCH_LENGTH;
+        while engine.blockchain.read().unwrap().height() < next_epoch - 1 {
+            let tx = transfer(&engine, 1);
+            produce(&engine, tx.clone());
+            assert_eq!(receipt(&tx).gas_used, 21_000);
+        }
+
+        let tx = transfer(&engine, 1);
+        let block = produce(&engine, tx.clone());
+        assert_eq!(block.header.height, next_epoch);
+        assert_eq!(receipt(&tx).gas_used, 30_000);
+        let stored = engine.chain_parameters().unwrap();
+        assert_eq!(stored.current, params);
+        assert_eq!(stored.pending, None);
+    }
+
+    #[test]
+    fn test_staking_rewards_and_unbonding() {
+        use crate::chain_params::DEFAULT_EPOCH_LENGTH;
+        use crate::staking::{MIN_VALIDATOR_STAKE, STAKER_TIP_SHARE_BPS, VALIDATOR_COMMISSION_BPS};
+
+        let engine = new_engine();
+        let dev = KanariAddress::DEV_ADDRESS.to_string();
+        let delegator = new_account();
+        let balance = |address: &str| engine.get_account_info(address).unwrap().balance;
+        let staking = |sender: &str, action: StakingAction| {
+            let mut tx = Transaction::new_staking(sender.to_string(), action);
+            if let Transaction::Staking {
+                sequence_number, ..
+            } = &mut tx
+            {
+                *sequence_number = engine.get_account_info(sender).unwrap().sequence_number;
+            }
+            tx
+        };
+        let tipped_transfer = || {
+            let mut tx = transfer(&engine, 1);
+            if let Transaction::Transfer {
+                to, priority_fee, ..
+            } = &mut tx
+            {
+                *to = "0x43".to_string();
+                *priority_fee = 100;
+            }
+            tx
+        };
+        let produce_until = |height: u64| {
+            while engine.blockchain.read().unwrap().height() < height {
+                produce(&engine, tipped_transfer());
+            }
+        };
+
+        let mut fund = transfer(&engine, 2 * MIN_VALIDATOR_STAKE);
+        if let Transaction::Transfer { to, .. } = &mut fund {
+            *to = delegator.clone();
+        }
+        produce(&engine, fund);
+        produce(
+            &engine,
+            staking(
+                &dev,
+                StakingAction::Stake {
+                    validator: dev.clone(),
+                    amount: MIN_VALIDATOR_STAKE,
+                },
+            ),
+        );
+        produce(
+            &engine,
+            staking(
+                &delegator,
+                StakingAction::Stake {
+                    validator: dev.clone(),
+                    amount: MIN_VALIDATOR_STAKE,
+                },
+            ),
+        );
+        let pool = engine.staking_pool().unwrap();
+        assert_eq!(pool.stakes.len(), 2);
+        assert!(pool.active_validators.is_empty());
+        assert!(balance(KanariAddress::KANARI_SYSTEM_ADDRESS) >= 2 * MIN_VALIDATOR_STAKE);
+
+        // Tips go to the producer (here the DAO) until validators are elected
+        produce_until(DEFAULT_EPOCH_LENGTH - 1);
+        assert_eq!(engine.staking_pool().unwrap().rewards, 0);
+
+        produce_until(DEFAULT_EPOCH_LENGTH);
+        let pool = engine.staking_pool().unwrap();
+        assert_eq!(pool.active_validators.len(), 1);
+        assert_eq!(pool.active_validators[0].stake, 2 * MIN_VALIDATOR_STAKE);
+        assert!(pool.rewards > 0);
+
+        // The producer (here the DAO) keeps its share of the tips
+        let dao = KanariAddress::DAO_ADDRESS;
+        let (dao_before, rewards_before) = (balance(dao), pool.rewards);
+        let tx = tipped_transfer();
+        produce(&engine, tx.clone());
+        let receipt = engine
+            .get_transaction_receipt(&hex::encode(sign(&tx).hash()))
+            .unwrap();
+        let tip = receipt.gas_charged - receipt.gas_burned;
+        let pooled = engine.staking_pool().unwrap().rewards - rewards_before;
+        assert_eq!(pooled, tip * STAKER_TIP_SHARE_BPS / 10_000);
+        assert_eq!(balance(dao) - dao_before, tip - pooled);
+
+        // Fees of the epoch are paid out at its end
+        produce_until(2 * DEFAULT_EPOCH_LENGTH - 1);
+        let rewards = engine.staking_pool().unwrap().rewards;
+        let before = balance(&delegator);
+        produce_until(2 * DEFAULT_EPOCH_LENGTH);
+        let commission = rewards * VALIDATOR_COMMISSION_BPS / 10_000;
+        assert_eq!(balance(&delegator) - before, (rewards - commission) / 2);
+
+        // Unstaked KANARI stays locked until the unbonding delay has passed
+        produce(
+            &engine,
+            staking(
+                &delegator,
+                StakingAction::Unstake {
+                    validator: dev.clone(),
+                    amount: MIN_VALIDATOR_STAKE,
+                },
+            ),
+        );
+        let withdraw = staking(&delegator, StakingAction::Withdraw);
+        produce(&engine, withdraw.clone());
+        let receipt = engine
+            .get_transaction_receipt(&hex::encode(sign(&withdraw).hash()))
+            .unwrap();
+        assert!(!receipt.success);
+        let pool = engine.staking_pool().unwrap();
+        assert_eq!(pool.unbonding.len(), 1);
+        assert_eq!(
+            pool.active_stake(&AccountAddress::from_hex_literal(&dev).unwrap(), 3),
+            MIN_VALIDATOR_STAKE
+        );
+    }
+
+    #[test]
+    fn test_post_quantum_transactions() {
+        use kanari_crypto::keys::account_address;
+
+        let engine = new_engine();
+
+        for curve in [CurveType::Dilithium3, CurveType::Ed25519Dilithium3] {
+            let keypair = generate_keypair(curve).unwrap();
+            let sender = account_address(curve, &keypair.public_key)
+                .unwrap()
+                .to_string();
+
+            let mut fund = transfer(&engine, 1_000_000_000);
+            if let Transaction::Transfer { to, .. } = &mut fund {
+                *to = sender.clone();
+            }
+            produce(&engine, fund);
+
+            let mut signed = SignedTransaction::new(Transaction::new_transfer(
+                sender.clone(),
+                "0x2".to_string(),
+                10,
+            ));
+            signed.sign(&keypair.private_key, curve).unwrap();
+
+            // Tampering with the signature invalidates it
+            let mut tampered = signed.clone();
+            if let Some(authenticator) = tampered.authenticator.as_mut() {
+                let last = authenticator.signature.len() - 1;
+                authenticator.signature[last] ^= 0x01;
+            }
+            assert!(engine.submit_transaction(tampered).is_err());
+
+            engine.submit_transaction(signed).unwrap();
+            let info = engine.produce_block().unwrap();
+            let gas_price = info.receipts[0].effective_gas_price;
+
+            let gas_units = GasOperation::Transfer.gas_units()
+                + GasOperation::SignatureVerification {
+                    signature_size: curve.signature_size(),
+                }
+                .gas_units();
+            let account = engine.get_account_info(&sender).unwrap();
+            assert_eq!(account.balance, 1_000_000_000 - 10 - gas_units * gas_price);
+        }
+    }
+
+    #[test]
+    fn test_under_gassed_post_quantum_transaction() {
+        use kanari_crypto::keys::account_address;
+
+        let engine = new_engine();
+        let curve = CurveType::SphincsPlusSha256Robust;
+        let keypair = generate_keypair(curve).unwrap();
+        let sender = account_address(curve, &keypair.public_key)
+            .unwrap()
+            .to_string();
+        let mut fund = transfer(&engine, 1_000_000_000);
+        if let Transaction::Transfer { to, .. } = &mut fund {
+            *to = sender.clone();
+        }
+        produce(&engine, fund);
+
+        // The default gas limit doesn't cover a SPHINCS+ signature
+        let mut signed = SignedTransaction::new(Transaction::new_transfer(
+            sender.clone(),
+            "0x2".to_string(),
+            10,
+        ));
+        signed.sign(&keypair.private_key, curve).unwrap();
+        let gas_limit = signed.transaction.gas_limit();
+        assert!(gas_limit < intrinsic_gas(&signed, &GasSchedule::default()));
+
+        let err = engine.submit_transaction(signed.clone()).unwrap_err();
+        assert!(err.to_string().contains("intrinsic gas"), "{}", err);
+
+        // Nor does it make it into a block, produced or imported
+        engine.pending_txs.write().unwrap().push(signed.clone());
+        assert!(engine.produce_block().is_err());
+        assert!(engine.pending_txs.read().unwrap().is_empty());
+
+        let (parent, context) = {
+            let chain = engine.blockchain.read().unwrap();
+            let parent = chain.latest_block().header.clone();
+            let context = engine.next_block_context(&parent, None, None).unwrap();
+            (parent, context)
+        };
+        let mut block = Block::new(
+            parent.height + 1,
+            parent.hash(),
+            vec![signed.clone()],
+            Vec::new(),
+        );
+        block.header.base_fee = context.base_fee;
+        block.header.random_beacon = RandomBeacon::produce(&parent, None).unwrap().beacon;
+        let err = engine.import_block(block).unwrap_err();
+        assert!(format!("{:#}", err).contains("intrinsic gas"), "{:#}", err);
+
+        // Should it run anyway, the sender pays the whole gas limit
+        let changeset = engine.execute_transaction(&signed, &context, None).unwrap();
+        assert!(!changeset.success);
+        assert_eq!(changeset.gas_used, gas_limit);
+        let gas_price = signed
+            .transaction
+            .effective_gas_price(context.base_fee)
+            .unwrap();
+        let sender = AccountAddress::from_hex_literal(&sender).unwrap();
+        let change = &changeset.account_changes[&sender];
+        assert_eq!(change.balance_delta, -((gas_limit * gas_price) as i64));
+    }
+
+    #[test]
+    fn test_gas_limit_below_base_cost() {
+        let engine = new_engine();
+        let context = {
+            let chain = engine.blockchain.read().unwrap();
+            engine
+                .next_block_context(&chain.latest_block().header, None, None)
+                .unwrap()
+        };
+        let dev = KanariAddress::DEV_ADDRESS.to_string();
+        let before = engine.get_account_info(&dev).unwrap();
+
+        let mut tx = transfer(&engine, 100);
+        if let Transaction::Transfer {
+            gas_limit,
+            gas_price,
+            ..
+        } = &mut tx
+        {
+            *gas_limit = GasOperation::Transfer.gas_units() - 1;
+            *gas_price = context.base_fee;
+        }
+        let signed = sign(&tx);
+        assert!(engine.submit_transaction(signed.clone()).is_err());
+
+        // Running out on the base operation fails the transaction and
+        // charges the whole gas limit, with nothing refunded
+        let changeset = engine.execute_transaction(&signed, &context, None).unwrap();
+        assert!(!changeset.success);
+        let receipt = TransactionReceipt::new(&signed, &context, &changeset);
+        assert_eq!(receipt.gas_used, tx.gas_limit());
+        assert_eq!(receipt.gas_charged, tx.gas_limit() * context.base_fee);
+        assert_eq!(receipt.gas_refunded, 0);
+
+        engine
+            .state
+            .write()
+            .unwrap()
+            .apply_changeset(&changeset)
+            .unwrap();
+        let after = engine.get_account_info(&dev).unwrap();
+        assert_eq!(after.sequence_number, before.sequence_number + 1);
+        assert_eq!(after.balance, before.balance - receipt.gas_charged);
+    }
+
+    #[test]
+    fn test_import_block_and_reorg() {
+        let node_a = new_engine();
+        let node_b = new_engine();
+        assert_eq!(
+            node_a.get_block(0).unwrap().hash,
+            node_b.get_block(0).unwrap().hash
+        );
+
+        // Shared block 1
+        let a1 = produce(&node_a, transfer(&node_a, 100));
+        assert_eq!(
+            node_b.import_block(a1.clone()).unwrap(),
+            BlockImport::Extended { height: 1 }
+        );
+        assert_eq!(node_b.import_block(a1).unwrap(), BlockImport::Known);
+
+        // Both nodes produce a competing block 2; A extends its branch to 3
+        let b2 = produce(&node_b, transfer(&node_b, 200));
+        let a2 = produce(&node_a, transfer(&node_a, 300));
+        let a3 = produce(&node_a, transfer(&node_a, 400));
+
+        assert_eq!(
+            node_b.import_block(a2.clone()).unwrap(),
+            BlockImport::SideChain { height: 2 }
+        );
+        assert_eq!(node_b.get_block(2).unwrap().hash, hex::encode(b2.hash()));
+
+        // B's mempool already holds a transaction the winning branch includes
+        node_b
+            .pending_txs
+            .write()
+            .unwrap()
+            .push(a2.transactions[0].clone());
+
+        assert_eq!(
+            node_b.import_block(a3.clone()).unwrap(),
+            BlockImport::Reorg {
+                common_ancestor: 1,
+                reverted: 1,
+                applied: 2,
+                orphaned_txs: 1,
+            }
+        );
+        assert_eq!(node_b.get_stats().height, 3);
+        assert_eq!(node_b.get_block(3).unwrap().hash, hex::encode(a3.hash()));
+        assert_eq!(node_b.compute_state_root(), node_a.compute_state_root());
+        assert_eq!(node_a.get_account_info("0x42").unwrap().balance, 800);
+        assert_eq!(node_b.get_account_info("0x42").unwrap().balance, 800);
+
+        // b2's transaction went back to the mempool, a2's left it, and the
+        // abandoned block is a side block
+        let pending = node_b.pending_txs.read().unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].hash(), b2.transactions[0].hash());
+        assert!(node_b.block_tree.read().unwrap().contains(&b2.hash()));
+    }
+
+    #[test]
+    fn test_import_block_rejects_bad_state_root() {
+        let node_a = new_engine();
+        let node_b = new_engine();
+
+        let mut block = produce(&node_a, transfer(&node_a, 100));
+        block.header.state_root = vec![7u8; 32];
+        let root_before = node_b.compute_state_root();
+
+        assert!(node_b.import_block(block).is_err());
+        assert_eq!(node_b.get_stats().height, 0);
+        assert_eq!(node_b.compute_state_root(), root_before);
+    }
+
+    #[test]
+    fn test_proof_of_authority_production() {
+        let key_a = generate_keypair(CurveType::Ed25519).unwrap();
+        let key_b = generate_keypair(CurveType::Ed25519).unwrap();
+        let signer_a = BlockSigner::new(&key_a.private_key, CurveType::Ed25519).unwrap();
+        let signer_b = BlockSigner::new(&key_b.private_key, CurveType::Ed25519).unwrap();
+        let validators =
+            ValidatorSet::new(vec![signer_a.validator(), signer_b.validator()]).unwrap();
+
+        let node_a = new_engine();
+        node_a.set_validators(validators.clone());
+        node_a.set_block_signer(signer_a);
+
+        let node_b = new_engine();
+        node_b.set_validators(validators);
+        node_b.set_block_signer(signer_b.clone());
+
+        // Height 1 is scheduled for node B
+        assert!(!node_a.is_leader_for_next_block());
+        assert!(node_b.is_leader_for_next_block());
+        node_a
+            .pending_txs
+            .write()
+            .unwrap()
+            .push(sign(&transfer(&node_a, 100)));
+        assert!(node_a.produce_block().is_err());
+
+        let b1 = produce(&node_b, transfer(&node_b, 100));
+        assert_eq!(
+            b1.header.producer.as_deref(),
+            Some(key_b.address.to_lowercase().as_str())
+        );
+        assert_eq!(
+            node_a.import_block(b1.clone()).unwrap(),
+            BlockImport::Extended { height: 1 }
+        );
+
+        // Node A now leads height 2; a block at height 2 signed by B is rejected
+        assert!(node_a.is_leader_for_next_block());
+        let mut forged = Block::new(2, b1.hash(), vec![], vec![]);
+        forged.header.state_root = node_a.compute_state_root();
+        forged.sign(&signer_b).unwrap();
+        assert!(node_a.import_block(forged).is_err());
+    }
+
+    #[test]
+    fn test_contract_randomness() {
+        let key = generate_keypair(CurveType::Ed25519).unwrap();
+        let signer = BlockSigner::new(&key.private_key, CurveType::Ed25519).unwrap();
+        let validators = ValidatorSet::new(vec![signer.validator()]).unwrap();
+        let node_a = new_engine();
+        let node_b = new_engine();
+        let unsigned = new_engine();
+        node_a.set_validators(validators.clone());
+        node_a.set_block_signer(signer);
+        node_b.set_validators(validators);
+
+        let dev = KanariAddress::DEV_ADDRESS.to_string();
+        let modules = compile_with_framework(
+            r#"
+            module 0x42::dice {
+                use kanari_system::event;
+                use kanari_system::random;
+
+                struct Rolled has copy, drop {
+                    value: u64,
+                }
+
+                entry fun roll(times: u64) {
+                    while (times > 0) {
+                        event::emit(Rolled { value: random::generate_u64_in_range(1, 6) });
+                        times = times - 1;
+                    }
+                }
+            }
+            "#,
+        );
+        for node in [&node_a, &node_b, &unsigned] {
+            node.move_runtime
+                .write()
+                .unwrap()
+                .publish_modules_ordered(modules.clone())
+                .unwrap();
+        }
+
+        let roll = Transaction::ExecuteFunction {
+            sender: dev.clone(),
+            module: "0x42::dice".to_string(),
+            function: "roll".to_string(),
+            type_args: vec![],
+            args: vec![bcs::to_bytes(&20u64).unwrap()],
+            objects: vec![],
+            gas_limit: 1_000_000,
+            gas_price: 1000,
+            priority_fee: 0,
+            sequence_number: 0,
+        };
+        let rolls = |block: &Block| -> Vec<u64> {
+            block
+                .events
+                .iter()
+                .filter(|event| event.type_tag == "0x42::dice::Rolled")
+                .map(|event| node_a.decode_event(event)["value"].as_u64().unwrap())
+                .collect()
+        };
+        let roll_hash = hex::encode(sign(&roll).hash());
+
+        // The call is included in block 1 but waits for the beacon of block 2,
+        // which nobody knew when it was chosen
+        let b1 = produce(&node_a, roll.clone());
+        assert!(rolls(&b1).is_empty());
+        assert!(node_a.get_transaction_receipt(&roll_hash).is_none());
+        assert_eq!(node_a.get_account_info(&dev).unwrap().sequence_number, 0);
+
+        let mut next = transfer(&node_a, 10);
+        if let Transaction::Transfer {
+            sequence_number, ..
+        } = &mut next
+        {
+            *sequence_number = 1;
+        }
+        let b2 = produce(&node_a, next);
+        let values = rolls(&b2);
+        assert_eq!(values.len(), 20);
+        assert!(values.iter().all(|value| (1..=6).contains(value)));
+        assert!(values.iter().any(|value| *value != values[0]));
+        assert_eq!(b2.header.random_beacon.len(), 32);
+        let receipt = node_a.get_transaction_receipt(&roll_hash).unwrap();
+        assert!(receipt.success);
+        assert_eq!(receipt.block_height, 2);
+
+        // A block whose beacon doesn't follow its parent's is rejected; the
+        // committed ones replay to the same state
+        node_b.import_block(b1).unwrap();
+        let mut tampered = b2.clone();
+        tampered.header.random_beacon = vec![7; 32];
+        assert!(node_b.import_block(tampered).is_err());
+        assert_eq!(
+            node_b.import_block(b2).unwrap(),
+            BlockImport::Extended { height: 2 }
+        );
+        assert_eq!(node_b.compute_state_root(), node_a.compute_state_root());
+
+        // Unsigned blocks only have a public hash chain, so randomness aborts
+        let b1 = produce(&unsigned, roll);
+        assert!(rolls(&b1).is_empty());
+        let receipt = unsigned.get_transaction_receipt(&roll_hash).unwrap();
+        assert!(!receipt.success);
+        assert!(
+            receipt
+                .error_message
+                .unwrap()
+                .contains("sub_status: Some(2)")
+        );
+    }
+
+    #[test]
+    fn test_nft_collection() {
+        use crate::object::derive_id;
+
+        let engine = new_engine();
+        engine
+            .move_runtime
+            .write()
+            .unwrap()
+            .publish_modules_ordered(compile_with_framework(""))
+            .unwrap();
+        let dev = KanariAddress::DEV_ADDRESS.to_string();
+        let call = |function: &str, objects: Vec<String>, args: Vec<Vec<u8>>| {
+            let sequence_number = engine.get_account_info(&dev).unwrap().sequence_number;
+            let tx = Transaction::ExecuteFunction {
+                sender: dev.clone(),
+                module: "0x2::nft".to_string(),
+                function: function.to_string(),
+                type_args: vec![],
+                args,
+                objects,
+                gas_limit: 1_000_000,
+                gas_price: 1000,
+                priority_fee: 0,
+                sequence_number,
+            };
+            let block = produce(&engine, tx.clone());
+            let receipt = engine
+                .get_transaction_receipt(&hex::encode(sign(&tx).hash()))
+                .unwrap();
+            (receipt, block.events)
+        };
+        let bytes = |value: &str| bcs::to_bytes(value.as_bytes()).unwrap();
+        let address =
+            |value: &str| bcs::to_bytes(&AccountAddress::from_hex_literal(value).unwrap()).unwrap();
+
+        // A collection capped at two NFTs, with a 2.5% royalty to 0x44
+        let (receipt, _) = call(
+            "create_collection",
+            vec![],
+            vec![
+                bytes("Birds"),
+                bytes("Pixel birds"),
+                bytes("https://example.com/birds"),
+                bcs::to_bytes(&2u64).unwrap(),
+                bcs::to_bytes(&250u16).unwrap(),
+                address("0x44"),
+            ],
+        );
+        assert!(receipt.success, "{:?}", receipt.error_message);
+        let collection = format!(
+            "{:#x}",
+            derive_id(&hex::decode(&receipt.tx_hash).unwrap(), 0)
+        );
+
+        let mint = |name: &str| {
+            call(
+                "mint_to",
+                vec![collection.clone()],
+                vec![
+                    bytes(name),
+                    bytes("A bird"),
+                    bytes("https://example.com/bird.png"),
+                    bcs::to_bytes(&vec![b"color".to_vec()]).unwrap(),
+                    bcs::to_bytes(&vec![b"red".to_vec()]).unwrap(),
+                    address(&dev),
+                ],
+            )
+        };
+        let (receipt, events) = mint("Robin");
+        assert!(receipt.success, "{:?}", receipt.error_message);
+        let minted = events
+            .iter()
+            .find(|event| event.type_tag == "0x2::nft::NftMinted")
+            .unwrap();
+        assert_eq!(engine.decode_event(minted)["number"], 1);
+
+        let nfts = engine.get_owned_nfts(&dev).unwrap();
+        assert_eq!(nfts.len(), 1);
+        let robin = &nfts[0];
+        assert_eq!(robin.nft.name, "Robin");
+        assert_eq!(robin.nft.url, "https://example.com/bird.png");
+        assert_eq!(robin.nft.attributes[0].value, "red");
+        let info = robin.collection.as_ref().unwrap();
+        assert_eq!(
+            (info.name.as_str(), info.supply, info.royalty_bps),
+            ("Birds", 1, 250)
+        );
+        let robin_id = format!("{:#x}", robin.nft.id);
+
+        // Transfers move the NFT; the creator burns the second one, which
+        // still counts towards the cap
+        assert!(
+            call("transfer", vec![robin_id.clone()], vec![address("0x43")])
+                .0
+                .success
+        );
+        assert!(engine.get_owned_nfts(&dev).unwrap().is_empty());
+        assert_eq!(engine.get_owned_nfts("0x43").unwrap().len(), 1);
+        assert!(mint("Wren").0.success);
+        let wren = format!("{:#x}", engine.get_owned_nfts(&dev).unwrap()[0].nft.id);
+        let (receipt, events) = call("burn", vec![collection.clone(), wren.clone()], vec![]);
+        assert!(receipt.success, "{:?}", receipt.error_message);
+        assert!(
+            events
+                .iter()
+                .any(|event| event.type_tag == "0x2::nft::NftBurned")
+        );
+        assert!(engine.get_nft(&wren).unwrap().is_none());
+        assert!(!mint("Crow").0.success);
+
+        let robin = engine.get_nft(&robin_id).unwrap().unwrap();
+        let info = robin.collection.unwrap();
+        assert_eq!((info.minted, info.supply), (2, 1));
+        assert!(engine.get_nft(&collection).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_snapshot_export_import() {
+        let engine = new_engine();
+        let genesis = engine.get_block(0).unwrap();
+        assert_eq!(
+            engine.blockchain.read().unwrap().blocks[0]
+                .header
+                .state_root,
+            engine.compute_state_root()
+        );
+
+        // Produce a block that changes state
+        let dev = KanariAddress::DEV_ADDRESS.to_string();
+        let tx = Transaction::new_transfer(dev, "0x42".to_string(), 5000);
+        engine.pending_txs.write().unwrap().push(sign(&tx));
+        let info = engine.produce_block().unwrap();
+        assert_eq!(info.executed, 1);
+
+        let snapshot = engine.export_snapshot(1).unwrap();
+        assert_eq!(snapshot.header.state_root, engine.compute_state_root());
+        assert!(engine.export_snapshot(7).is_err());
+
+        let bytes = snapshot.to_bytes().unwrap();
+
+        // Import into the same engine after rolling state back to genesis contents
+        let genesis_snapshot = engine.export_snapshot(0).unwrap();
+        engine
+            .import_snapshot(StateSnapshot::clone(&genesis_snapshot))
+            .unwrap();
+        assert_eq!(engine.get_stats().height, 0);
+        assert_eq!(engine.get_block(0).unwrap().hash, genesis.hash);
+        assert!(engine.get_account_info("0x42").is_none());
+
+        engine
+            .import_snapshot(StateSnapshot::from_bytes(&bytes).unwrap())
+            .unwrap();
+        assert_eq!(engine.get_stats().height, 1);
+        assert_eq!(engine.get_account_info("0x42").unwrap().balance, 5000);
+
+        let mut tampered = StateSnapshot::from_bytes(&bytes).unwrap();
+        tampered.total_supply += 1;
+        assert!(engine.import_snapshot(tampered).is_err());
+        assert_eq!(engine.get_stats().height, 1);
+    }
+
+    #[test]
+    fn test_governance_proposal_execution() {
+        use crate::governance::ProposalStatus;
+
+        let engine = new_engine();
+        let dev = KanariAddress::DEV_ADDRESS.to_string();
+        let receipt = |tx: &Transaction| {
+            engine
+                .get_transaction_receipt(&hex::encode(sign(tx).hash()))
+                .unwrap()
+        };
+        let governance = |sender: &str, action: GovernanceAction| {
+            let mut tx = Transaction::new_governance(sender.to_string(), action);
+            if let Transaction::Governance {
+                sequence_number, ..
+            } = &mut tx
+            {
+                *sequence_number = engine.get_account_info(sender).unwrap().sequence_number;
+            }
+            tx
+        };
+        let spend = |amount: u64| {
+            GovernanceAction::Propose(ProposalAction::TreasurySpend {
+                recipient: "0x44".to_string(),
+                amount,
+            })
+        };
+
+        // Fund the treasury and give a holder less than the proposal threshold
+        let holder = new_account();
+        let fund = |recipient: &str, amount: u64| {
+            let mut tx = transfer(&engine, amount);
+            if let Transaction::Transfer { to, .. } = &mut tx {
+                *to = recipient.to_string();
+            }
+            produce(&engine, tx);
+        };
+        fund(KanariAddress::DAO_ADDRESS, 1_000_000);
+        fund(&holder, PROPOSAL_THRESHOLD / 2);
+
+        let too_small = governance(&holder, spend(500));
+        produce(&engine, too_small.clone());
+        assert!(!receipt(&too_small).success);
+
+        produce(&engine, governance(&dev, spend(500)));
+        produce(&engine, governance(&dev, spend(u64::MAX / 2)));
+        produce(&engine, governance(&dev, spend(700)));
+        let store = engine.governance().unwrap();
+        assert_eq!(store.proposals.len(), 3);
+        let voting_end = store.proposals[2].voting_end_height;
+
+        let vote = |sender: &str, proposal_id: u64, approve: bool| {
+            let tx = governance(
+                sender,
+                GovernanceAction::Vote {
+                    proposal_id,
+                    approve,
+                },
+            );
+            produce(&engine, tx.clone());
+            receipt(&tx).success
+        };
+        assert!(vote(&dev, 0, true));
+        assert!(!vote(&dev, 0, true));
+        assert!(vote(&dev, 1, true));
+        assert!(vote(&dev, 2, false));
+        assert!(vote(&holder, 2, true));
+
+        // The holder's vote locks its weight, so it can't be passed on and voted again
+        let holder_transfer = || {
+            let mut tx = Transaction::new_transfer(holder.clone(), "0x45".to_string(), 1);
+            if let Transaction::Transfer {
+                sequence_number, ..
+            } = &mut tx
+            {
+                *sequence_number = engine.get_account_info(&holder).unwrap().sequence_number;
+            }
+            tx
+        };
+        let locked = holder_transfer();
+        produce(&engine, locked.clone());
+        assert!(!receipt(&locked).success);
+
+        // Proposals are closed at the first block after their voting period
+        while engine.blockchain.read().unwrap().height() < voting_end {
+            produce(&engine, transfer(&engine, 1));
+        }
+        assert_eq!(
+            engine.governance().unwrap().proposals[2].status,
+            ProposalStatus::Active
+        );
+        produce(&engine, transfer(&engine, 1));
+
+        let store = engine.governance().unwrap();
+        assert_eq!(store.proposals[0].status, ProposalStatus::Executed);
+        assert_eq!(engine.get_account_info("0x44").unwrap().balance, 500);
+        // Passed, but the treasury can't cover it
+        assert_eq!(store.proposals[1].status, ProposalStatus::Failed);
+        assert_eq!(store.proposals[2].status, ProposalStatus::Rejected);
+
+        let unlocked = holder_transfer();
+        produce(&engine, unlocked.clone());
+        assert!(receipt(&unlocked).success);
+    }
+
+    #[test]
+    fn test_multi_currency_transfer() {
+        use crate::coin::{Uid, coin_key};
+
+        let engine = new_engine();
+        let dev = KanariAddress::DEV_ADDRESS.to_string();
+        let dev_addr = AccountAddress::from_hex_literal(&dev).unwrap();
+        let usd = parse_type_tag("0x42::usd::USD").unwrap();
+        let receipt = |tx: &Transaction| {
+            engine
+                .get_transaction_receipt(&hex::encode(sign(tx).hash()))
+                .unwrap()
+        };
+        let coin_transfer = |amount: u64| {
+            let mut tx = Transaction::new_coin_transfer(
+                dev.clone(),
+                "0x43".to_string(),
+                usd.clone(),
+                amount,
+            );
+            if let Transaction::Transfer {
+                sequence_number, ..
+            } = &mut tx
+            {
+                *sequence_number = engine.get_account_info(&dev).unwrap().sequence_number;
+            }
+            tx
+        };
+
+        // Seed a USD balance and its metadata as the currency's module would
+        let mut mint = ChangeSet::new();
+        mint.get_or_create_change(dev_addr).credit_coin(&usd, 1_000);
+        engine
+            .state
+            .write()
+            .unwrap()
+            .apply_changeset(&mint)
+            .unwrap();
+        let metadata = CoinMetadata {
+            id: Uid {
+                addr: AccountAddress::from_hex_literal("0x42").unwrap(),
+            },
+            decimals: 6,
+            name: "US Dollar".to_string(),
+            symbol: "USD".to_string(),
+            description: String::new(),
+            icon_url: None,
+        };
+        metadata
+            .save(&mut engine.move_runtime.write().unwrap(), &usd)
+            .unwrap();
+        let supply = engine.state.read().unwrap().total_supply;
+
+        let tx = coin_transfer(400);
+        produce(&engine, tx.clone());
+        assert!(receipt(&tx).success);
+        let overdraw = coin_transfer(601);
+        produce(&engine, overdraw.clone());
+        assert!(!receipt(&overdraw).success);
+
+        let state = engine.state.read().unwrap();
+        assert_eq!(state.get_coin_balance(&dev_addr, &usd), 600);
+        let recipient = AccountAddress::from_hex_literal("0x43").unwrap();
+        assert_eq!(state.get_coin_balance(&recipient, &usd), 400);
+        // KANARI moved only as gas, and other coins never touch its supply
+        assert_eq!(state.get_coin_balance(&recipient, &kanari_coin_type()), 0);
+        assert!(state.total_supply <= supply);
+        drop(state);
+
+        let balances = engine.get_balances("0x43");
+        assert_eq!(
+            balances,
+            vec![CoinBalance {
+                coin_type: coin_key(&usd),
+                balance: 400,
+                symbol: Some("USD".to_string()),
+                name: Some("US Dollar".to_string()),
+                decimals: Some(6),
+            }]
+        );
+        let dev_balances = engine.get_account_info(&dev).unwrap().balances;
+        assert_eq!(dev_balances[0].symbol.as_deref(), Some("KANARI"));
+        assert_eq!(dev_balances[0].decimals, Some(9));
+        assert_eq!(dev_balances[1].balance, 600);
+    }
+
+    /// Compile the standard library, the framework and `extra` (a Move source
+    /// using the `std` and `kanari_system` named addresses) to module bytecode
+    fn compile_with_framework(extra: &str) -> Vec<Vec<u8>> {
+        use move_command_line_common::address::NumericalAddress;
+        use move_compiler::{Compiler, Flags};
+        use move_symbol_pool::Symbol;
+
+        let packages =
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../kanari-frameworks/packages");
+        let mut dirs = vec![
+            packages.join("move-stdlib/sources"),
+            packages.join("kanari-system/sources"),
+        ];
+        let mut targets = Vec::new();
+        while let Some(dir) = dirs.pop() {
+            for entry in std::fs::read_dir(dir).unwrap() {
+                let path = entry.unwrap().path();
+                if path.is_dir() {
+                    dirs.push(path);
+                } else if path.extension().is_some_and(|ext| ext == "move") {
+                    targets.push(Symbol::from(path.to_string_lossy().as_ref()));
+                }
+            }
+        }
+        let extra_path = std::path::PathBuf::from(std::env::var("KANARI_MOVE_VM_DB").unwrap())
+            .join("extra.move");
+        std::fs::write(&extra_path, extra).unwrap();
+        targets.push(Symbol::from(extra_path.to_string_lossy().as_ref()));
+
+        let named_addresses = [("std", "0x1"), ("kanari_system", "0x2")]
+            .into_iter()
+            .map(|(name, addr)| {
+                (
+                    Symbol::from(name),
+                    NumericalAddress::parse_str(addr).unwrap(),
+                )
+            })
+            .collect();
+        let (_, units) = Compiler::from_files(None, targets, vec![], named_addresses)
+            .set_flags(Flags::empty().set_silence_warnings(true))
+            .build_and_report()
+            .unwrap();
+        units
+            .into_iter()
+            .map(|unit| {
+                let mut bytes = vec![];
+                unit.into_compiled_unit()
+                    .module
+                    .serialize(&mut bytes)
+                    .unwrap();
+                bytes
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_minted_coins_credit_balances() {
+        let engine = new_engine();
+        let dev = KanariAddress::DEV_ADDRESS.to_string();
+        let dev_addr = AccountAddress::from_hex_literal(&dev).unwrap();
+        let recipient = AccountAddress::from_hex_literal("0x43").unwrap();
+        let usd = parse_type_tag("0x42::usd::USD").unwrap();
+        let modules = compile_with_framework(
+            r#"
+            module 0x42::usd {
+                use std::ascii;
+                use std::option;
+                use std::string;
+                use kanari_system::coin::{Self, TreasuryCap};
+                use kanari_system::transfer;
+                use kanari_system::tx_context::{Self, TxContext};
+
+                struct USD has drop {}
+
+                public entry fun launch(ctx: &mut TxContext) {
+                    let (cap, metadata) = coin::create_currency(
+                        USD {},
+                        6,
+                        ascii::string(b"USD"),
+                        string::utf8(b"US Dollar"),
+                        string::utf8(b""),
+                        option::none(),
+                        ctx,
+                    );
+                    transfer::public_freeze_object(metadata);
+                    transfer::public_transfer(cap, tx_context::sender(ctx))
+                }
+
+                public entry fun mint(
+                    cap: &mut TreasuryCap<USD>,
+                    amount: u64,
+                    recipient: address,
+                    ctx: &mut TxContext,
+                ) {
+                    coin::mint_and_transfer(cap, amount, recipient, ctx)
+                }
+            }
+            "#,
+        );
+        engine
+            .move_runtime
+            .write()
+            .unwrap()
+            .publish_modules_ordered(modules)
+            .unwrap();
+
+        let call = |function: &str, objects: Vec<String>, args: Vec<Vec<u8>>| {
+            let sequence_number = engine.get_account_info(&dev).unwrap().sequence_number;
+            let tx = Transaction::ExecuteFunction {
+                sender: dev.clone(),
+                module: "0x42::usd".to_string(),
+                function: function.to_string(),
+                type_args: vec![],
+                args,
+                objects,
+                gas_limit: 1_000_000,
+                gas_price: 1000,
+                priority_fee: 0,
+                sequence_number,
+            };
+            produce(&engine, tx.clone());
+            let receipt = engine
+                .get_transaction_receipt(&hex::encode(sign(&tx).hash()))
+                .unwrap();
+            assert!(receipt.success, "{:?}", receipt.error_message);
+        };
+        let balance =
+            |address: &AccountAddress| engine.state.read().unwrap().get_coin_balance(address, &usd);
+
+        call("launch", vec![], vec![]);
+        let cap = engine
+            .get_owned_objects(&dev)
+            .unwrap()
+            .into_iter()
+            .find(|o| o.type_tag == "0x2::coin::TreasuryCap<0x42::usd::USD>")
+            .unwrap()
+            .id;
+
+        // Coins minted to an address land in its USD balance, not in objects
+        let mint = |amount: u64, to: &AccountAddress| {
+            call(
+                "mint",
+                vec![cap.clone()],
+                vec![bcs::to_bytes(&amount).unwrap(), bcs::to_bytes(to).unwrap()],
+            );
+        };
+        mint(1_000, &dev_addr);
+        mint(250, &recipient);
+        assert_eq!(balance(&dev_addr), 1_000);
+        assert_eq!(balance(&recipient), 250);
+        assert!(
+            engine
+                .get_owned_objects(&dev)
+                .unwrap()
+                .iter()
+                .all(|o| !o.type_tag.starts_with("0x2::coin::Coin<"))
+        );
+
+        // and move with ordinary coin transfers
+        let mut tx =
+            Transaction::new_coin_transfer(dev.clone(), "0x43".to_string(), usd.clone(), 400);
+        if let Transaction::Transfer {
+            sequence_number, ..
+        } = &mut tx
+        {
+            *sequence_number = engine.get_account_info(&dev).unwrap().sequence_number;
+        }
+        produce(&engine, tx);
+        assert_eq!(balance(&dev_addr), 600);
+        assert_eq!(balance(&recipient), 650);
+    }
+
+    #[test]
+    fn test_object_ownership() {
+        use crate::object::derive_id;
+
+        let engine = new_engine();
+        let dev = KanariAddress::DEV_ADDRESS.to_string();
+        let modules = compile_with_framework(
+            r#"
+            module 0x42::pet {
+                use kanari_system::object::{Self, UID};
+                use kanari_system::transfer;
+                use kanari_system::tx_context::{Self, TxContext};
+
+                struct Pet has key, store {
+                    id: UID,
+                    age: u64,
+                }
+
+                public entry fun adopt(ctx: &mut TxContext) {
+                    let pet = Pet { id: object::new(ctx), age: 0 };
+                    transfer::public_transfer(pet, tx_context::sender(ctx))
+                }
+
+                public entry fun birthday(pet: &mut Pet) {
+                    pet.age = pet.age + 1
+                }
+
+                public entry fun give(pet: Pet, recipient: address) {
+                    transfer::public_transfer(pet, recipient)
+                }
+
+                public entry fun release(pet: Pet) {
+                    let Pet { id: _, age: _ } = pet;
+                }
+
+                public entry fun enshrine(pet: Pet) {
+                    transfer::public_freeze_object(pet)
+                }
+
+                struct Impostor has key, store {
+                    id: address,
+                    age: u64,
+                }
+
+                public entry fun forge(id: address) {
+                    transfer::public_transfer(Impostor { id, age: 99 }, @0x44)
+                }
+
+                public entry fun forge_derived(tx_hash: vector<u8>) {
+                    let id = tx_context::derive_id(tx_hash, 0);
+                    transfer::public_transfer(Impostor { id, age: 99 }, @0x44)
+                }
+            }
+            "#,
+        );
+        engine
+            .move_runtime
+            .write()
+            .unwrap()
+            .publish_modules_ordered(modules)
+            .unwrap();
+
+        let call = |function: &str, objects: Vec<String>, args: Vec<Vec<u8>>| {
+            let sequence_number = engine.get_account_info(&dev).unwrap().sequence_number;
+            let tx = Transaction::ExecuteFunction {
+                sender: dev.clone(),
+                module: "0x42::pet".to_string(),
+                function: function.to_string(),
+                type_args: vec![],
+                args,
+                objects,
+                gas_limit: 1_000_000,
+                gas_price: 1000,
+                priority_fee: 0,
+                sequence_number,
+            };
+            produce(&engine, tx.clone());
+            engine
+                .get_transaction_receipt(&hex::encode(sign(&tx).hash()))
+                .unwrap()
+        };
+        let adopt = || {
+            let receipt = call("adopt", vec![], vec![]);
+            assert!(receipt.success, "{:?}", receipt.error_message);
+            let id = derive_id(&hex::decode(&receipt.tx_hash).unwrap(), 0);
+            let pet = engine
+                .get_owned_objects(&dev)
+                .unwrap()
+                .into_iter()
+                .find(|o| o.id == format!("{:#x}", id))
+                .unwrap();
+            assert_eq!(pet.json["age"], 0);
+            pet.id
+        };
+
+        // Created objects are persisted under the derived id, owned by the sender
+        let pet = adopt();
+        let info = engine.get_object(&pet).unwrap().unwrap();
+        assert_eq!(info.type_tag, "0x42::pet::Pet");
+        assert_eq!(info.ownership, Ownership::Address);
+        let dev_addr = AccountAddress::from_hex_literal(&dev).unwrap();
+        assert_eq!(info.owner, Some(format!("{:#x}", dev_addr)));
+
+        // Mutable references write back, transfers change the owner
+        assert!(call("birthday", vec![pet.clone()], vec![]).success);
+        assert_eq!(engine.get_object(&pet).unwrap().unwrap().json["age"], 1);
+        let recipient = bcs::to_bytes(&AccountAddress::from_hex_literal("0x43").unwrap()).unwrap();
+        assert!(call("give", vec![pet.clone()], vec![recipient]).success);
+        assert_eq!(engine.get_owned_objects("0x43").unwrap().len(), 1);
+        assert!(engine.get_owned_objects(&dev).unwrap().is_empty());
+        let stolen = call("birthday", vec![pet.clone()], vec![]);
+        assert!(!stolen.success);
+        assert!(stolen.error_message.unwrap().contains("is owned by"));
+
+        // Objects can't be recorded under ids the transaction neither received
+        // by value nor created, so existing objects can't be overwritten
+        let pet_addr = AccountAddress::from_hex_literal(&pet).unwrap();
+        let forged = call("forge", vec![], vec![bcs::to_bytes(&pet_addr).unwrap()]);
+        assert!(!forged.success);
+        assert!(
+            forged
+                .error_message
+                .unwrap()
+                .contains("sub_status: Some(3)")
+        );
+        assert_eq!(engine.get_object(&pet).unwrap().unwrap().json["age"], 1);
+        let adopted = call("adopt", vec![], vec![]);
+        let tx_hash = hex::decode(&adopted.tx_hash).unwrap();
+        let forged = call(
+            "forge_derived",
+            vec![],
+            vec![bcs::to_bytes(&tx_hash).unwrap()],
+        );
+        assert!(!forged.success);
+        assert!(
+            forged
+                .error_message
+                .unwrap()
+                .contains("sub_status: Some(3)")
+        );
+        let id = format!("{:#x}", derive_id(&tx_hash, 0));
+        let info = engine.get_object(&id).unwrap().unwrap();
+        assert_eq!(info.type_tag, "0x42::pet::Pet");
+        assert!(engine.get_owned_objects("0x44").unwrap().is_empty());
+
+        // Frozen objects can no longer be mutated; unpacked ones are deleted
+        let frozen = adopt();
+        assert!(call("enshrine", vec![frozen.clone()], vec![]).success);
+        let info = engine.get_object(&frozen).unwrap().unwrap();
+        assert_eq!(info.ownership, Ownership::Immutable);
+        assert_eq!(info.owner, None);
+        assert!(!call("birthday", vec![frozen], vec![]).success);
+        let released = adopt();
+        assert!(call("release", vec![released.clone()], vec![]).success);
+        assert!(engine.get_object(&released).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_table_entries() {
+        use crate::object::{derive_id, field_id};
+        use move_core_types::language_storage::TypeTag;
+
+        let engine = new_engine();
+        let dev = KanariAddress::DEV_ADDRESS.to_string();
+        let dev_addr = AccountAddress::from_hex_literal(&dev).unwrap();
+        let modules = compile_with_framework(
+            r#"
+            module 0x42::registry {
+                use kanari_system::object::{Self, UID};
+                use kanari_system::table::{Self, Table};
+                use kanari_system::transfer;
+                use kanari_system::tx_context::{Self, TxContext};
+
+                struct Registry has key {
+                    id: UID,
+                    names: Table<address, vector<u8>>,
+                }
+
+                public entry fun create(ctx: &mut TxContext) {
+                    let registry = Registry { id: object::new(ctx), names: table::new(ctx) };
+                    transfer::share_object(registry)
+                }
+
+                public entry fun register(registry: &mut Registry, name: vector<u8>, ctx: &mut TxContext) {
+                    table::add(&mut registry.names, tx_context::sender(ctx), name)
+                }
+
+                public entry fun rename(registry: &mut Registry, name: vector<u8>, ctx: &mut TxContext) {
+                    *table::borrow_mut(&mut registry.names, tx_context::sender(ctx)) = name
+                }
+
+                public entry fun unregister(registry: &mut Registry, ctx: &mut TxContext) {
+                    table::remove(&mut registry.names, tx_context::sender(ctx));
+                }
+            }
+            "#,
+        );
+        engine
+            .move_runtime
+            .write()
+            .unwrap()
+            .publish_modules_ordered(modules)
+            .unwrap();
+
+        let call = |function: &str, objects: Vec<String>, args: Vec<Vec<u8>>| {
+            let sequence_number = engine.get_account_info(&dev).unwrap().sequence_number;
+            let tx = Transaction::ExecuteFunction {
+                sender: dev.clone(),
+                module: "0x42::registry".to_string(),
+                function: function.to_string(),
+                type_args: vec![],
+                args,
+                objects,
+                gas_limit: 1_000_000,
+                gas_price: 1000,
+                priority_fee: 0,
+                sequence_number,
+            };
+            produce(&engine, tx.clone());
+            engine
+                .get_transaction_receipt(&hex::encode(sign(&tx).hash()))
+                .unwrap()
+        };
+        let name = |name: &[u8]| bcs::to_bytes(&name.to_vec()).unwrap();
+        let fields = || {
+            let runtime = engine.move_runtime.read().unwrap();
+            runtime
+                .storage()
+                .fields()
+                .map(|(key, value)| (*key, value.clone()))
+                .collect::<Vec<_>>()
+        };
+
+        let receipt = call("create", vec![], vec![]);
+        assert!(receipt.success, "{:?}", receipt.error_message);
+        let tx_hash = hex::decode(&receipt.tx_hash).unwrap();
+        let registry = format!("{:#x}", derive_id(&tx_hash, 0));
+        let table = derive_id(&tx_hash, 1);
+        assert!(fields().is_empty());
+
+        // Each entry is its own storage key under the table's id
+        let receipt = call("register", vec![registry.clone()], vec![name(b"alice")]);
+        assert!(receipt.success, "{:?}", receipt.error_message);
+        let entries = fields();
+        let key = field_id(
+            &table,
+            &TypeTag::Address,
+            &bcs::to_bytes(&dev_addr).unwrap(),
+        );
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].0, (table, key));
+        assert_eq!(
+            entries[0].1.type_tag.to_string(),
+            "0x2::dynamic_field::Field<address, vector<u8>>"
+        );
+        let (owner, value): (AccountAddress, Vec<u8>) =
+            bcs::from_bytes(&entries[0].1.bytes).unwrap();
+        assert_eq!((owner, value.as_slice()), (dev_addr, b"alice".as_slice()));
+        let info = engine.get_object(&registry).unwrap().unwrap();
+        assert_eq!(info.json["names"]["size"], 1);
+
+        // Adding an existing key aborts; writes through references persist and
+        // are charged by size
+        assert!(!call("register", vec![registry.clone()], vec![name(b"bob")]).success);
+        let short = call("rename", vec![registry.clone()], vec![name(b"bob")]);
+        assert!(short.success, "{:?}", short.error_message);
+        let (_, value): (AccountAddress, Vec<u8>) = bcs::from_bytes(&fields()[0].1.bytes).unwrap();
+        assert_eq!(value, b"bob");
+        let long = call("rename", vec![registry.clone()], vec![name(&[b'x'; 2000])]);
+        assert!(long.gas_used > short.gas_used);
+
+        // Removed entries are deleted from storage
+        assert!(call("unregister", vec![registry.clone()], vec![]).success);
+        assert!(fields().is_empty());
+        assert!(!call("rename", vec![registry.clone()], vec![name(b"carol")]).success);
+        let info = engine.get_object(&registry).unwrap().unwrap();
+        assert_eq!(info.json["names"]["size"], 0);
+    }
+
+    #[test]
+    fn test_contract_events() {
+        let engine = new_engine();
+        let dev = KanariAddress::DEV_ADDRESS.to_string();
+        let modules = compile_with_framework(
+            r#"
+            module 0x42::counter {
+                use kanari_system::event;
+                use kanari_system::tx_context::{Self, TxContext};
+
+                struct Bumped has copy, drop {
+                    by: address,
+                    value: u64,
+                }
+
+                public entry fun bump(value: u64, ctx: &mut TxContext) {
+                    let by = tx_context::sender(ctx);
+                    event::emit(Bumped { by, value });
+                    event::emit(Bumped { by, value: value + 1 });
+                    assert!(value < 100, 0);
+                }
+            }
+            "#,
+        );
+        engine
+            .move_runtime
+            .write()
+            .unwrap()
+            .publish_modules_ordered(modules)
+            .unwrap();
+
+        let bump = |value: u64| {
+            let sequence_number = engine.get_account_info(&dev).unwrap().sequence_number;
+            let tx = Transaction::ExecuteFunction {
+                sender: dev.clone(),
+                module: "0x42::counter".to_string(),
+                function: "bump".to_string(),
+                type_args: vec![],
+                args: vec![bcs::to_bytes(&value).unwrap()],
+                objects: vec![],
+                gas_limit: 1_000_000,
+                gas_price: 1000,
+                priority_fee: 0,
+                sequence_number,
+            };
+            let block = produce(&engine, tx.clone());
+            let events: Vec<Event> = block
+                .events
+                .into_iter()
+                .filter(|event| event.type_tag == "0x42::counter::Bumped")
+                .collect();
+            (tx, events)
+        };
+
+        // Events carry their type, the emitting transaction and their order
+        let (tx, events) = bump(7);
+        assert_eq!(events.len(), 2);
+        for (index, event) in events.iter().enumerate() {
+            assert_eq!(event.key, sign(&tx).hash());
+            assert_eq!(event.sequence_number, index as u64);
+        }
+        let json = engine.decode_event(&events[1]);
+        assert_eq!(json["value"], 8);
+        let dev_addr = AccountAddress::from_hex_literal(&dev).unwrap();
+        assert_eq!(json["by"], format!("{:#x}", dev_addr));
+
+        // A failed transaction emits nothing
+        let (tx, events) = bump(100);
+        assert!(
+            !engine
+                .get_transaction_receipt(&hex::encode(sign(&tx).hash()))
+                .unwrap()
+                .success
+        );
+        assert!(events.is_empty());
+    }
 }
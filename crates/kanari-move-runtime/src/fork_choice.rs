@@ -0,0 +1,194 @@
+use crate::blockchain::{Block, Blockchain};
+use std::collections::{HashMap, VecDeque};
+
+/// Default number of blocks after which a block is considered final
+pub const DEFAULT_FINALITY_DEPTH: u64 = 64;
+
+/// Most blocks with an unknown parent kept at once; the oldest are dropped first
+pub const MAX_ORPHAN_BLOCKS: usize = 64;
+
+/// Known blocks that are not on the canonical chain, keyed by block hash.
+///
+/// The canonical chain itself lives in `Blockchain`; together they form the block
+/// tree. Fork choice is longest chain: a side branch becomes canonical only when
+/// it is strictly higher than the current tip (ties keep the current tip), and it
+/// must fork off above the finalized height (`tip - finality_depth`).
+#[derive(Debug, Clone)]
+pub struct BlockTree {
+    blocks: HashMap<Vec<u8>, Block>,
+    /// Hashes of blocks inserted before their parent was known, oldest first
+    orphans: VecDeque<Vec<u8>>,
+    finality_depth: u64,
+}
+
+impl Default for BlockTree {
+    fn default() -> Self {
+        Self::new(DEFAULT_FINALITY_DEPTH)
+    }
+}
+
+impl BlockTree {
+    pub fn new(finality_depth: u64) -> Self {
+        Self {
+            blocks: HashMap::new(),
+            orphans: VecDeque::new(),
+            finality_depth,
+        }
+    }
+
+    pub fn finality_depth(&self) -> u64 {
+        self.finality_depth
+    }
+
+    /// Highest height that can no longer be reverted
+    pub fn finalized_height(&self, tip: u64) -> u64 {
+        tip.saturating_sub(self.finality_depth)
+    }
+
+    pub fn insert(&mut self, block: Block) {
+        self.blocks.insert(block.hash(), block);
+    }
+
+    /// Keep a block whose parent is not known yet, evicting the oldest orphans
+    /// beyond `MAX_ORPHAN_BLOCKS`
+    pub fn insert_orphan(&mut self, block: Block) {
+        let hash = block.hash();
+        self.blocks.insert(hash.clone(), block);
+        self.orphans.push_back(hash);
+        while self.orphans.len() > MAX_ORPHAN_BLOCKS {
+            if let Some(oldest) = self.orphans.pop_front() {
+                self.blocks.remove(&oldest);
+            }
+        }
+    }
+
+    pub fn orphan_count(&self) -> usize {
+        self.orphans.len()
+    }
+
+    pub fn contains(&self, hash: &[u8]) -> bool {
+        self.blocks.contains_key(hash)
+    }
+
+    pub fn get(&self, hash: &[u8]) -> Option<&Block> {
+        self.blocks.get(hash)
+    }
+
+    pub fn remove(&mut self, hash: &[u8]) -> Option<Block> {
+        self.orphans.retain(|orphan| orphan.as_slice() != hash);
+        self.blocks.remove(hash)
+    }
+
+    pub fn len(&self) -> usize {
+        self.blocks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty()
+    }
+
+    /// Walk back from `tip_hash` through side blocks until reaching the canonical chain.
+    /// Returns the common ancestor height and the branch blocks in ascending order,
+    /// or `None` if the branch does not connect to the locally held canonical chain.
+    pub fn branch_from(&self, tip_hash: &[u8], chain: &Blockchain) -> Option<(u64, Vec<Block>)> {
+        let mut branch = Vec::new();
+        let mut cursor = self.blocks.get(tip_hash)?;
+        loop {
+            branch.push(cursor.clone());
+            let parent_height = cursor.header.height.checked_sub(1)?;
+            let parent_hash = &cursor.header.prev_hash;
+
+            if let Some(canonical) = chain.get_block(parent_height)
+                && &canonical.hash() == parent_hash
+            {
+                branch.reverse();
+                return Some((parent_height, branch));
+            }
+            cursor = self.blocks.get(parent_hash)?;
+        }
+    }
+
+    /// Longest-chain rule
+    pub fn prefers(&self, branch_tip_height: u64, canonical_tip_height: u64) -> bool {
+        branch_tip_height > canonical_tip_height
+    }
+
+    /// Drop side blocks that can no longer become canonical
+    pub fn prune_finalized(&mut self, tip: u64) -> usize {
+        let finalized = self.finalized_height(tip);
+        let before = self.blocks.len();
+        self.blocks.retain(|_, b| b.header.height > finalized);
+        let blocks = &self.blocks;
+        self.orphans.retain(|orphan| blocks.contains_key(orphan));
+        before - self.blocks.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blockchain::{SignedTransaction, Transaction};
+
+    fn extend(parent: &Block, tx_amount: u64) -> Block {
+        let tx = SignedTransaction::new(Transaction::new_transfer(
+            "0x1".to_string(),
+            "0x2".to_string(),
+            tx_amount,
+        ));
+        Block::new(parent.header.height + 1, parent.hash(), vec![tx], vec![])
+    }
+
+    #[test]
+    fn test_branch_from_finds_common_ancestor() {
+        let mut chain = Blockchain::new();
+        let b1 = extend(chain.latest_block(), 1);
+        chain.add_block(b1.clone()).unwrap();
+        let b2 = extend(&b1, 2);
+        chain.add_block(b2).unwrap();
+
+        // Competing branch off b1
+        let mut tree = BlockTree::new(10);
+        let s2 = extend(&b1, 20);
+        let s3 = extend(&s2, 30);
+        tree.insert(s2.clone());
+        tree.insert(s3.clone());
+
+        let (ancestor, branch) = tree.branch_from(&s3.hash(), &chain).unwrap();
+        assert_eq!(ancestor, 1);
+        assert_eq!(
+            branch.iter().map(|b| b.hash()).collect::<Vec<_>>(),
+            vec![s2.hash(), s3.hash()]
+        );
+        assert!(tree.prefers(3, chain.height()));
+        assert!(!tree.prefers(2, chain.height()));
+
+        // Disconnected blocks have no branch
+        let orphan = extend(&extend(&s3, 40), 50);
+        tree.insert(orphan.clone());
+        assert!(tree.branch_from(&orphan.hash(), &chain).is_none());
+
+        assert_eq!(tree.prune_finalized(12), 1);
+        assert!(tree.contains(&s3.hash()));
+    }
+
+    #[test]
+    fn test_orphans_are_capped() {
+        let chain = Blockchain::new();
+        let mut tree = BlockTree::new(10);
+        let parent = extend(chain.latest_block(), 1);
+
+        let orphans: Vec<Block> = (0..MAX_ORPHAN_BLOCKS as u64 + 1)
+            .map(|amount| extend(&parent, amount))
+            .collect();
+        for orphan in &orphans {
+            tree.insert_orphan(orphan.clone());
+        }
+        assert_eq!(tree.orphan_count(), MAX_ORPHAN_BLOCKS);
+        assert_eq!(tree.len(), MAX_ORPHAN_BLOCKS);
+        assert!(!tree.contains(&orphans[0].hash()));
+        assert!(tree.contains(&orphans[MAX_ORPHAN_BLOCKS].hash()));
+
+        tree.remove(&orphans[1].hash());
+        assert_eq!(tree.orphan_count(), MAX_ORPHAN_BLOCKS - 1);
+    }
+}
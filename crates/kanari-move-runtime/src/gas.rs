@@ -1,7 +1,14 @@
+use move_binary_format::errors::{PartialVMError, PartialVMResult};
+use move_core_types::gas_algebra::{InternalGas, NumArgs, NumBytes};
+use move_core_types::language_storage::ModuleId;
+use move_core_types::vm_status::StatusCode;
+use move_vm_profiler::GasProfiler;
+use move_vm_types::gas::{GasMeter as MoveVMGasMeter, SimpleInstruction};
+use move_vm_types::views::{TypeView, ValueView};
 use serde::{Deserialize, Serialize};
 
 /// Gas configuration and pricing for the Kanari blockchain
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct GasConfig {
     /// Base gas price per unit (in Mist)
     pub base_price: u64,
@@ -27,6 +34,35 @@ impl Default for GasConfig {
     }
 }
 
+/// Largest change of the base fee from one block to the next is
+/// `1 / BASE_FEE_CHANGE_DENOMINATOR`
+pub const BASE_FEE_CHANGE_DENOMINATOR: u64 = 8;
+
+impl GasConfig {
+    /// Gas used by a block that leaves the base fee unchanged
+    pub fn target_gas_per_block(&self) -> u64 {
+        self.max_gas_per_block / 2
+    }
+
+    /// Base fee for the block after one with `parent_base_fee` that used
+    /// `parent_gas_used`: it rises when the parent was more than half full and
+    /// falls when it was less, never below `min_gas_price`.
+    pub fn next_base_fee(&self, parent_base_fee: u64, parent_gas_used: u64) -> u64 {
+        let target = self.target_gas_per_block().max(1) as u128;
+        let used = parent_gas_used.min(self.max_gas_per_block) as u128;
+        let base_fee = parent_base_fee as u128;
+        let denominator = BASE_FEE_CHANGE_DENOMINATOR as u128;
+
+        let next = if used > target {
+            let delta = (base_fee * (used - target) / target / denominator).max(1);
+            base_fee + delta
+        } else {
+            base_fee - base_fee * (target - used) / target / denominator
+        };
+        (next.min(u64::MAX as u128) as u64).max(self.min_gas_price)
+    }
+}
+
 /// Gas costs for different operations
 #[derive(Debug, Clone, Copy)]
 pub enum GasOperation {
@@ -49,35 +85,105 @@ pub enum GasOperation {
     CreateAccount,
     /// Update account state
     UpdateAccount,
+    /// Verify a transaction signature of the given size
+    SignatureVerification { signature_size: usize },
+    /// Write resource bytes to storage
+    StorageWrite { bytes: usize },
+}
+
+/// Signature size covered by the base cost of every operation; the largest
+/// classical (DER-encoded ECDSA) signature
+pub const BASE_SIGNATURE_SIZE: usize = 72;
+
+/// Gas units charged per operation. The schedule in force is part of the
+/// on-chain chain parameters; `Default` is the genesis schedule.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GasSchedule {
+    pub transfer: u64,
+    pub publish_base: u64,
+    pub publish_per_byte: u64,
+    pub execute_base: u64,
+    pub execute_per_complexity: u64,
+    pub contract_call_base: u64,
+    pub contract_call_per_name_byte: u64,
+    pub deployment_base: u64,
+    pub deployment_per_metadata_byte: u64,
+    pub contract_query: u64,
+    pub create_account: u64,
+    pub update_account: u64,
+    /// Per signature byte beyond `BASE_SIGNATURE_SIZE`
+    pub signature_per_byte: u64,
+    pub storage_per_byte: u64,
+    /// Per executed Move instruction
+    pub instruction: u64,
+    /// Per Move function call, on top of its instruction
+    pub call: u64,
+}
+
+impl Default for GasSchedule {
+    fn default() -> Self {
+        Self {
+            transfer: 21_000,
+            publish_base: 50_000,
+            publish_per_byte: 10,
+            execute_base: 30_000,
+            execute_per_complexity: 1_000,
+            contract_call_base: 35_000,
+            contract_call_per_name_byte: 100,
+            deployment_base: 60_000,
+            deployment_per_metadata_byte: 5,
+            contract_query: 1_000,
+            create_account: 25_000,
+            update_account: 5_000,
+            signature_per_byte: 10,
+            storage_per_byte: 10,
+            instruction: 10,
+            call: 100,
+        }
+    }
 }
 
 impl GasOperation {
-    /// Calculate gas units required for this operation
+    /// Calculate gas units required for this operation under the genesis schedule
     pub fn gas_units(&self) -> u64 {
+        self.gas_units_with(&GasSchedule::default())
+    }
+
+    /// Calculate gas units required for this operation under `schedule`
+    pub fn gas_units_with(&self, schedule: &GasSchedule) -> u64 {
         match self {
-            GasOperation::Transfer => 21_000,
+            GasOperation::Transfer => schedule.transfer,
             GasOperation::PublishModule { module_size } => {
                 // Base cost + per-byte cost
-                50_000 + (*module_size as u64 * 10)
+                schedule.publish_base + (*module_size as u64 * schedule.publish_per_byte)
             }
             GasOperation::ExecuteFunction { complexity } => {
                 // Base cost + complexity multiplier
-                30_000 + (*complexity as u64 * 1_000)
+                schedule.execute_base + (*complexity as u64 * schedule.execute_per_complexity)
             }
             GasOperation::ContractCall { function_name_len } => {
                 // Base cost for contract call + name length overhead
-                35_000 + (*function_name_len as u64 * 100)
+                schedule.contract_call_base
+                    + (*function_name_len as u64 * schedule.contract_call_per_name_byte)
             }
             GasOperation::ContractDeployment {
                 module_size,
                 metadata_size,
             } => {
                 // Higher cost for full contract deployment with registry
-                60_000 + (*module_size as u64 * 10) + (*metadata_size as u64 * 5)
+                schedule.deployment_base
+                    + (*module_size as u64 * schedule.publish_per_byte)
+                    + (*metadata_size as u64 * schedule.deployment_per_metadata_byte)
+            }
+            GasOperation::ContractQuery => schedule.contract_query,
+            GasOperation::CreateAccount => schedule.create_account,
+            GasOperation::UpdateAccount => schedule.update_account,
+            GasOperation::SignatureVerification { signature_size } => {
+                // Post-quantum signatures pay for the bytes beyond a classical one
+                signature_size.saturating_sub(BASE_SIGNATURE_SIZE) as u64
+                    * schedule.signature_per_byte
             }
-            GasOperation::ContractQuery => 1_000,
-            GasOperation::CreateAccount => 25_000,
-            GasOperation::UpdateAccount => 5_000,
+            GasOperation::StorageWrite { bytes } => *bytes as u64 * schedule.storage_per_byte,
         }
     }
 
@@ -92,6 +198,8 @@ impl GasOperation {
             GasOperation::ContractQuery => "ContractQuery",
             GasOperation::CreateAccount => "CreateAccount",
             GasOperation::UpdateAccount => "UpdateAccount",
+            GasOperation::SignatureVerification { .. } => "SignatureVerification",
+            GasOperation::StorageWrite { .. } => "StorageWrite",
         }
     }
 }
@@ -141,6 +249,16 @@ impl GasMeter {
         self.gas_used.saturating_mul(self.gas_price)
     }
 
+    /// Amount reserved from the sender before execution, `gas_limit * gas_price`
+    pub fn max_cost(&self) -> u64 {
+        self.gas_limit.saturating_mul(self.gas_price)
+    }
+
+    /// Part of the reserved amount that was not used and stays with the sender
+    pub fn refund_cost(&self) -> u64 {
+        self.max_cost().saturating_sub(self.total_cost())
+    }
+
     /// Calculate remaining gas
     pub fn remaining(&self) -> u64 {
         self.gas_limit.saturating_sub(self.gas_used)
@@ -160,6 +278,254 @@ impl GasMeter {
     }
 }
 
+/// Gas meter handed to the Move VM. Every instruction costs a flat amount,
+/// plus the abstract memory size of the values it copies, compares or writes.
+#[derive(Debug, Clone)]
+pub struct MoveGasMeter {
+    budget: u64,
+    used: u64,
+    instruction: u64,
+    call: u64,
+    storage_per_byte: u64,
+}
+
+impl MoveGasMeter {
+    /// Meter charging the genesis gas schedule
+    pub fn new(budget: u64) -> Self {
+        Self::with_schedule(budget, &GasSchedule::default())
+    }
+
+    /// Meter charging the Move costs of `schedule`
+    pub fn with_schedule(budget: u64, schedule: &GasSchedule) -> Self {
+        Self {
+            budget,
+            used: 0,
+            instruction: schedule.instruction,
+            call: schedule.call,
+            storage_per_byte: schedule.storage_per_byte,
+        }
+    }
+
+    /// Gas units consumed so far
+    pub fn gas_used(&self) -> u64 {
+        self.used
+    }
+
+    /// Consume gas units, failing with `OUT_OF_GAS` once the budget is exhausted
+    pub fn charge(&mut self, gas_units: u64) -> PartialVMResult<()> {
+        match self.used.checked_add(gas_units) {
+            Some(used) if used <= self.budget => {
+                self.used = used;
+                Ok(())
+            }
+            _ => {
+                self.used = self.budget;
+                Err(PartialVMError::new(StatusCode::OUT_OF_GAS))
+            }
+        }
+    }
+
+    /// Charge for `bytes` of resources written to storage
+    pub fn charge_storage_write(&mut self, bytes: usize) -> PartialVMResult<()> {
+        self.charge((bytes as u64).saturating_mul(self.storage_per_byte))
+    }
+
+    fn charge_value(&mut self, val: impl ValueView) -> PartialVMResult<()> {
+        let size: u64 = val.legacy_abstract_memory_size().into();
+        self.charge(self.instruction + size)
+    }
+
+    fn charge_values(
+        &mut self,
+        vals: impl ExactSizeIterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        let size: u64 = vals
+            .map(|val| u64::from(val.legacy_abstract_memory_size()))
+            .sum();
+        self.charge(self.instruction + size)
+    }
+}
+
+impl MoveVMGasMeter for MoveGasMeter {
+    fn charge_simple_instr(&mut self, _instr: SimpleInstruction) -> PartialVMResult<()> {
+        self.charge(self.instruction)
+    }
+
+    fn charge_pop(&mut self, _popped_val: impl ValueView) -> PartialVMResult<()> {
+        self.charge(self.instruction)
+    }
+
+    fn charge_call(
+        &mut self,
+        _module_id: &ModuleId,
+        _func_name: &str,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+        num_locals: NumArgs,
+    ) -> PartialVMResult<()> {
+        self.charge(self.call + u64::from(num_locals))?;
+        self.charge_values(args)
+    }
+
+    fn charge_call_generic(
+        &mut self,
+        _module_id: &ModuleId,
+        _func_name: &str,
+        ty_args: impl ExactSizeIterator<Item = impl TypeView>,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+        num_locals: NumArgs,
+    ) -> PartialVMResult<()> {
+        self.charge(self.call + ty_args.len() as u64 * self.instruction + u64::from(num_locals))?;
+        self.charge_values(args)
+    }
+
+    fn charge_ld_const(&mut self, size: NumBytes) -> PartialVMResult<()> {
+        self.charge(self.instruction + u64::from(size))
+    }
+
+    fn charge_ld_const_after_deserialization(
+        &mut self,
+        _val: impl ValueView,
+    ) -> PartialVMResult<()> {
+        Ok(())
+    }
+
+    fn charge_copy_loc(&mut self, val: impl ValueView) -> PartialVMResult<()> {
+        self.charge_value(val)
+    }
+
+    fn charge_move_loc(&mut self, _val: impl ValueView) -> PartialVMResult<()> {
+        self.charge(self.instruction)
+    }
+
+    fn charge_store_loc(&mut self, _val: impl ValueView) -> PartialVMResult<()> {
+        self.charge(self.instruction)
+    }
+
+    fn charge_pack(
+        &mut self,
+        _is_generic: bool,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        self.charge(self.instruction * (1 + args.len() as u64))
+    }
+
+    fn charge_unpack(
+        &mut self,
+        _is_generic: bool,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        self.charge(self.instruction * (1 + args.len() as u64))
+    }
+
+    fn charge_read_ref(&mut self, val: impl ValueView) -> PartialVMResult<()> {
+        self.charge_value(val)
+    }
+
+    fn charge_write_ref(
+        &mut self,
+        new_val: impl ValueView,
+        _old_val: impl ValueView,
+    ) -> PartialVMResult<()> {
+        self.charge_value(new_val)
+    }
+
+    fn charge_eq(&mut self, lhs: impl ValueView, rhs: impl ValueView) -> PartialVMResult<()> {
+        let size = u64::from(lhs.legacy_abstract_memory_size())
+            + u64::from(rhs.legacy_abstract_memory_size());
+        self.charge(self.instruction + size)
+    }
+
+    fn charge_neq(&mut self, lhs: impl ValueView, rhs: impl ValueView) -> PartialVMResult<()> {
+        let size = u64::from(lhs.legacy_abstract_memory_size())
+            + u64::from(rhs.legacy_abstract_memory_size());
+        self.charge(self.instruction + size)
+    }
+
+    fn charge_vec_pack<'a>(
+        &mut self,
+        _ty: impl TypeView + 'a,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        self.charge(self.instruction * (1 + args.len() as u64))
+    }
+
+    fn charge_vec_len(&mut self, _ty: impl TypeView) -> PartialVMResult<()> {
+        self.charge(self.instruction)
+    }
+
+    fn charge_vec_borrow(
+        &mut self,
+        _is_mut: bool,
+        _ty: impl TypeView,
+        _is_success: bool,
+    ) -> PartialVMResult<()> {
+        self.charge(self.instruction)
+    }
+
+    fn charge_vec_push_back(
+        &mut self,
+        _ty: impl TypeView,
+        val: impl ValueView,
+    ) -> PartialVMResult<()> {
+        self.charge_value(val)
+    }
+
+    fn charge_vec_pop_back(
+        &mut self,
+        _ty: impl TypeView,
+        _val: Option<impl ValueView>,
+    ) -> PartialVMResult<()> {
+        self.charge(self.instruction)
+    }
+
+    fn charge_vec_unpack(
+        &mut self,
+        _ty: impl TypeView,
+        expect_num_elements: NumArgs,
+        _elems: impl ExactSizeIterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        self.charge(self.instruction * (1 + u64::from(expect_num_elements)))
+    }
+
+    fn charge_vec_swap(&mut self, _ty: impl TypeView) -> PartialVMResult<()> {
+        self.charge(self.instruction)
+    }
+
+    fn charge_native_function(
+        &mut self,
+        amount: InternalGas,
+        _ret_vals: Option<impl ExactSizeIterator<Item = impl ValueView>>,
+    ) -> PartialVMResult<()> {
+        self.charge(u64::from(amount))
+    }
+
+    fn charge_native_function_before_execution(
+        &mut self,
+        _ty_args: impl ExactSizeIterator<Item = impl TypeView>,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        self.charge(self.call)?;
+        self.charge_values(args)
+    }
+
+    fn charge_drop_frame(
+        &mut self,
+        _locals: impl Iterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        Ok(())
+    }
+
+    fn remaining_gas(&self) -> InternalGas {
+        InternalGas::new(self.budget.saturating_sub(self.used))
+    }
+
+    fn get_profiler_mut(&mut self) -> Option<&mut GasProfiler> {
+        None
+    }
+
+    fn set_profiler(&mut self, _profiler: GasProfiler) {}
+}
+
 /// Gas estimation result
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GasEstimate {
@@ -301,6 +667,13 @@ mod tests {
             metadata_size: 200,
         };
         assert_eq!(deployment.gas_units(), 71_000); // 60_000 + 1000*10 + 200*5
+
+        let classical = GasOperation::SignatureVerification { signature_size: 64 };
+        assert_eq!(classical.gas_units(), 0);
+        let dilithium3 = GasOperation::SignatureVerification {
+            signature_size: 3309,
+        };
+        assert_eq!(dilithium3.gas_units(), 32_370); // (3309 - 72) * 10
     }
 
     #[test]
@@ -337,4 +710,37 @@ mod tests {
         assert_eq!(tx_gas.refund_amount(), 5_000_000);
         assert_eq!(tx_gas.net_cost(), 16_000_000);
     }
+
+    #[test]
+    fn test_next_base_fee() {
+        let config = GasConfig::default();
+        let target = config.target_gas_per_block();
+
+        assert_eq!(config.next_base_fee(1000, target), 1000);
+        assert_eq!(config.next_base_fee(1000, config.max_gas_per_block), 1125);
+        assert_eq!(config.next_base_fee(1000, 0), 875);
+        // Overfull blocks move the fee no faster than full ones
+        assert_eq!(config.next_base_fee(1000, u64::MAX), 1125);
+        assert_eq!(config.next_base_fee(config.min_gas_price, 0), 100);
+    }
+
+    #[test]
+    fn test_gas_meter_refund() {
+        let mut meter = GasMeter::new(100_000, 1000);
+        meter.consume(21_000).unwrap();
+
+        assert_eq!(meter.max_cost(), 100_000_000);
+        assert_eq!(meter.refund_cost(), 79_000_000);
+    }
+
+    #[test]
+    fn test_move_gas_meter_budget() {
+        let mut meter = MoveGasMeter::new(100);
+        meter.charge(60).unwrap();
+        assert_eq!(meter.gas_used(), 60);
+
+        let err = meter.charge(50).unwrap_err();
+        assert_eq!(err.major_status(), StatusCode::OUT_OF_GAS);
+        assert_eq!(meter.gas_used(), 100);
+    }
 }
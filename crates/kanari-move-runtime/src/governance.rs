@@ -0,0 +1,338 @@
+use crate::chain_params::ChainParameters;
+use crate::changeset::ChangeSet;
+use crate::move_runtime::MoveRuntime;
+use crate::move_storage::MoveStorage;
+use anyhow::{Context, Result};
+use kanari_types::address::Address as KanariAddress;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::StructTag;
+use serde::{Deserialize, Serialize};
+
+/// Blocks a proposal stays open for voting
+pub const VOTING_PERIOD_BLOCKS: u64 = 100;
+
+/// Voting weight needed to submit a proposal, in Mist (1 KANARI)
+pub const PROPOSAL_THRESHOLD: u64 = 1_000_000_000;
+
+/// Share of the total supply that must vote, in basis points
+pub const QUORUM_BPS: u64 = 1_000;
+
+/// What a proposal does once it passes
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProposalAction {
+    /// Pay `amount` from the DAO treasury to `recipient`
+    TreasurySpend { recipient: String, amount: u64 },
+    /// Stage chain parameters, taking effect at the next epoch
+    ChangeParameters(ChainParameters),
+    /// Republish framework modules at `0x2`
+    UpgradeFramework { modules: Vec<Vec<u8>> },
+}
+
+impl ProposalAction {
+    /// Reject actions that could never be executed
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            ProposalAction::TreasurySpend { recipient, amount } => {
+                AccountAddress::from_hex_literal(recipient)
+                    .with_context(|| format!("Invalid recipient address: {}", recipient))?;
+                if *amount == 0 {
+                    anyhow::bail!("Treasury spend amount must be positive");
+                }
+            }
+            ProposalAction::ChangeParameters(parameters) => parameters.validate()?,
+            ProposalAction::UpgradeFramework { modules } => {
+                if modules.is_empty() {
+                    anyhow::bail!("Framework upgrade must contain at least one module");
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Governance operation carried by `Transaction::Governance`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GovernanceAction {
+    /// Open a proposal for voting
+    Propose(ProposalAction),
+    /// Vote on an active proposal with the sender's balance plus stake, which
+    /// stays locked until the proposal closes
+    Vote { proposal_id: u64, approve: bool },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProposalStatus {
+    Active,
+    Rejected,
+    Executed,
+    /// Passed, but the action could not be carried out
+    Failed,
+}
+
+/// BCS layout matches `0x2::governance::Proposal`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Proposal {
+    pub id: u64,
+    pub proposer: AccountAddress,
+    /// BCS-encoded `ProposalAction`
+    pub action: Vec<u8>,
+    /// Last block at which votes are accepted
+    pub voting_end_height: u64,
+    pub yes_votes: u64,
+    pub no_votes: u64,
+    pub voters: Vec<AccountAddress>,
+    pub status: ProposalStatus,
+}
+
+impl Proposal {
+    pub fn action(&self) -> Result<ProposalAction> {
+        bcs::from_bytes(&self.action).context("Failed to decode proposal action")
+    }
+}
+
+/// Voting weight an account has committed to proposals that are still open.
+/// BCS layout matches `0x2::governance::VoteLock`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VoteLock {
+    pub voter: AccountAddress,
+    pub amount: u64,
+    /// Last block of the latest voting period the lock covers
+    pub until_height: u64,
+}
+
+/// The `0x2::governance::GovernanceStore` value kept in Move storage
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GovernanceStore {
+    pub proposals: Vec<Proposal>,
+    pub next_id: u64,
+    pub locks: Vec<VoteLock>,
+}
+
+impl GovernanceStore {
+    pub const MODULE_NAME: &'static str = "governance";
+    pub const STRUCT_NAME: &'static str = "GovernanceStore";
+
+    /// Account holding the store, `0x2`
+    pub fn address() -> AccountAddress {
+        AccountAddress::from_hex_literal(KanariAddress::KANARI_SYSTEM_ADDRESS).unwrap()
+    }
+
+    pub fn struct_tag() -> StructTag {
+        StructTag {
+            address: Self::address(),
+            module: Identifier::new(Self::MODULE_NAME).unwrap(),
+            name: Identifier::new(Self::STRUCT_NAME).unwrap(),
+            type_params: vec![],
+        }
+    }
+
+    /// Read the store from Move storage
+    pub fn load(storage: &MoveStorage) -> Result<Self> {
+        let bytes = storage
+            .resource(&Self::address(), &Self::struct_tag())
+            .context("Governance store is missing from Move storage")?;
+        bcs::from_bytes(bytes).context("Failed to decode governance store")
+    }
+
+    /// Write the store into the runtime's Move storage
+    pub fn save(&self, runtime: &mut MoveRuntime) -> Result<()> {
+        runtime.set_resource(Self::address(), Self::struct_tag(), bcs::to_bytes(self)?);
+        Ok(())
+    }
+
+    /// Emit the store as a resource write of `changeset`
+    pub fn write_to(&self, changeset: &mut ChangeSet) -> Result<()> {
+        changeset.write_resource(Self::address(), Self::struct_tag(), bcs::to_bytes(self)?);
+        Ok(())
+    }
+
+    pub fn get(&self, id: u64) -> Option<&Proposal> {
+        self.proposals.iter().find(|p| p.id == id)
+    }
+
+    /// Open a proposal at `height`, returning its id
+    pub fn propose(
+        &mut self,
+        proposer: AccountAddress,
+        action: &ProposalAction,
+        height: u64,
+    ) -> Result<u64> {
+        action.validate()?;
+        let id = self.next_id;
+        self.proposals.push(Proposal {
+            id,
+            proposer,
+            action: bcs::to_bytes(action)?,
+            voting_end_height: height + VOTING_PERIOD_BLOCKS,
+            yes_votes: 0,
+            no_votes: 0,
+            voters: Vec::new(),
+            status: ProposalStatus::Active,
+        });
+        self.next_id += 1;
+        Ok(id)
+    }
+
+    /// Record a vote of `weight` at `height`. The weight stays locked until
+    /// the proposal closes, so it can't be moved to another voter.
+    pub fn vote(
+        &mut self,
+        voter: AccountAddress,
+        id: u64,
+        approve: bool,
+        weight: u64,
+        height: u64,
+    ) -> Result<()> {
+        let proposal = self
+            .proposals
+            .iter_mut()
+            .find(|p| p.id == id)
+            .ok_or_else(|| anyhow::anyhow!("Proposal {} not found", id))?;
+        if proposal.status != ProposalStatus::Active || height > proposal.voting_end_height {
+            anyhow::bail!("Voting on proposal {} has closed", id);
+        }
+        if proposal.voters.contains(&voter) {
+            anyhow::bail!("{} already voted on proposal {}", voter, id);
+        }
+        if weight == 0 {
+            anyhow::bail!("{} has no voting weight", voter);
+        }
+        if approve {
+            proposal.yes_votes = proposal.yes_votes.saturating_add(weight);
+        } else {
+            proposal.no_votes = proposal.no_votes.saturating_add(weight);
+        }
+        proposal.voters.push(voter);
+        let until_height = proposal.voting_end_height;
+
+        match self.locks.iter_mut().find(|lock| lock.voter == voter) {
+            Some(lock) => {
+                lock.amount = lock.amount.max(weight);
+                lock.until_height = lock.until_height.max(until_height);
+            }
+            None => self.locks.push(VoteLock {
+                voter,
+                amount: weight,
+                until_height,
+            }),
+        }
+        Ok(())
+    }
+
+    /// Voting weight of `voter` locked at `height`
+    pub fn locked(&self, voter: &AccountAddress, height: u64) -> u64 {
+        self.locks
+            .iter()
+            .find(|lock| lock.voter == *voter && lock.until_height >= height)
+            .map(|lock| lock.amount)
+            .unwrap_or(0)
+    }
+
+    /// Close the proposals whose voting period ended before `height`. A proposal
+    /// passes with more yes than no votes and a turnout of at least `QUORUM_BPS`
+    /// of `total_supply`. Passed proposals are marked executed and returned in id
+    /// order for the caller to carry out.
+    pub fn tally(&mut self, height: u64, total_supply: u64) -> Vec<(u64, ProposalAction)> {
+        let quorum = (total_supply as u128 * QUORUM_BPS as u128 / 10_000) as u64;
+        self.locks.retain(|lock| lock.until_height >= height);
+        let mut passed = Vec::new();
+        for proposal in &mut self.proposals {
+            if proposal.status != ProposalStatus::Active || proposal.voting_end_height >= height {
+                continue;
+            }
+            let turnout = proposal.yes_votes.saturating_add(proposal.no_votes);
+            let action = proposal.action();
+            match action {
+                Ok(action) if proposal.yes_votes > proposal.no_votes && turnout >= quorum => {
+                    proposal.status = ProposalStatus::Executed;
+                    passed.push((proposal.id, action));
+                }
+                _ => proposal.status = ProposalStatus::Rejected,
+            }
+        }
+        passed
+    }
+
+    /// Record that a passed proposal could not be executed
+    pub fn mark_failed(&mut self, id: u64) {
+        if let Some(proposal) = self.proposals.iter_mut().find(|p| p.id == id) {
+            proposal.status = ProposalStatus::Failed;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn addr(n: u8) -> AccountAddress {
+        AccountAddress::new([n; AccountAddress::LENGTH])
+    }
+
+    fn spend() -> ProposalAction {
+        ProposalAction::TreasurySpend {
+            recipient: "0x42".to_string(),
+            amount: 10,
+        }
+    }
+
+    #[test]
+    fn test_voting_and_tally() {
+        let mut store = GovernanceStore::default();
+        let passing = store.propose(addr(1), &spend(), 10).unwrap();
+        let losing = store.propose(addr(1), &spend(), 10).unwrap();
+        let short_of_quorum = store.propose(addr(1), &spend(), 10).unwrap();
+
+        store.vote(addr(1), passing, true, 600, 10).unwrap();
+        store.vote(addr(2), passing, false, 400, 11).unwrap();
+        assert!(store.vote(addr(1), passing, true, 600, 12).is_err());
+        store.vote(addr(2), losing, false, 1_000, 12).unwrap();
+        store.vote(addr(1), short_of_quorum, true, 99, 12).unwrap();
+
+        let end = 10 + VOTING_PERIOD_BLOCKS;
+        assert!(store.vote(addr(3), passing, true, 1, end + 1).is_err());
+        assert!(store.tally(end, 1_000).is_empty());
+
+        let passed = store.tally(end + 1, 1_000);
+        assert_eq!(passed, vec![(passing, spend())]);
+        assert_eq!(store.get(passing).unwrap().status, ProposalStatus::Executed);
+        assert_eq!(store.get(losing).unwrap().status, ProposalStatus::Rejected);
+        assert_eq!(
+            store.get(short_of_quorum).unwrap().status,
+            ProposalStatus::Rejected
+        );
+    }
+
+    #[test]
+    fn test_votes_lock_weight_until_close() {
+        let mut store = GovernanceStore::default();
+        let first = store.propose(addr(1), &spend(), 10).unwrap();
+        let second = store.propose(addr(1), &spend(), 50).unwrap();
+
+        store.vote(addr(2), first, true, 300, 10).unwrap();
+        store.vote(addr(2), second, true, 200, 50).unwrap();
+        assert_eq!(store.locked(&addr(2), 50), 300);
+        assert_eq!(store.locked(&addr(3), 50), 0);
+
+        // The lock lasts until the later of the two proposals closes
+        let end = 50 + VOTING_PERIOD_BLOCKS;
+        assert_eq!(store.locked(&addr(2), end), 300);
+        assert_eq!(store.locked(&addr(2), end + 1), 0);
+        store.tally(end + 1, 1_000);
+        assert!(store.locks.is_empty());
+    }
+
+    #[test]
+    fn test_invalid_proposals_rejected() {
+        let mut store = GovernanceStore::default();
+        let zero = ProposalAction::TreasurySpend {
+            recipient: "0x42".to_string(),
+            amount: 0,
+        };
+        assert!(store.propose(addr(1), &zero, 1).is_err());
+        let empty = ProposalAction::UpgradeFramework { modules: vec![] };
+        assert!(store.propose(addr(1), &empty, 1).is_err());
+        assert!(store.proposals.is_empty());
+    }
+}
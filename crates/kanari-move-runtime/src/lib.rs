@@ -1,22 +1,59 @@
 pub mod blockchain;
+pub mod chain_params;
 pub mod changeset;
+pub mod coin;
+pub mod consensus;
 pub mod contract;
 pub mod engine;
+pub mod fork_choice;
 pub mod gas;
+pub mod governance;
 pub mod move_runtime;
+pub mod move_storage;
 pub mod move_vm_state;
+pub mod natives;
+pub mod nft;
+pub mod object;
+pub mod pruning;
+pub mod random;
+pub mod snapshot;
+pub mod staking;
 pub mod state;
+pub mod view;
 
-pub use blockchain::{Block, BlockHeader, Blockchain, SignedTransaction, Transaction};
+pub use blockchain::{
+    Block, BlockHeader, Blockchain, SignedTransaction, Transaction, TransactionAuthenticator,
+};
+pub use chain_params::{ChainParameters, ChainParamsStore, DEFAULT_EPOCH_LENGTH};
 pub use changeset::Event;
-pub use changeset::{AccountChange, ChangeSet};
+pub use changeset::{AccountChange, ChangeSet, ResourceWrite};
+pub use coin::{CoinMetadata, KANARI_DECIMALS, kanari_coin_type};
+pub use consensus::{BlockSigner, Validator, ValidatorSet};
 pub use contract::{
     ContractABI, ContractCall, ContractDeployment, ContractInfo, ContractMetadata,
     ContractRegistry, FieldInfo, FunctionSignature, ParameterInfo, StructSignature,
 };
-pub use engine::{AccountInfo, BlockData, BlockInfo, BlockchainEngine, BlockchainStats};
-pub use gas::{GasConfig, GasError, GasEstimate, GasMeter, GasOperation, TransactionGas};
+pub use engine::{
+    AccountInfo, BlockData, BlockImport, BlockInfo, BlockchainEngine, BlockchainStats, ChainEvent,
+    CoinBalance, NftInfo, ObjectInfo, TransactionReceipt,
+};
+pub use fork_choice::{BlockTree, DEFAULT_FINALITY_DEPTH, MAX_ORPHAN_BLOCKS};
+pub use gas::{
+    BASE_FEE_CHANGE_DENOMINATOR, BASE_SIGNATURE_SIZE, GasConfig, GasError, GasEstimate, GasMeter,
+    GasOperation, GasSchedule, MoveGasMeter, TransactionGas,
+};
+pub use governance::{
+    GovernanceAction, GovernanceStore, Proposal, ProposalAction, ProposalStatus, VoteLock,
+};
 pub use kanari_crypto::keys::CurveType;
 pub use move_runtime::MoveRuntime;
+pub use move_storage::MoveStorage;
 pub use move_vm_state::MoveVMState;
+pub use nft::{Collection, Nft};
+pub use object::{CallInputs, Object, Ownership, TxContext};
+pub use pruning::{DEFAULT_STATE_HISTORY, PruneStats, RetentionMode};
+pub use random::{DeferredCalls, RandomBeacon};
+pub use snapshot::{SNAPSHOT_VERSION, StateSnapshot, compute_state_root};
+pub use staking::{StakingAction, StakingPool};
 pub use state::{Account, StateManager};
+pub use view::{MoveResource, ViewResult, ViewValue, move_value_to_json};
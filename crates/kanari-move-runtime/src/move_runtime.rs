@@ -1,26 +1,87 @@
 // This file contains the MoveRuntime wrapper implementation.
-// It utilizes MoveVM and InMemoryStorage for executing functions and publishing modules.
+// It utilizes MoveVM and MoveStorage for executing functions and publishing modules.
 
 use anyhow::Result;
 use move_binary_format::file_format::CompiledModule;
 use move_core_types::account_address::AccountAddress;
+use move_core_types::annotated_value::MoveValue as AnnotatedMoveValue;
 use move_core_types::effects::Op as MoveOp;
 use move_core_types::identifier::IdentStr;
-use move_core_types::language_storage::{ModuleId, TypeTag};
+use move_core_types::language_storage::{ModuleId, StructTag, TypeTag};
+use move_core_types::resolver::ModuleResolver;
+use move_core_types::resolver::MoveResolver;
+use move_core_types::vm_status::StatusCode;
 use move_vm_runtime::move_vm::MoveVM;
-use move_vm_test_utils::InMemoryStorage;
+use move_vm_runtime::session::Session;
 use move_vm_types::gas::UnmeteredGasMeter;
+use move_vm_types::loaded_data::runtime_types::Type;
+use std::collections::{BTreeMap, BTreeSet};
 
-use crate::gas::{GasMeter, GasOperation};
+use crate::gas::{GasMeter, GasOperation, MoveGasMeter};
 use kanari_types::address::Address as KanariAddress;
 
 use crate::changeset::ChangeSet;
+use crate::coin::Coin;
+use crate::contract::ContractABI;
+use crate::move_storage::MoveStorage;
 use crate::move_vm_state::MoveVMState;
+use crate::natives::{EventRuntime, FieldRuntime, ObjectRuntime, all_natives, new_extensions};
+use crate::object::{CallInputs, Ownership, TxContext};
+use crate::view::{MoveResource, ViewValue, move_value_to_json};
+
+/// Bytes of resources created or modified by a Move changeset; modules are
+/// priced separately by `GasOperation::PublishModule`
+fn resource_bytes_written(move_cs: &move_core_types::effects::ChangeSet) -> usize {
+    move_cs
+        .accounts()
+        .values()
+        .flat_map(|account| account.resources().values())
+        .map(|op| match op {
+            MoveOp::New(bytes) | MoveOp::Modify(bytes) => bytes.len(),
+            MoveOp::Delete => 0,
+        })
+        .sum()
+}
+
+/// Charge storage gas for the resources written by a Move changeset
+fn charge_storage(
+    gas: &mut MoveGasMeter,
+    move_cs: &move_core_types::effects::ChangeSet,
+) -> Result<()> {
+    gas.charge_storage_write(resource_bytes_written(move_cs))
+        .map_err(|e| anyhow::anyhow!(format!("storage error: {:?}", e)))
+}
+
+/// Create a VM with every native the framework and standard library declare
+fn new_vm() -> Result<MoveVM> {
+    MoveVM::new(all_natives()).map_err(|e| anyhow::anyhow!(format!("VM init error: {:?}", e)))
+}
+
+/// How an entry function takes one of its object inputs
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ObjectParam {
+    ByValue,
+    Ref,
+    MutRef,
+}
+
+fn decode_json<S: MoveResolver>(
+    session: &Session<'_, '_, S>,
+    type_tag: &TypeTag,
+    bytes: &[u8],
+) -> serde_json::Value {
+    session
+        .get_fully_annotated_type_layout(type_tag)
+        .ok()
+        .and_then(|layout| AnnotatedMoveValue::simple_deserialize(bytes, &layout).ok())
+        .map(|value| move_value_to_json(&value))
+        .unwrap_or(serde_json::Value::Null)
+}
 
 /// Simple runtime wrapper around `move-vm` for executing functions and publishing modules.
 pub struct MoveRuntime {
     vm: MoveVM,
-    storage: InMemoryStorage,
+    storage: MoveStorage,
     state: MoveVMState,
 }
 
@@ -28,14 +89,57 @@ impl MoveRuntime {
     /// Open the runtime using the default persistent DB path (see README).
     pub fn new() -> Result<Self> {
         let state = MoveVMState::open_default()?;
-        let mut storage = InMemoryStorage::new();
+        let mut storage = MoveStorage::new();
         state.load_into_storage(&mut storage)?;
-        // For simplicity we initialise the VM with no custom natives.
-        let vm =
-            MoveVM::new(vec![]).map_err(|e| anyhow::anyhow!(format!("VM init error: {:?}", e)))?;
+        let vm = new_vm()?;
         Ok(MoveRuntime { vm, storage, state })
     }
 
+    /// Read-only access to the current Move storage (modules and resources).
+    pub fn storage(&self) -> &MoveStorage {
+        &self.storage
+    }
+
+    /// Whether `module` uses `dependency`, directly or through the modules it
+    /// depends on
+    pub fn depends_on(&self, module: &ModuleId, dependency: &ModuleId) -> bool {
+        let mut seen = BTreeSet::new();
+        let mut stack = vec![module.clone()];
+        while let Some(id) = stack.pop() {
+            if &id == dependency {
+                return true;
+            }
+            if !seen.insert(id.clone()) {
+                continue;
+            }
+            let Ok(Some(bytes)) = self.storage.get_module(&id) else {
+                continue;
+            };
+            if let Ok(compiled) = CompiledModule::deserialize_with_defaults(&bytes) {
+                stack.extend(compiled.immediate_dependencies());
+            }
+        }
+        false
+    }
+
+    /// Replace the whole Move storage, e.g. when bootstrapping from a snapshot.
+    /// Modules are persisted to the DB and the VM is recreated so that its loader
+    /// cache does not serve modules from the previous storage.
+    pub fn restore_storage(&mut self, storage: MoveStorage) -> Result<()> {
+        for (module_id, blob) in storage.modules() {
+            self.state.save_module(module_id, blob)?;
+        }
+        self.vm = new_vm()?;
+        self.storage = storage;
+        Ok(())
+    }
+
+    /// Write a resource directly, for state owned by the node rather than by
+    /// Move code (e.g. chain parameters)
+    pub fn set_resource(&mut self, address: AccountAddress, struct_tag: StructTag, blob: Vec<u8>) {
+        self.storage.set_resource(address, struct_tag, blob);
+    }
+
     /// Publish a module (bytes) with the given sender address.
     /// Returns ChangeSet containing the module addition and any resource changes from Move VM.
     pub fn publish_module(
@@ -45,18 +149,53 @@ impl MoveRuntime {
         // Optional gas tuple: (gas_limit, gas_price). If `Some`, runtime will
         // include gas accounting (debit sender, credit DAO) in the returned ChangeSet.
         gas_info: Option<(u64, u64)>,
+    ) -> Result<ChangeSet> {
+        let budget = gas_info.map_or(u64::MAX, |(gas_limit, _)| gas_limit);
+        let mut gas = MoveGasMeter::new(budget);
+        let mut cs = self.publish_module_metered(module_bytes.clone(), sender, &mut gas)?;
+
+        // If caller provided gas info, include gas accounting in the ChangeSet.
+        if let Some((gas_limit, gas_price)) = gas_info {
+            let mut meter = GasMeter::new(gas_limit, gas_price);
+            let gas_op = GasOperation::PublishModule {
+                module_size: module_bytes.len(),
+            };
+            meter.consume(gas_op.gas_units())?;
+            meter.consume(gas.gas_used())?;
+            let gas_cost = meter.total_cost();
+
+            let sender_change = cs.get_or_create_change(sender);
+            sender_change.increment_sequence();
+            sender_change.debit(gas_cost);
+
+            let dao_addr = AccountAddress::from_hex_literal(KanariAddress::DAO_ADDRESS)?;
+            cs.collect_gas(dao_addr, gas_cost);
+            cs.set_gas_used(meter.gas_used);
+        }
+
+        Ok(cs)
+    }
+
+    /// Publish a module, charging execution and storage to `gas`. The returned
+    /// ChangeSet reports the Move gas consumed in `gas_used`; nothing is
+    /// committed if the budget runs out.
+    pub fn publish_module_metered(
+        &mut self,
+        module_bytes: Vec<u8>,
+        sender: AccountAddress,
+        gas: &mut MoveGasMeter,
     ) -> Result<ChangeSet> {
         let storage_clone = self.storage.clone();
         let mut session = self.vm.new_session(storage_clone);
-        let mut gas = UnmeteredGasMeter;
 
         session
-            .publish_module(module_bytes.clone(), sender, &mut gas)
+            .publish_module(module_bytes.clone(), sender, gas)
             .map_err(|e| anyhow::anyhow!(format!("publish error: {:?}", e)))?;
 
         let (res, new_storage) = session.finish();
         let (move_changeset, events) =
             res.map_err(|e| anyhow::anyhow!(format!("finish error: {:?}", e)))?;
+        charge_storage(gas, &move_changeset)?;
 
         let mut storage = new_storage;
         storage
@@ -73,27 +212,9 @@ impl MoveRuntime {
         self.state.save_module(&module_id, &module_bytes)?;
 
         // Create ChangeSet from Move VM changeset
-        let mut cs = ChangeSet::new();
+        let mut cs = ChangeSet::with_gas(gas.gas_used());
         cs.publish_module(sender, module_id.name().to_string());
 
-        // If caller provided gas info, include gas accounting in the ChangeSet.
-        if let Some((gas_limit, gas_price)) = gas_info {
-            let mut meter = GasMeter::new(gas_limit, gas_price);
-            let gas_op = GasOperation::PublishModule {
-                module_size: module_bytes.len(),
-            };
-            meter.consume(gas_op.gas_units())?;
-            let gas_cost = meter.total_cost();
-
-            let sender_change = cs.get_or_create_change(sender);
-            sender_change.increment_sequence();
-            sender_change.debit(gas_cost);
-
-            let dao_addr = AccountAddress::from_hex_literal(KanariAddress::DAO_ADDRESS)?;
-            cs.collect_gas(dao_addr, gas_cost);
-            cs.set_gas_used(meter.gas_used);
-        }
-
         // Parse Move VM changeset and events
         self.parse_move_changeset(&move_changeset, &mut cs);
         self.parse_move_events(&events, &mut cs);
@@ -138,6 +259,23 @@ impl MoveRuntime {
         Ok(())
     }
 
+    /// Republish framework modules at `0x2`, e.g. for an approved governance
+    /// upgrade. The modules must be compatible with the ones they replace. The VM
+    /// is recreated so that its loader cache does not serve the old code.
+    pub fn upgrade_framework(&mut self, modules: Vec<Vec<u8>>) -> Result<()> {
+        let framework = AccountAddress::from_hex_literal(KanariAddress::KANARI_SYSTEM_ADDRESS)?;
+        for module_bytes in &modules {
+            let compiled = CompiledModule::deserialize_with_defaults(module_bytes)
+                .map_err(|e| anyhow::anyhow!(format!("deserialize error: {:?}", e)))?;
+            if *compiled.self_id().address() != framework {
+                anyhow::bail!("Module {} is not a framework module", compiled.self_id());
+            }
+        }
+        self.publish_module_bundle(modules, framework)?;
+        self.vm = new_vm()?;
+        Ok(())
+    }
+
     /// Attempt to publish modules in an order that satisfies dependencies by retrying
     /// individual publishes. Each module is published with its declared `self_id().address()` as sender.
     pub fn publish_modules_ordered(&mut self, modules: Vec<Vec<u8>>) -> Result<()> {
@@ -200,10 +338,73 @@ impl MoveRuntime {
         // Optional gas tuple: (gas_limit, gas_price). If provided, runtime will
         // include gas accounting (debit sender if available, credit DAO) in the returned ChangeSet.
         gas_info: Option<(u64, u64)>,
+    ) -> Result<ChangeSet> {
+        let budget = gas_info.map_or(u64::MAX, |(gas_limit, _)| gas_limit);
+        let mut gas = MoveGasMeter::new(budget);
+        let tx_context = TxContext::new(sender.unwrap_or(AccountAddress::ZERO), vec![], 0);
+        let mut cs = self.execute_entry_function_metered(
+            module_id,
+            function_name,
+            type_args,
+            args,
+            &CallInputs::new(tx_context),
+            &mut gas,
+        )?;
+
+        // If gas accounting requested, include gas debit/credit in ChangeSet.
+        if let Some((gas_limit, gas_price)) = gas_info {
+            let mut meter = GasMeter::new(gas_limit, gas_price);
+            let gas_op = GasOperation::ExecuteFunction { complexity: 0 };
+            meter.consume(gas_op.gas_units())?;
+            meter.consume(gas.gas_used())?;
+            let gas_cost = meter.total_cost();
+
+            // If sender provided, debit them and increment sequence to prevent replay.
+            if let Some(saddr) = sender {
+                let sender_change = cs.get_or_create_change(saddr);
+                sender_change.increment_sequence();
+                sender_change.debit(gas_cost);
+            }
+
+            let dao_addr = AccountAddress::from_hex_literal(KanariAddress::DAO_ADDRESS)?;
+            cs.collect_gas(dao_addr, gas_cost);
+            cs.set_gas_used(meter.gas_used);
+        }
+
+        Ok(cs)
+    }
+
+    /// Execute an entry function, charging execution and storage to `gas`. The
+    /// returned ChangeSet reports the Move gas consumed in `gas_used`; nothing is
+    /// committed if the budget runs out.
+    ///
+    /// The objects in `inputs` are passed as the leading parameters, followed by
+    /// `args`, and the `TxContext` when the last parameter is `&mut TxContext`.
+    /// Objects the function transfers, freezes or shares are written to storage
+    /// under their ids; objects it takes by value and does not hand on are deleted.
+    /// `Coin<T>`s it sends to an address are credited to that address's balance
+    /// of `T` instead.
+    /// Dynamic fields it adds, changes or removes are written as separate
+    /// storage entries and charged as storage like resources, as are the events
+    /// it emits.
+    pub fn execute_entry_function_metered(
+        &mut self,
+        module_id: &ModuleId,
+        function_name: &str,
+        type_args: Vec<TypeTag>,
+        args: Vec<Vec<u8>>,
+        inputs: &CallInputs,
+        gas: &mut MoveGasMeter,
     ) -> Result<ChangeSet> {
         let storage_clone = self.storage.clone();
-        let mut session = self.vm.new_session(storage_clone);
-        let mut gas = UnmeteredGasMeter;
+        let mut session = self.vm.new_session_with_extensions(
+            storage_clone,
+            new_extensions(
+                &self.storage,
+                inputs.tx_context.tx_hash.clone(),
+                inputs.random_seed.clone(),
+            ),
+        );
 
         // convert type tags to VM runtime types
         let mut ty_args_loaded = vec![];
@@ -215,50 +416,301 @@ impl MoveRuntime {
         }
 
         let ident = IdentStr::new(function_name).map_err(|e| anyhow::anyhow!(e.to_string()))?;
-
+        let parameters = session
+            .load_function(module_id, ident, &ty_args_loaded)
+            .map_err(|e| anyhow::anyhow!(format!("load function error: {:?}", e)))?
+            .parameters
+            .iter()
+            .map(|ty| ty.subst(&ty_args_loaded))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| anyhow::anyhow!(format!("type error: {:?}", e)))?;
+
+        // Object inputs come first, checked against their owner and the parameter type
+        let sender = inputs.tx_context.sender;
+        let mut object_params = Vec::new();
+        let mut call_args = Vec::new();
+        if inputs.objects.len() > parameters.len() {
+            anyhow::bail!(
+                "{}::{} takes {} parameters but {} objects were passed",
+                module_id,
+                function_name,
+                parameters.len(),
+                inputs.objects.len()
+            );
+        }
+        for (id, ty) in inputs.objects.iter().zip(&parameters) {
+            let (param, inner) = match ty {
+                Type::Reference(inner) => (ObjectParam::Ref, inner.as_ref()),
+                Type::MutableReference(inner) => (ObjectParam::MutRef, inner.as_ref()),
+                other => (ObjectParam::ByValue, other),
+            };
+            let object = self
+                .storage
+                .object(id)
+                .ok_or_else(|| anyhow::anyhow!("Object {} not found", id))?;
+            let expected = session
+                .get_type_tag(inner)
+                .map_err(|e| anyhow::anyhow!(format!("type error: {:?}", e)))?
+                .to_string();
+            if object.type_tag != expected {
+                anyhow::bail!(
+                    "Object {} is a {}, expected {}",
+                    id,
+                    object.type_tag,
+                    expected
+                );
+            }
+            match object.ownership {
+                Ownership::Address if object.owner != sender => {
+                    anyhow::bail!("Object {} is owned by {}, not {}", id, object.owner, sender)
+                }
+                Ownership::Shared if param == ObjectParam::ByValue => {
+                    anyhow::bail!("Shared object {} can only be passed by reference", id)
+                }
+                Ownership::Immutable if param != ObjectParam::Ref => {
+                    anyhow::bail!("Immutable object {} can only be read", id)
+                }
+                _ => {}
+            }
+            if object_params.iter().any(|(seen, _)| seen == id) {
+                anyhow::bail!("Object {} is passed more than once", id);
+            }
+            object_params.push((*id, param));
+            call_args.push(object.contents.clone());
+        }
+        call_args.extend(args);
         session
-            .execute_entry_function(module_id, ident, ty_args_loaded, args, &mut gas)
+            .get_native_extensions()
+            .get_mut::<ObjectRuntime>()
+            .make_available(
+                object_params
+                    .iter()
+                    .filter(|(_, param)| *param == ObjectParam::ByValue)
+                    .map(|(id, _)| *id),
+            );
+
+        let takes_tx_context = parameters.len() == call_args.len() + 1
+            && match parameters.last() {
+                Some(Type::MutableReference(inner)) => session
+                    .get_type_tag(inner)
+                    .is_ok_and(|tag| tag == TypeTag::Struct(Box::new(TxContext::struct_tag()))),
+                _ => false,
+            };
+        if takes_tx_context {
+            call_args.push(bcs::to_bytes(&inputs.tx_context)?);
+        }
+
+        let returned = session
+            .execute_entry_function(module_id, ident, ty_args_loaded, call_args, gas)
             .map_err(|e| anyhow::anyhow!(format!("exec error: {:?}", e)))?;
 
-        let (res, new_storage) = session.finish();
-        let (move_changeset, events) =
+        let (res, new_storage) = session.finish_with_extensions();
+        let (move_changeset, events, mut extensions) =
             res.map_err(|e| anyhow::anyhow!(format!("finish error: {:?}", e)))?;
+        let object_changes = extensions.remove::<ObjectRuntime>().changes;
+        let field_changes = extensions
+            .remove::<FieldRuntime>()
+            .into_changes()
+            .map_err(|e| anyhow::anyhow!(format!("dynamic field error: {:?}", e)))?;
+        let emitted = extensions.remove::<EventRuntime>().events;
+        drop(extensions);
+        charge_storage(gas, &move_changeset)?;
+        // Events are kept in blocks, so they are charged like storage too
+        let field_bytes: usize = field_changes
+            .values()
+            .flatten()
+            .map(|v| v.bytes.len())
+            .sum();
+        let event_bytes: usize = emitted.iter().map(|(_, bytes)| bytes.len()).sum();
+        gas.charge_storage_write(field_bytes + event_bytes)
+            .map_err(|e| anyhow::anyhow!(format!("storage error: {:?}", e)))?;
+
+        // Objects taken by value are consumed unless handed on below; mutable
+        // references write their new contents back
+        let mut object_writes = BTreeMap::new();
+        let mut written = 0;
+        for (index, (id, param)) in object_params.iter().enumerate() {
+            match param {
+                ObjectParam::ByValue => {
+                    object_writes.insert(*id, None);
+                }
+                ObjectParam::MutRef => {
+                    let contents = returned
+                        .mutable_reference_outputs
+                        .iter()
+                        .find(|(local, _, _)| *local as usize == index)
+                        .map(|(_, bytes, _)| bytes.clone());
+                    if let (Some(object), Some(contents)) = (self.storage.object(id), contents) {
+                        written += contents.len();
+                        let mut object = object.clone();
+                        object.contents = contents;
+                        object_writes.insert(*id, Some(object));
+                    }
+                }
+                ObjectParam::Ref => {}
+            }
+        }
+        let mut deposits = Vec::new();
+        for object in object_changes {
+            if let Some((coin_type, coin)) = Coin::deposit(&object)? {
+                object_writes.insert(object.id, None);
+                deposits.push((object.owner, coin_type, coin.value));
+                continue;
+            }
+            written += object.contents.len();
+            object_writes.insert(object.id, Some(object));
+        }
+        gas.charge_storage_write(written)
+            .map_err(|e| anyhow::anyhow!(format!("storage error: {:?}", e)))?;
 
         let mut storage = new_storage;
         storage
             .apply(move_changeset.clone())
             .map_err(|e| anyhow::anyhow!(format!("apply error: {:?}", e)))?;
+        storage.apply_fields(field_changes);
+        storage.apply_objects(object_writes);
 
         self.storage = storage;
 
         // Create ChangeSet from Move VM execution
-        let mut cs = ChangeSet::new();
+        let mut cs = ChangeSet::with_gas(gas.gas_used());
+
+        for (owner, coin_type, value) in deposits {
+            cs.get_or_create_change(owner)
+                .credit_coin(&coin_type, value);
+        }
 
         // Parse Move VM changeset and events
         self.parse_move_changeset(&move_changeset, &mut cs);
         self.parse_move_events(&events, &mut cs);
 
-        // If gas accounting requested, include gas debit/credit in ChangeSet.
-        if let Some((gas_limit, gas_price)) = gas_info {
-            let mut meter = GasMeter::new(gas_limit, gas_price);
-            // We use a default complexity of 1 when called directly via runtime.
-            let gas_op = GasOperation::ExecuteFunction { complexity: 1 };
-            meter.consume(gas_op.gas_units())?;
-            let gas_cost = meter.total_cost();
+        // Events from `0x2::event::emit` are keyed by the emitting transaction
+        // and numbered in emission order
+        for (type_tag, event_data) in emitted {
+            let sequence_number = cs.events.len() as u64;
+            cs.add_event(crate::changeset::Event {
+                key: inputs.tx_context.tx_hash.clone(),
+                sequence_number,
+                type_tag: type_tag.to_string(),
+                event_data,
+            });
+        }
 
-            // If sender provided, debit them and increment sequence to prevent replay.
-            if let Some(saddr) = sender {
-                let sender_change = cs.get_or_create_change(saddr);
-                sender_change.increment_sequence();
-                sender_change.debit(gas_cost);
+        Ok(cs)
+    }
+
+    /// Execute a view function without committing any changes and return its
+    /// values as BCS plus JSON. Runs against `storage` when given (e.g. state at
+    /// an earlier height) using a fresh VM, otherwise against the current storage.
+    /// Fails once the call uses up `gas`.
+    pub fn execute_view_function(
+        &self,
+        storage: Option<MoveStorage>,
+        module_id: &ModuleId,
+        function_name: &str,
+        type_args: Vec<TypeTag>,
+        args: Vec<Vec<u8>>,
+        gas: &mut MoveGasMeter,
+    ) -> Result<Vec<ViewValue>> {
+        let fresh_vm;
+        let (vm, storage) = match storage {
+            Some(storage) => {
+                fresh_vm = new_vm()?;
+                (&fresh_vm, storage)
             }
+            None => (&self.vm, self.storage.clone()),
+        };
+
+        let module_bytes = storage
+            .get_module(module_id)
+            .ok()
+            .flatten()
+            .ok_or_else(|| anyhow::anyhow!("Module {} not found", module_id))?;
+        let abi = ContractABI::from_bytecode(&module_bytes)?;
+        match abi.get_function(function_name) {
+            Some(function) if function.is_view => {}
+            Some(_) => anyhow::bail!("{}::{} is not a view function", module_id, function_name),
+            None => anyhow::bail!("Function {}::{} not found", module_id, function_name),
+        }
 
-            let dao_addr = AccountAddress::from_hex_literal(KanariAddress::DAO_ADDRESS)?;
-            cs.collect_gas(dao_addr, gas_cost);
-            cs.set_gas_used(meter.gas_used);
+        let mut session =
+            vm.new_session_with_extensions(&storage, new_extensions(&storage, Vec::new(), None));
+
+        let mut ty_args_loaded = vec![];
+        for tag in type_args.iter() {
+            let ty = session
+                .load_type(tag)
+                .map_err(|e| anyhow::anyhow!(format!("load type error: {:?}", e)))?;
+            ty_args_loaded.push(ty);
         }
 
-        Ok(cs)
+        let ident = IdentStr::new(function_name).map_err(|e| anyhow::anyhow!(e.to_string()))?;
+        let instantiation = session
+            .load_function(module_id, ident, &ty_args_loaded)
+            .map_err(|e| anyhow::anyhow!(format!("load function error: {:?}", e)))?;
+
+        let returned = session
+            .execute_function_bypass_visibility(module_id, ident, ty_args_loaded.clone(), args, gas)
+            .map_err(|e| {
+                if e.major_status() == StatusCode::OUT_OF_GAS {
+                    anyhow::anyhow!(
+                        "View function ran out of gas after {} units",
+                        gas.gas_used()
+                    )
+                } else {
+                    anyhow::anyhow!(format!("exec error: {:?}", e))
+                }
+            })?;
+
+        // The session is dropped without `finish`, discarding any writes
+        let mut values = Vec::new();
+        for (ty, (bytes, _)) in instantiation.return_.iter().zip(returned.return_values) {
+            let ty = ty
+                .subst(&ty_args_loaded)
+                .map_err(|e| anyhow::anyhow!(format!("type error: {:?}", e)))?;
+            let type_tag = session
+                .get_type_tag(&ty)
+                .map_err(|e| anyhow::anyhow!(format!("type error: {:?}", e)))?;
+            let layout = session
+                .type_to_fully_annotated_layout(&ty)
+                .map_err(|e| anyhow::anyhow!(format!("layout error: {:?}", e)))?;
+            let value = AnnotatedMoveValue::simple_deserialize(&bytes, &layout)?;
+            values.push(ViewValue {
+                type_tag: type_tag.to_string(),
+                json: move_value_to_json(&value),
+                bcs: bytes,
+            });
+        }
+
+        Ok(values)
+    }
+
+    /// Resources stored under `address`, decoded with the annotated layouts of
+    /// their published modules. Pass `struct_tag` to fetch a single resource.
+    pub fn get_resources(
+        &self,
+        address: &AccountAddress,
+        struct_tag: Option<&StructTag>,
+    ) -> Vec<MoveResource> {
+        let session = self.vm.new_session(&self.storage);
+        self.storage
+            .resources()
+            .filter(|(addr, tag, _)| {
+                *addr == address && struct_tag.is_none_or(|wanted| *tag == wanted)
+            })
+            .map(|(_, tag, blob)| MoveResource {
+                struct_tag: tag.to_string(),
+                bcs: blob.clone(),
+                json: decode_json(&session, &TypeTag::Struct(Box::new(tag.clone())), blob),
+            })
+            .collect()
+    }
+
+    /// Decode BCS `bytes` of type `type_tag` (e.g. an event payload) into JSON using
+    /// the annotated layout from the published modules; `null` if it cannot be decoded
+    pub fn decode_json(&self, type_tag: &TypeTag, bytes: &[u8]) -> serde_json::Value {
+        let session = self.vm.new_session(&self.storage);
+        decode_json(&session, type_tag, bytes)
     }
 
     /// Parse Move VM ChangeSet and extract state changes into Kanari ChangeSet
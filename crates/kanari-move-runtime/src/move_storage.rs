@@ -0,0 +1,306 @@
+use crate::object::Object;
+use anyhow::Result;
+use kanari_crypto::hash_data_blake3;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::effects::{ChangeSet as MoveChangeSet, Op as MoveOp};
+use move_core_types::language_storage::{ModuleId, StructTag, TypeTag};
+use move_core_types::resolver::{LinkageResolver, ModuleResolver, ResourceResolver};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Key of a dynamic field: the id of the object it is attached to and the
+/// field id derived from its name
+pub type FieldKey = (AccountAddress, AccountAddress);
+
+/// A stored dynamic field value and the type it was written with
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldValue {
+    pub type_tag: TypeTag,
+    pub bytes: Vec<u8>,
+}
+
+/// In-memory Move storage backend holding published modules, resources,
+/// dynamic fields and objects.
+///
+/// Unlike `move_vm_test_utils::InMemoryStorage`, resources are kept and all maps
+/// can be enumerated, which is required for snapshots and state root computation.
+#[derive(Debug, Clone, Default)]
+pub struct MoveStorage {
+    modules: BTreeMap<ModuleId, Vec<u8>>,
+    resources: BTreeMap<(AccountAddress, StructTag), Vec<u8>>,
+    fields: BTreeMap<FieldKey, FieldValue>,
+    objects: BTreeMap<AccountAddress, Object>,
+}
+
+impl MoveStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Apply a Move VM changeset (module and resource writes) to storage
+    pub fn apply(&mut self, changeset: MoveChangeSet) -> Result<()> {
+        for (addr, account_changes) in changeset.into_inner() {
+            let (modules, resources) = account_changes.into_inner();
+            for (name, op) in modules {
+                let module_id = ModuleId::new(addr, name);
+                match op {
+                    MoveOp::New(blob) | MoveOp::Modify(blob) => {
+                        self.modules.insert(module_id, blob);
+                    }
+                    MoveOp::Delete => {
+                        self.modules.remove(&module_id);
+                    }
+                }
+            }
+            for (struct_tag, op) in resources {
+                match op {
+                    MoveOp::New(blob) | MoveOp::Modify(blob) => {
+                        self.resources.insert((addr, struct_tag), blob);
+                    }
+                    MoveOp::Delete => {
+                        self.resources.remove(&(addr, struct_tag));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn publish_or_overwrite_module(&mut self, module_id: ModuleId, blob: Vec<u8>) {
+        self.modules.insert(module_id, blob);
+    }
+
+    pub fn set_resource(&mut self, address: AccountAddress, struct_tag: StructTag, blob: Vec<u8>) {
+        self.resources.insert((address, struct_tag), blob);
+    }
+
+    pub fn resource(&self, address: &AccountAddress, struct_tag: &StructTag) -> Option<&Vec<u8>> {
+        self.resources.get(&(*address, struct_tag.clone()))
+    }
+
+    pub fn remove_resource(&mut self, address: &AccountAddress, struct_tag: &StructTag) {
+        self.resources.remove(&(*address, struct_tag.clone()));
+    }
+
+    /// Apply dynamic field writes (`Some`) and deletions (`None`)
+    pub fn apply_fields(&mut self, changes: BTreeMap<FieldKey, Option<FieldValue>>) {
+        for (key, change) in changes {
+            match change {
+                Some(value) => {
+                    self.fields.insert(key, value);
+                }
+                None => {
+                    self.fields.remove(&key);
+                }
+            }
+        }
+    }
+
+    pub fn set_field(&mut self, parent: AccountAddress, id: AccountAddress, value: FieldValue) {
+        self.fields.insert((parent, id), value);
+    }
+
+    pub fn field(&self, parent: &AccountAddress, id: &AccountAddress) -> Option<&FieldValue> {
+        self.fields.get(&(*parent, *id))
+    }
+
+    /// Apply object writes (`Some`) and deletions (`None`), keyed by object id
+    pub fn apply_objects(&mut self, changes: BTreeMap<AccountAddress, Option<Object>>) {
+        for (id, change) in changes {
+            match change {
+                Some(object) => {
+                    self.objects.insert(id, object);
+                }
+                None => {
+                    self.objects.remove(&id);
+                }
+            }
+        }
+    }
+
+    pub fn set_object(&mut self, object: Object) {
+        self.objects.insert(object.id, object);
+    }
+
+    pub fn object(&self, id: &AccountAddress) -> Option<&Object> {
+        self.objects.get(id)
+    }
+
+    /// Address-owned objects of `owner`
+    pub fn owned_objects<'a>(
+        &'a self,
+        owner: &'a AccountAddress,
+    ) -> impl Iterator<Item = &'a Object> {
+        self.objects
+            .values()
+            .filter(move |object| object.owner().as_ref() == Some(owner))
+    }
+
+    /// All published modules in deterministic (address, name) order
+    pub fn modules(&self) -> impl Iterator<Item = (&ModuleId, &Vec<u8>)> {
+        self.modules.iter()
+    }
+
+    /// All stored resources in deterministic (address, type) order
+    pub fn resources(&self) -> impl Iterator<Item = (&AccountAddress, &StructTag, &Vec<u8>)> {
+        self.resources
+            .iter()
+            .map(|((addr, tag), blob)| (addr, tag, blob))
+    }
+
+    /// All dynamic fields in deterministic (parent, id) order
+    pub fn fields(&self) -> impl Iterator<Item = (&FieldKey, &FieldValue)> {
+        self.fields.iter()
+    }
+
+    /// All objects in deterministic id order
+    pub fn objects(&self) -> impl Iterator<Item = &Object> {
+        self.objects.values()
+    }
+
+    pub fn module_count(&self) -> usize {
+        self.modules.len()
+    }
+
+    pub fn resource_count(&self) -> usize {
+        self.resources.len()
+    }
+
+    /// Hash over all modules, resources, dynamic fields and objects.
+    /// All maps are ordered, so the root is identical on every node holding the same data.
+    pub fn compute_root(&self) -> Vec<u8> {
+        let mut data = Vec::new();
+        for (module_id, blob) in &self.modules {
+            data.extend_from_slice(module_id.address().as_ref());
+            data.extend_from_slice(module_id.name().as_bytes());
+            data.extend_from_slice(&hash_data_blake3(blob));
+        }
+        for ((addr, tag), blob) in &self.resources {
+            data.extend_from_slice(addr.as_ref());
+            data.extend_from_slice(tag.to_canonical_string(true).as_bytes());
+            data.extend_from_slice(&hash_data_blake3(blob));
+        }
+        for ((parent, id), value) in &self.fields {
+            data.extend_from_slice(parent.as_ref());
+            data.extend_from_slice(id.as_ref());
+            data.extend_from_slice(value.type_tag.to_canonical_string(true).as_bytes());
+            data.extend_from_slice(&hash_data_blake3(&value.bytes));
+        }
+        for (id, object) in &self.objects {
+            data.extend_from_slice(id.as_ref());
+            data.extend_from_slice(object.owner.as_ref());
+            data.push(object.ownership as u8);
+            data.extend_from_slice(object.type_tag.as_bytes());
+            data.extend_from_slice(&hash_data_blake3(&object.contents));
+        }
+        hash_data_blake3(&data)
+    }
+}
+
+impl LinkageResolver for MoveStorage {
+    type Error = ();
+}
+
+impl ModuleResolver for MoveStorage {
+    type Error = ();
+
+    fn get_module(&self, module_id: &ModuleId) -> Result<Option<Vec<u8>>, Self::Error> {
+        Ok(self.modules.get(module_id).cloned())
+    }
+}
+
+impl ResourceResolver for MoveStorage {
+    type Error = ();
+
+    fn get_resource(
+        &self,
+        address: &AccountAddress,
+        tag: &StructTag,
+    ) -> Result<Option<Vec<u8>>, Self::Error> {
+        Ok(self.resources.get(&(*address, tag.clone())).cloned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::object::Ownership;
+    use move_core_types::identifier::Identifier;
+
+    fn object(id: u8, owner: u8, ownership: Ownership) -> Object {
+        Object {
+            id: AccountAddress::new([id; AccountAddress::LENGTH]),
+            owner: AccountAddress::new([owner; AccountAddress::LENGTH]),
+            ownership,
+            type_tag: "0x42::pet::Pet".to_string(),
+            contents: vec![id],
+        }
+    }
+
+    #[test]
+    fn test_root_is_order_independent() {
+        let a = ModuleId::new(AccountAddress::ONE, Identifier::new("a").unwrap());
+        let b = ModuleId::new(AccountAddress::TWO, Identifier::new("b").unwrap());
+
+        let mut s1 = MoveStorage::new();
+        s1.publish_or_overwrite_module(a.clone(), vec![1]);
+        s1.publish_or_overwrite_module(b.clone(), vec![2]);
+
+        let mut s2 = MoveStorage::new();
+        s2.publish_or_overwrite_module(b, vec![2]);
+        s2.publish_or_overwrite_module(a, vec![1]);
+
+        assert_eq!(s1.compute_root(), s2.compute_root());
+    }
+
+    #[test]
+    fn test_fields_in_root() {
+        let mut storage = MoveStorage::new();
+        let empty = storage.compute_root();
+        let key = (AccountAddress::ONE, AccountAddress::TWO);
+        let value = FieldValue {
+            type_tag: TypeTag::U64,
+            bytes: bcs::to_bytes(&7u64).unwrap(),
+        };
+
+        let mut changes = BTreeMap::new();
+        changes.insert(key, Some(value.clone()));
+        storage.apply_fields(changes.clone());
+        assert_eq!(storage.field(&key.0, &key.1), Some(&value));
+        assert_ne!(storage.compute_root(), empty);
+
+        changes.insert(key, None);
+        storage.apply_fields(changes);
+        assert_eq!(storage.fields().count(), 0);
+        assert_eq!(storage.compute_root(), empty);
+    }
+
+    #[test]
+    fn test_objects_keyed_by_id() {
+        let mut storage = MoveStorage::new();
+        let empty = storage.compute_root();
+        storage.set_object(object(1, 7, Ownership::Address));
+        storage.set_object(object(2, 7, Ownership::Shared));
+        storage.set_object(object(3, 8, Ownership::Address));
+        assert_ne!(storage.compute_root(), empty);
+
+        let owner = AccountAddress::new([7; AccountAddress::LENGTH]);
+        let owned: Vec<_> = storage.owned_objects(&owner).map(|o| o.id).collect();
+        assert_eq!(
+            owned,
+            vec![AccountAddress::new([1; AccountAddress::LENGTH])]
+        );
+
+        // Writing an id replaces the object in place
+        let mut changes = BTreeMap::new();
+        changes.insert(
+            AccountAddress::new([1; 32]),
+            Some(object(1, 8, Ownership::Immutable)),
+        );
+        changes.insert(AccountAddress::new([3; 32]), None);
+        storage.apply_objects(changes);
+        assert_eq!(storage.objects().count(), 2);
+        assert_eq!(storage.owned_objects(&owner).count(), 0);
+        assert!(storage.object(&AccountAddress::new([3; 32])).is_none());
+    }
+}
@@ -1,8 +1,8 @@
+use crate::move_storage::MoveStorage;
 use anyhow::{Context, Result};
 use move_core_types::account_address::AccountAddress;
 use move_core_types::identifier::Identifier;
 use move_core_types::language_storage::ModuleId;
-use move_vm_test_utils::InMemoryStorage;
 use rocksdb::Direction;
 use rocksdb::{DB, IteratorMode, Options};
 use std::path::PathBuf;
@@ -57,8 +57,8 @@ impl MoveVMState {
         Ok(())
     }
 
-    /// Load persisted modules into an `InMemoryStorage` instance.
-    pub fn load_into_storage(&self, storage: &mut InMemoryStorage) -> Result<()> {
+    /// Load persisted modules into a `MoveStorage` instance.
+    pub fn load_into_storage(&self, storage: &mut MoveStorage) -> Result<()> {
         // Start iteration from the module prefix to avoid scanning unrelated keys.
         let prefix = b"module:";
         let iter = self
@@ -0,0 +1,559 @@
+// Native functions available to Move code run by the node: the standard
+// library, the kanari-system crypto natives and the object, dynamic field,
+// event and random natives below.
+
+use better_any::{Tid, TidAble};
+use kanari_types::address::Address as KanariAddress;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::effects::Op;
+use move_core_types::language_storage::TypeTag;
+use move_core_types::runtime_value::MoveTypeLayout;
+use move_core_types::vm_status::StatusCode;
+use move_stdlib_natives::GasParameters;
+use move_vm_runtime::native_extensions::NativeContextExtensions;
+use move_vm_runtime::native_functions::{
+    NativeContext, NativeFunction, NativeFunctionTable, make_table_from_iter,
+};
+use move_vm_types::loaded_data::runtime_types::Type;
+use move_vm_types::natives::function::{NativeResult, PartialVMError, PartialVMResult};
+use move_vm_types::pop_arg;
+use move_vm_types::values::{GlobalValue, Reference, StructRef, Value};
+use sha3::{Digest, Sha3_256};
+use smallvec::smallvec;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::sync::{Arc, LazyLock};
+
+use crate::move_storage::{FieldKey, FieldValue, MoveStorage};
+use crate::object::{Object, Ownership, derive_id, field_id};
+
+/// Abort code of `0x2::transfer` for an object whose id was neither passed
+/// to the transaction by value nor created by it
+const E_OBJECT_ID_UNAVAILABLE: u64 = 3;
+
+/// Abort codes of `0x2::dynamic_field`
+const E_FIELD_ALREADY_EXISTS: u64 = 0;
+const E_FIELD_DOES_NOT_EXIST: u64 = 1;
+const E_FIELD_TYPE_MISMATCH: u64 = 2;
+
+/// Abort codes of `0x2::random`
+const E_INVALID_RANGE: u64 = 0;
+const E_TOO_MANY_BYTES: u64 = 1;
+const E_RANDOMNESS_UNAVAILABLE: u64 = 2;
+
+/// Most bytes a single `0x2::random::bytes` call can draw
+const MAX_RANDOM_BYTES: u64 = 4096;
+
+/// Native context extension collecting the objects a transaction transferred,
+/// froze or shared, in call order
+#[derive(Default, Tid)]
+pub struct ObjectRuntime {
+    pub changes: Vec<Object>,
+    /// Hash of the running transaction; ids derived from it are new objects.
+    /// `None` in Move unit tests, where any derived id counts as new.
+    tx_hash: Option<Vec<u8>>,
+    /// Ids the transaction may record an object under: those of the objects
+    /// it took by value and those it created. Each can be used once.
+    available: BTreeSet<AccountAddress>,
+}
+
+impl ObjectRuntime {
+    pub fn new(tx_hash: Vec<u8>) -> Self {
+        Self {
+            tx_hash: Some(tx_hash),
+            ..Self::default()
+        }
+    }
+
+    /// Allow recording objects under `ids`, e.g. objects passed by value
+    pub fn make_available(&mut self, ids: impl IntoIterator<Item = AccountAddress>) {
+        self.available.extend(ids);
+    }
+}
+
+/// Native context extension collecting the events a transaction emitted, as
+/// type and BCS payload, in emission order
+#[derive(Default, Tid)]
+pub struct EventRuntime {
+    pub events: Vec<(TypeTag, Vec<u8>)>,
+}
+
+/// Native context extension drawing a transaction's random bytes. Block `n`
+/// of the stream is SHA3-256 of the transaction's seed and `n`. Without a
+/// seed the natives abort.
+#[derive(Default, Tid)]
+pub struct RandomRuntime {
+    seed: Option<Vec<u8>>,
+    counter: u64,
+}
+
+impl RandomRuntime {
+    pub fn new(seed: Option<Vec<u8>>) -> Self {
+        Self { seed, counter: 0 }
+    }
+
+    fn next_block(&mut self) -> [u8; 32] {
+        let mut hasher = Sha3_256::new();
+        hasher.update(self.seed.as_deref().unwrap_or_default());
+        hasher.update(self.counter.to_le_bytes());
+        self.counter += 1;
+        hasher.finalize().into()
+    }
+
+    fn bytes(&mut self, len: usize) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(len);
+        while bytes.len() < len {
+            let block = self.next_block();
+            let take = (len - bytes.len()).min(block.len());
+            bytes.extend_from_slice(&block[..take]);
+        }
+        bytes
+    }
+
+    /// Uniform integer in `[min, max]`, rejecting draws from the incomplete
+    /// last multiple of the range so no value is favoured
+    fn u128_in_range(&mut self, min: u128, max: u128) -> u128 {
+        let draw = |runtime: &mut Self| {
+            u128::from_le_bytes(runtime.next_block()[..16].try_into().unwrap())
+        };
+        let Some(range) = (max - min).checked_add(1) else {
+            return draw(self);
+        };
+        // Largest draw below the last multiple of `range` within u128
+        let limit = u128::MAX - (u128::MAX % range + 1) % range;
+        loop {
+            let value = draw(self);
+            if value <= limit {
+                return min + value % range;
+            }
+        }
+    }
+}
+
+/// Native context extension holding the dynamic fields a session has touched.
+/// Fields are loaded from `storage` on first access; their writes are taken
+/// out with `into_changes` once the session finishes.
+#[derive(Tid)]
+pub struct FieldRuntime<'a> {
+    storage: &'a MoveStorage,
+    fields: BTreeMap<FieldKey, LoadedField>,
+}
+
+struct LoadedField {
+    type_tag: TypeTag,
+    layout: MoveTypeLayout,
+    value: GlobalValue,
+}
+
+impl<'a> FieldRuntime<'a> {
+    pub fn new(storage: &'a MoveStorage) -> Self {
+        Self {
+            storage,
+            fields: BTreeMap::new(),
+        }
+    }
+
+    /// Type of the field at `key`, if it exists
+    fn type_of(&self, key: &FieldKey) -> PartialVMResult<Option<&TypeTag>> {
+        match self.fields.get(key) {
+            Some(field) => Ok(field.value.exists()?.then_some(&field.type_tag)),
+            None => Ok(self
+                .storage
+                .field(&key.0, &key.1)
+                .map(|value| &value.type_tag)),
+        }
+    }
+
+    fn add(
+        &mut self,
+        key: FieldKey,
+        type_tag: TypeTag,
+        layout: MoveTypeLayout,
+        value: Value,
+    ) -> PartialVMResult<Result<(), u64>> {
+        if self.type_of(&key)?.is_some() {
+            return Ok(Err(E_FIELD_ALREADY_EXISTS));
+        }
+        let field = self.fields.entry(key).or_insert_with(|| LoadedField {
+            type_tag: type_tag.clone(),
+            layout: layout.clone(),
+            value: GlobalValue::none(),
+        });
+        field.type_tag = type_tag;
+        field.layout = layout;
+        field.value.move_to(value).map_err(|(e, _)| e)?;
+        Ok(Ok(()))
+    }
+
+    /// The field at `key` if it holds a `type_tag`, otherwise the abort code
+    fn load(
+        &mut self,
+        key: FieldKey,
+        type_tag: TypeTag,
+        layout: MoveTypeLayout,
+    ) -> PartialVMResult<Result<&mut GlobalValue, u64>> {
+        match self.type_of(&key)? {
+            None => return Ok(Err(E_FIELD_DOES_NOT_EXIST)),
+            Some(stored) if *stored != type_tag => return Ok(Err(E_FIELD_TYPE_MISMATCH)),
+            Some(_) => {}
+        }
+        if !self.fields.contains_key(&key) {
+            let bytes = &self
+                .storage
+                .field(&key.0, &key.1)
+                .ok_or_else(invariant)?
+                .bytes;
+            let value = Value::simple_deserialize(bytes, &layout).ok_or_else(invariant)?;
+            self.fields.insert(
+                key,
+                LoadedField {
+                    type_tag,
+                    layout,
+                    value: GlobalValue::cached(value)?,
+                },
+            );
+        }
+        Ok(Ok(&mut self
+            .fields
+            .get_mut(&key)
+            .ok_or_else(invariant)?
+            .value))
+    }
+
+    /// Fields written (`Some`) or removed (`None`) by the session
+    pub fn into_changes(self) -> PartialVMResult<BTreeMap<FieldKey, Option<FieldValue>>> {
+        let mut changes = BTreeMap::new();
+        for (key, field) in self.fields {
+            let change = match field.value.into_effect() {
+                None => continue,
+                Some(Op::Delete) => None,
+                Some(Op::New(value) | Op::Modify(value)) => Some(FieldValue {
+                    type_tag: field.type_tag,
+                    bytes: value
+                        .simple_serialize(&field.layout)
+                        .ok_or_else(invariant)?,
+                }),
+            };
+            changes.insert(key, change);
+        }
+        Ok(changes)
+    }
+}
+
+/// Extensions the object, dynamic field, event and random natives need for
+/// the transaction hashed `tx_hash`, reading fields from `storage` and drawing
+/// randomness from `random_seed`, if it may draw any
+pub fn new_extensions(
+    storage: &MoveStorage,
+    tx_hash: Vec<u8>,
+    random_seed: Option<Vec<u8>>,
+) -> NativeContextExtensions<'_> {
+    let mut extensions = NativeContextExtensions::default();
+    extensions.add(ObjectRuntime::new(tx_hash));
+    extensions.add(FieldRuntime::new(storage));
+    extensions.add(EventRuntime::default());
+    extensions.add(RandomRuntime::new(random_seed));
+    extensions
+}
+
+/// Extension hook for Move unit tests, which run without chain state
+pub fn add_unit_test_extensions(extensions: &mut NativeContextExtensions<'_>) {
+    static EMPTY: LazyLock<MoveStorage> = LazyLock::new(MoveStorage::new);
+    extensions.add(ObjectRuntime::default());
+    extensions.add(FieldRuntime::new(&EMPTY));
+    extensions.add(EventRuntime::default());
+    extensions.add(RandomRuntime::new(Some(Vec::new())));
+}
+
+/// Every native function the node's VM is created with
+pub fn all_natives() -> NativeFunctionTable {
+    let std_addr = AccountAddress::from_hex_literal(KanariAddress::STD_ADDRESS).unwrap();
+    let system_addr =
+        AccountAddress::from_hex_literal(KanariAddress::KANARI_SYSTEM_ADDRESS).unwrap();
+
+    let mut natives = move_stdlib_natives::all_natives(std_addr, GasParameters::zeros());
+    natives.extend(kanari_crypto::move_natives::all_natives(system_addr));
+    natives.extend(object_natives(system_addr));
+    natives.extend(dynamic_field_natives(system_addr));
+    natives.extend(event_natives(system_addr));
+    natives.extend(random_natives(system_addr));
+    natives
+}
+
+/// `0x2::transfer` ownership natives and `0x2::tx_context::derive_id`
+pub fn object_natives(system_addr: AccountAddress) -> NativeFunctionTable {
+    let natives: Vec<(&str, &str, NativeFunction)> = vec![
+        (
+            "transfer",
+            "transfer_impl",
+            Arc::new(|context, ty_args, mut args| {
+                let recipient = pop_arg!(args, AccountAddress);
+                record_object(context, ty_args, args, recipient, Ownership::Address)
+            }),
+        ),
+        (
+            "transfer",
+            "freeze_object_impl",
+            Arc::new(|context, ty_args, args| {
+                record_object(
+                    context,
+                    ty_args,
+                    args,
+                    AccountAddress::ZERO,
+                    Ownership::Immutable,
+                )
+            }),
+        ),
+        (
+            "transfer",
+            "share_object_impl",
+            Arc::new(|context, ty_args, args| {
+                record_object(
+                    context,
+                    ty_args,
+                    args,
+                    AccountAddress::ZERO,
+                    Ownership::Shared,
+                )
+            }),
+        ),
+        (
+            "tx_context",
+            "derive_id",
+            Arc::new(|context, _ty_args, mut args| {
+                let ids_created = pop_arg!(args, u64);
+                let tx_hash = pop_arg!(args, Vec<u8>);
+                let id = derive_id(&tx_hash, ids_created);
+                let objects = context.extensions_mut().get_mut::<ObjectRuntime>();
+                if objects.tx_hash.as_ref().is_none_or(|hash| *hash == tx_hash) {
+                    objects.available.insert(id);
+                }
+                Ok(NativeResult::ok(
+                    context.gas_used(),
+                    smallvec![Value::address(id)],
+                ))
+            }),
+        ),
+    ];
+    make_table_from_iter(system_addr, natives)
+}
+
+/// `0x2::dynamic_field` natives, backed by the session's `FieldRuntime`
+pub fn dynamic_field_natives(system_addr: AccountAddress) -> NativeFunctionTable {
+    let natives: Vec<(&str, &str, NativeFunction)> = vec![
+        (
+            "dynamic_field",
+            "hash_type_and_key",
+            Arc::new(|context, ty_args, mut args| {
+                let key = args.pop_back().ok_or_else(invariant)?;
+                let parent = pop_arg!(args, AccountAddress);
+                let (type_tag, layout) = type_info(context, &ty_args)?;
+                let bytes = key.simple_serialize(&layout).ok_or_else(invariant)?;
+                Ok(NativeResult::ok(
+                    context.gas_used(),
+                    smallvec![Value::address(field_id(&parent, &type_tag, &bytes))],
+                ))
+            }),
+        ),
+        (
+            "dynamic_field",
+            "add_field",
+            Arc::new(|context, ty_args, mut args| {
+                let child = args.pop_back().ok_or_else(invariant)?;
+                let id = pop_arg!(args, AccountAddress);
+                let parent = pop_arg!(args, AccountAddress);
+                let (type_tag, layout) = type_info(context, &ty_args)?;
+                let cost = context.gas_used();
+                let fields = context.extensions_mut().get_mut::<FieldRuntime>();
+                Ok(match fields.add((parent, id), type_tag, layout, child)? {
+                    Ok(()) => NativeResult::ok(cost, smallvec![]),
+                    Err(code) => NativeResult::err(cost, code),
+                })
+            }),
+        ),
+        ("dynamic_field", "borrow_field", Arc::new(borrow_field)),
+        ("dynamic_field", "borrow_field_mut", Arc::new(borrow_field)),
+        (
+            "dynamic_field",
+            "remove_field",
+            Arc::new(|context, ty_args, mut args| {
+                let id = pop_arg!(args, AccountAddress);
+                let parent = pop_arg!(args, AccountAddress);
+                let (type_tag, layout) = type_info(context, &ty_args)?;
+                let cost = context.gas_used();
+                let fields = context.extensions_mut().get_mut::<FieldRuntime>();
+                Ok(match fields.load((parent, id), type_tag, layout)? {
+                    Ok(value) => NativeResult::ok(cost, smallvec![value.move_from()?]),
+                    Err(code) => NativeResult::err(cost, code),
+                })
+            }),
+        ),
+        (
+            "dynamic_field",
+            "has_field",
+            Arc::new(|context, _ty_args, mut args| {
+                let id = pop_arg!(args, AccountAddress);
+                let parent = pop_arg!(args, AccountAddress);
+                let fields = context.extensions().get::<FieldRuntime>();
+                let exists = fields.type_of(&(parent, id))?.is_some();
+                Ok(NativeResult::ok(
+                    context.gas_used(),
+                    smallvec![Value::bool(exists)],
+                ))
+            }),
+        ),
+        (
+            "dynamic_field",
+            "has_field_with_type",
+            Arc::new(|context, ty_args, mut args| {
+                let id = pop_arg!(args, AccountAddress);
+                let parent = pop_arg!(args, AccountAddress);
+                let ty = ty_args.first().ok_or_else(invariant)?;
+                let type_tag = context.type_to_type_tag(ty)?;
+                let fields = context.extensions().get::<FieldRuntime>();
+                let exists = fields.type_of(&(parent, id))? == Some(&type_tag);
+                Ok(NativeResult::ok(
+                    context.gas_used(),
+                    smallvec![Value::bool(exists)],
+                ))
+            }),
+        ),
+    ];
+    make_table_from_iter(system_addr, natives)
+}
+
+/// `0x2::event::emit`, recording into the session's `EventRuntime`
+pub fn event_natives(system_addr: AccountAddress) -> NativeFunctionTable {
+    let natives: Vec<(&str, &str, NativeFunction)> = vec![(
+        "event",
+        "emit",
+        Arc::new(|context, ty_args, mut args| {
+            let event = args.pop_back().ok_or_else(invariant)?;
+            let (type_tag, layout) = type_info(context, &ty_args)?;
+            let bytes = event.simple_serialize(&layout).ok_or_else(invariant)?;
+            let cost = context.gas_used();
+            context
+                .extensions_mut()
+                .get_mut::<EventRuntime>()
+                .events
+                .push((type_tag, bytes));
+            Ok(NativeResult::ok(cost, smallvec![]))
+        }),
+    )];
+    make_table_from_iter(system_addr, natives)
+}
+
+/// `0x2::random` natives, drawing from the session's `RandomRuntime`
+pub fn random_natives(system_addr: AccountAddress) -> NativeFunctionTable {
+    let natives: Vec<(&str, &str, NativeFunction)> = vec![
+        (
+            "random",
+            "bytes",
+            Arc::new(|context, _ty_args, mut args| {
+                let len = pop_arg!(args, u64);
+                let cost = context.gas_used();
+                if len > MAX_RANDOM_BYTES {
+                    return Ok(NativeResult::err(cost, E_TOO_MANY_BYTES));
+                }
+                let random = context.extensions_mut().get_mut::<RandomRuntime>();
+                if random.seed.is_none() {
+                    return Ok(NativeResult::err(cost, E_RANDOMNESS_UNAVAILABLE));
+                }
+                let bytes = random.bytes(len as usize);
+                Ok(NativeResult::ok(cost, smallvec![Value::vector_u8(bytes)]))
+            }),
+        ),
+        (
+            "random",
+            "u128_in_range",
+            Arc::new(|context, _ty_args, mut args| {
+                let max = pop_arg!(args, u128);
+                let min = pop_arg!(args, u128);
+                let cost = context.gas_used();
+                if min > max {
+                    return Ok(NativeResult::err(cost, E_INVALID_RANGE));
+                }
+                let random = context.extensions_mut().get_mut::<RandomRuntime>();
+                if random.seed.is_none() {
+                    return Ok(NativeResult::err(cost, E_RANDOMNESS_UNAVAILABLE));
+                }
+                let value = random.u128_in_range(min, max);
+                Ok(NativeResult::ok(cost, smallvec![Value::u128(value)]))
+            }),
+        ),
+    ];
+    make_table_from_iter(system_addr, natives)
+}
+
+/// `borrow_field` and `borrow_field_mut`: a reference to the field cached by
+/// the `FieldRuntime`, so writes through it are picked up at the end of the
+/// session
+fn borrow_field(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    let id = pop_arg!(args, AccountAddress);
+    let parent = pop_arg!(args, StructRef)
+        .borrow_field(0)?
+        .value_as::<Reference>()?
+        .read_ref()?
+        .value_as::<AccountAddress>()?;
+    let (type_tag, layout) = type_info(context, &ty_args)?;
+    let cost = context.gas_used();
+    let fields = context.extensions_mut().get_mut::<FieldRuntime>();
+    Ok(match fields.load((parent, id), type_tag, layout)? {
+        Ok(value) => NativeResult::ok(cost, smallvec![value.borrow_global()?]),
+        Err(code) => NativeResult::err(cost, code),
+    })
+}
+
+/// Type tag and layout of the single type argument of a native
+fn type_info(
+    context: &NativeContext,
+    ty_args: &[Type],
+) -> PartialVMResult<(TypeTag, MoveTypeLayout)> {
+    let ty = ty_args.first().ok_or_else(invariant)?;
+    let type_tag = context.type_to_type_tag(ty)?;
+    let layout = context.type_to_type_layout(ty)?.ok_or_else(invariant)?;
+    Ok((type_tag, layout))
+}
+
+fn invariant() -> PartialVMError {
+    PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
+}
+
+/// Serialize the object left in `args` and record its new owner. The id is the
+/// leading `UID` field of the object, and aborts unless it is one the
+/// transaction received by value or created.
+fn record_object(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+    owner: AccountAddress,
+    ownership: Ownership,
+) -> PartialVMResult<NativeResult> {
+    let ty = ty_args.first().ok_or_else(invariant)?;
+    let value = args.pop_back().ok_or_else(invariant)?;
+
+    let type_tag = context.type_to_type_tag(ty)?;
+    let layout = context.type_to_type_layout(ty)?.ok_or_else(invariant)?;
+    let contents = value.simple_serialize(&layout).ok_or_else(invariant)?;
+    let id = contents
+        .get(..AccountAddress::LENGTH)
+        .and_then(|bytes| AccountAddress::from_bytes(bytes).ok())
+        .ok_or_else(invariant)?;
+
+    let cost = context.gas_used();
+    let objects = context.extensions_mut().get_mut::<ObjectRuntime>();
+    if !objects.available.remove(&id) {
+        return Ok(NativeResult::err(cost, E_OBJECT_ID_UNAVAILABLE));
+    }
+    objects.changes.push(Object {
+        id,
+        owner,
+        ownership,
+        type_tag: type_tag.to_string(),
+        contents,
+    });
+    Ok(NativeResult::ok(cost, smallvec![]))
+}
@@ -0,0 +1,95 @@
+use crate::object::Object;
+use anyhow::{Context, Result};
+use kanari_types::address::Address as KanariAddress;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::StructTag;
+use serde::{Deserialize, Serialize};
+
+const MODULE_NAME: &str = "nft";
+
+/// BCS layout matches `0x2::nft::Attribute`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attribute {
+    pub key: String,
+    pub value: String,
+}
+
+/// BCS layout matches `0x2::nft::Nft`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Nft {
+    pub id: AccountAddress,
+    pub collection: AccountAddress,
+    /// Position in the collection's mint order, starting at 1
+    pub number: u64,
+    pub name: String,
+    pub description: String,
+    pub url: String,
+    pub attributes: Vec<Attribute>,
+}
+
+/// BCS layout matches `0x2::nft::Collection`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Collection {
+    pub id: AccountAddress,
+    pub creator: AccountAddress,
+    pub name: String,
+    pub description: String,
+    pub url: String,
+    /// Most NFTs the collection can ever mint; 0 for no cap
+    pub max_supply: u64,
+    /// NFTs minted so far, burned ones included
+    pub minted: u64,
+    /// NFTs minted and not burned
+    pub supply: u64,
+    /// Share of each sale price owed to `royalty_recipient`, in basis points
+    pub royalty_bps: u16,
+    pub royalty_recipient: AccountAddress,
+}
+
+impl Nft {
+    pub const STRUCT_NAME: &'static str = "Nft";
+
+    pub fn struct_tag() -> StructTag {
+        nft_struct_tag(Self::STRUCT_NAME)
+    }
+
+    /// Decode `object` if it is an NFT
+    pub fn from_object(object: &Object) -> Result<Option<Self>> {
+        from_object(object, &Self::struct_tag())
+    }
+}
+
+impl Collection {
+    pub const STRUCT_NAME: &'static str = "Collection";
+
+    pub fn struct_tag() -> StructTag {
+        nft_struct_tag(Self::STRUCT_NAME)
+    }
+
+    /// Decode `object` if it is a collection
+    pub fn from_object(object: &Object) -> Result<Option<Self>> {
+        from_object(object, &Self::struct_tag())
+    }
+}
+
+fn nft_struct_tag(name: &str) -> StructTag {
+    StructTag {
+        address: AccountAddress::from_hex_literal(KanariAddress::KANARI_SYSTEM_ADDRESS).unwrap(),
+        module: Identifier::new(MODULE_NAME).unwrap(),
+        name: Identifier::new(name).unwrap(),
+        type_params: vec![],
+    }
+}
+
+fn from_object<T: for<'de> Deserialize<'de>>(
+    object: &Object,
+    tag: &StructTag,
+) -> Result<Option<T>> {
+    if object.type_tag != tag.to_string() {
+        return Ok(None);
+    }
+    bcs::from_bytes(&object.contents)
+        .map(Some)
+        .with_context(|| format!("Failed to decode {} {}", tag.name, object.id))
+}
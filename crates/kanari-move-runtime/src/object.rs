@@ -0,0 +1,131 @@
+use kanari_types::address::Address as KanariAddress;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::{StructTag, TypeTag};
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+
+/// Derive the id of the `ids_created`-th object created by the transaction
+/// hashed `tx_hash`, as `0x2::tx_context::derive_id` does
+pub fn derive_id(tx_hash: &[u8], ids_created: u64) -> AccountAddress {
+    let mut hasher = Sha3_256::new();
+    hasher.update(tx_hash);
+    hasher.update(ids_created.to_le_bytes());
+    AccountAddress::new(hasher.finalize().into())
+}
+
+/// Id of the dynamic field named `key` (of type `key_type`, BCS-encoded in
+/// `key_bcs`) of the object `parent`, as `0x2::dynamic_field` derives it
+pub fn field_id(parent: &AccountAddress, key_type: &TypeTag, key_bcs: &[u8]) -> AccountAddress {
+    let mut hasher = Sha3_256::new();
+    hasher.update(parent.as_ref());
+    hasher.update(key_type.to_canonical_string(true).as_bytes());
+    hasher.update(key_bcs);
+    AccountAddress::new(hasher.finalize().into())
+}
+
+/// BCS layout matches `0x2::tx_context::TxContext`. Passed to entry functions
+/// whose last parameter is `&mut TxContext`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxContext {
+    pub sender: AccountAddress,
+    pub tx_hash: Vec<u8>,
+    pub epoch: u64,
+    /// Epoch start times are not tracked yet, so this is always 0
+    pub epoch_timestamp_ms: u64,
+    pub ids_created: u64,
+}
+
+impl TxContext {
+    pub fn new(sender: AccountAddress, tx_hash: Vec<u8>, epoch: u64) -> Self {
+        Self {
+            sender,
+            tx_hash,
+            epoch,
+            epoch_timestamp_ms: 0,
+            ids_created: 0,
+        }
+    }
+
+    pub fn struct_tag() -> StructTag {
+        StructTag {
+            address: AccountAddress::from_hex_literal(KanariAddress::KANARI_SYSTEM_ADDRESS)
+                .unwrap(),
+            module: Identifier::new("tx_context").unwrap(),
+            name: Identifier::new("TxContext").unwrap(),
+            type_params: vec![],
+        }
+    }
+}
+
+/// Objects passed to an entry function, as its leading parameters, along
+/// with the `TxContext` of the call
+#[derive(Debug, Clone)]
+pub struct CallInputs {
+    pub objects: Vec<AccountAddress>,
+    pub tx_context: TxContext,
+    /// Seed `0x2::random` draws from, derived from the beacon of the block
+    /// after the one that included the call; without one the natives abort
+    pub random_seed: Option<Vec<u8>>,
+}
+
+impl CallInputs {
+    pub fn new(tx_context: TxContext) -> Self {
+        Self {
+            objects: Vec::new(),
+            tx_context,
+            random_seed: None,
+        }
+    }
+
+    pub fn with_objects(mut self, objects: Vec<AccountAddress>) -> Self {
+        self.objects = objects;
+        self
+    }
+
+    pub fn with_random_seed(mut self, random_seed: Option<Vec<u8>>) -> Self {
+        self.random_seed = random_seed;
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Ownership {
+    /// Only the owning address can pass the object to a transaction
+    Address,
+    /// Any transaction can pass the object by reference
+    Shared,
+    /// Frozen: any transaction can pass the object by immutable reference
+    Immutable,
+}
+
+/// BCS layout matches `0x2::object::Object`. Kept in Move storage under its id.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Object {
+    pub id: AccountAddress,
+    /// Owning address; `0x0` for shared and immutable objects
+    pub owner: AccountAddress,
+    pub ownership: Ownership,
+    /// Type of the object, e.g. `0x2::coin::Coin<0x2::kanari::KANARI>`
+    pub type_tag: String,
+    /// BCS-encoded object value
+    pub contents: Vec<u8>,
+}
+
+impl Object {
+    /// Owning address of an address-owned object
+    pub fn owner(&self) -> Option<AccountAddress> {
+        (self.ownership == Ownership::Address).then_some(self.owner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_derive_id() {
+        assert_ne!(derive_id(b"tx", 0), derive_id(b"tx", 1));
+        assert_ne!(derive_id(b"tx", 0), derive_id(b"other", 0));
+    }
+}
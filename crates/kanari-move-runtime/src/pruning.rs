@@ -0,0 +1,160 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Most historical state versions a node keeps in memory, whatever the retention
+/// mode; the oldest are dropped first. Covers the finality depth, so a reorg always
+/// finds the state at its common ancestor.
+pub const DEFAULT_STATE_HISTORY: usize = 128;
+
+/// How much block and state history a node keeps. State versions are further
+/// capped by the engine's state history limit (see `DEFAULT_STATE_HISTORY`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum RetentionMode {
+    /// Keep every block and every historical state version within the history limit
+    #[default]
+    Archive,
+    /// Keep only the most recent `n` blocks and their state versions.
+    /// Older blocks are removed entirely (header and body).
+    KeepLast(u64),
+    /// Keep all block headers, but only the most recent `keep_last` block bodies.
+    /// Older historical state is kept only at heights that are multiples of `interval`.
+    Checkpoints { interval: u64, keep_last: u64 },
+}
+
+impl RetentionMode {
+    /// Lowest height whose body and state are always retained, given the current tip.
+    /// Returns `None` when nothing may be pruned.
+    pub fn cutoff(&self, tip: u64) -> Option<u64> {
+        let keep = match self {
+            RetentionMode::Archive => return None,
+            RetentionMode::KeepLast(n) => *n,
+            RetentionMode::Checkpoints { keep_last, .. } => *keep_last,
+        };
+        // Always keep at least the tip
+        Some(tip.saturating_sub(keep.max(1) - 1))
+    }
+
+    /// Whether the state version at `height` should be kept
+    pub fn keeps_state(&self, height: u64, tip: u64) -> bool {
+        match (self, self.cutoff(tip)) {
+            (_, None) => true,
+            (_, Some(cutoff)) if height >= cutoff => true,
+            (RetentionMode::Checkpoints { interval, .. }, _) => {
+                *interval > 0 && height.is_multiple_of(*interval)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for RetentionMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RetentionMode::Archive => write!(f, "archive"),
+            RetentionMode::KeepLast(n) => write!(f, "keep-last:{}", n),
+            RetentionMode::Checkpoints {
+                interval,
+                keep_last,
+            } => write!(f, "checkpoints:{}:{}", interval, keep_last),
+        }
+    }
+}
+
+/// Parses `archive`, `keep-last:<n>` or `checkpoints:<interval>:<keep_last>`
+impl FromStr for RetentionMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let parts: Vec<&str> = s.split(':').collect();
+        match parts.as_slice() {
+            ["archive"] => Ok(RetentionMode::Archive),
+            ["keep-last", n] => {
+                let n: u64 = n.parse()?;
+                if n == 0 {
+                    anyhow::bail!("keep-last must retain at least one block");
+                }
+                Ok(RetentionMode::KeepLast(n))
+            }
+            ["checkpoints", interval, keep_last] => {
+                let interval: u64 = interval.parse()?;
+                if interval == 0 {
+                    anyhow::bail!("checkpoint interval must be greater than zero");
+                }
+                Ok(RetentionMode::Checkpoints {
+                    interval,
+                    keep_last: keep_last.parse()?,
+                })
+            }
+            _ => anyhow::bail!(
+                "Invalid retention mode '{}'. Expected archive, keep-last:<n> or checkpoints:<interval>:<keep_last>",
+                s
+            ),
+        }
+    }
+}
+
+/// Result of a pruning pass
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PruneStats {
+    pub blocks_removed: usize,
+    pub bodies_pruned: usize,
+    pub states_removed: usize,
+}
+
+impl PruneStats {
+    pub fn is_empty(&self) -> bool {
+        self.blocks_removed == 0 && self.bodies_pruned == 0 && self.states_removed == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_retention_mode() {
+        assert_eq!(
+            "archive".parse::<RetentionMode>().unwrap(),
+            RetentionMode::Archive
+        );
+        assert_eq!(
+            "keep-last:100".parse::<RetentionMode>().unwrap(),
+            RetentionMode::KeepLast(100)
+        );
+        let mode: RetentionMode = "checkpoints:10:50".parse().unwrap();
+        assert_eq!(
+            mode,
+            RetentionMode::Checkpoints {
+                interval: 10,
+                keep_last: 50
+            }
+        );
+        assert_eq!(mode.to_string().parse::<RetentionMode>().unwrap(), mode);
+
+        assert!("keep-last:0".parse::<RetentionMode>().is_err());
+        assert!("checkpoints:0:5".parse::<RetentionMode>().is_err());
+        assert!("forever".parse::<RetentionMode>().is_err());
+    }
+
+    #[test]
+    fn test_retention_policy() {
+        assert_eq!(RetentionMode::Archive.cutoff(100), None);
+        assert!(RetentionMode::Archive.keeps_state(0, 100));
+
+        let keep_last = RetentionMode::KeepLast(10);
+        assert_eq!(keep_last.cutoff(100), Some(91));
+        assert_eq!(keep_last.cutoff(3), Some(0));
+        assert!(!keep_last.keeps_state(90, 100));
+        assert!(keep_last.keeps_state(91, 100));
+
+        let checkpoints = RetentionMode::Checkpoints {
+            interval: 20,
+            keep_last: 5,
+        };
+        assert!(checkpoints.keeps_state(40, 100));
+        assert!(!checkpoints.keeps_state(41, 100));
+        assert!(checkpoints.keeps_state(97, 100));
+    }
+}
@@ -0,0 +1,232 @@
+use crate::blockchain::{BlockHeader, SignedTransaction};
+use crate::consensus::{BlockSigner, ValidatorSet};
+use crate::move_runtime::MoveRuntime;
+use crate::move_storage::MoveStorage;
+use anyhow::{Context, Result};
+use kanari_crypto::hash_data_blake3;
+use kanari_types::address::Address as KanariAddress;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::{ModuleId, StructTag};
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+
+/// Domain separator of the message the producer signs for a beacon
+const BEACON_DOMAIN: &[u8] = b"kanari::random_beacon";
+
+/// Randomness beacon committed into a block header.
+///
+/// The producer signs the parent's beacon and the block height, and the beacon
+/// is the BLAKE3 hash of that signature. With a deterministic signature scheme
+/// (Ed25519, ECDSA with RFC 6979) the producer has a single valid beacon per
+/// height. It depends only on the parent, so the producer knows it before
+/// choosing the block's transactions: calls that draw randomness are therefore
+/// seeded by the beacon of the block after the one that included them (see
+/// [`DeferredCalls`]). Blocks produced without a signer fall back to a public
+/// hash chain over the same message, which seeds nothing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RandomBeacon {
+    pub beacon: Vec<u8>,
+    pub proof: Vec<u8>,
+}
+
+impl RandomBeacon {
+    /// Beacon of the block following `parent`, signed by `signer` if set
+    pub fn produce(parent: &BlockHeader, signer: Option<&BlockSigner>) -> Result<Self> {
+        let message = Self::message(parent);
+        Ok(match signer {
+            Some(signer) => {
+                let proof = signer.sign(&message)?;
+                Self {
+                    beacon: hash_data_blake3(&proof),
+                    proof,
+                }
+            }
+            None => Self {
+                beacon: hash_data_blake3(&message),
+                proof: Vec::new(),
+            },
+        })
+    }
+
+    /// Check the beacon of `header` against its `parent`. Under proof-of-authority
+    /// (`validators` set) the beacon must come with a proof signed by the
+    /// producer's registered key; otherwise it must follow the hash chain.
+    pub fn verify(
+        parent: &BlockHeader,
+        header: &BlockHeader,
+        validators: Option<&ValidatorSet>,
+    ) -> Result<()> {
+        let message = Self::message(parent);
+        let expected = if let Some(validators) = validators {
+            if header.random_proof.is_empty() {
+                anyhow::bail!("Block {} has no randomness proof", header.height);
+            }
+            let producer = header.producer.as_deref().ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Block {} has a randomness proof but no producer",
+                    header.height
+                )
+            })?;
+            let validator = validators
+                .get(producer)
+                .ok_or_else(|| anyhow::anyhow!("Unknown block producer {}", producer))?;
+            let valid = validator
+                .verify(&message, &header.random_proof)
+                .map_err(|e| anyhow::anyhow!("Randomness proof verification failed: {}", e))?;
+            if !valid {
+                anyhow::bail!("Invalid randomness proof from {}", producer);
+            }
+            hash_data_blake3(&header.random_proof)
+        } else {
+            if !header.random_proof.is_empty() {
+                anyhow::bail!(
+                    "Block {} has a randomness proof but no validator set to check it",
+                    header.height
+                );
+            }
+            hash_data_blake3(&message)
+        };
+        if header.random_beacon != expected {
+            anyhow::bail!("Random beacon mismatch for block {}", header.height);
+        }
+        Ok(())
+    }
+
+    /// Message signed for the beacon of the block following `parent`
+    fn message(parent: &BlockHeader) -> Vec<u8> {
+        let mut message = BEACON_DOMAIN.to_vec();
+        message.extend_from_slice(&parent.random_beacon);
+        message.extend_from_slice(&(parent.height + 1).to_le_bytes());
+        message
+    }
+}
+
+/// Seed of `0x2::random` for the transaction hashed `tx_hash`, deferred to a
+/// block with beacon `beacon`: SHA3-256 of both, so transactions draw
+/// independent values
+pub fn tx_seed(beacon: &[u8], tx_hash: &[u8]) -> Vec<u8> {
+    let mut hasher = Sha3_256::new();
+    hasher.update(beacon);
+    hasher.update(tx_hash);
+    hasher.finalize().to_vec()
+}
+
+/// `0x2::random`, which calls that draw randomness depend on
+pub fn random_module() -> ModuleId {
+    ModuleId::new(
+        DeferredCalls::address(),
+        Identifier::new(DeferredCalls::MODULE_NAME).unwrap(),
+    )
+}
+
+/// The `0x2::random::DeferredCalls` resource: calls included in the latest
+/// block that draw randomness, as BCS-encoded signed transactions. They run at
+/// the start of the next block, once its beacon is known.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeferredCalls {
+    pub transactions: Vec<Vec<u8>>,
+}
+
+impl DeferredCalls {
+    pub const MODULE_NAME: &'static str = "random";
+    pub const STRUCT_NAME: &'static str = "DeferredCalls";
+
+    pub fn new(transactions: &[SignedTransaction]) -> Result<Self> {
+        let transactions = transactions
+            .iter()
+            .map(bcs::to_bytes)
+            .collect::<Result<_, _>>()?;
+        Ok(Self { transactions })
+    }
+
+    /// Account holding the resource, `0x2`
+    pub fn address() -> AccountAddress {
+        AccountAddress::from_hex_literal(KanariAddress::KANARI_SYSTEM_ADDRESS).unwrap()
+    }
+
+    pub fn struct_tag() -> StructTag {
+        StructTag {
+            address: Self::address(),
+            module: Identifier::new(Self::MODULE_NAME).unwrap(),
+            name: Identifier::new(Self::STRUCT_NAME).unwrap(),
+            type_params: vec![],
+        }
+    }
+
+    /// Read the resource from Move storage; empty until a call is first deferred
+    pub fn load(storage: &MoveStorage) -> Result<Self> {
+        match storage.resource(&Self::address(), &Self::struct_tag()) {
+            Some(bytes) => bcs::from_bytes(bytes).context("Failed to decode deferred calls"),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Write the resource into the runtime's Move storage
+    pub fn save(&self, runtime: &mut MoveRuntime) -> Result<()> {
+        runtime.set_resource(Self::address(), Self::struct_tag(), bcs::to_bytes(self)?);
+        Ok(())
+    }
+
+    /// The deferred transactions, in the order they were included
+    pub fn decode(&self) -> Result<Vec<SignedTransaction>> {
+        self.transactions
+            .iter()
+            .map(|bytes| bcs::from_bytes(bytes).context("Failed to decode deferred call"))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kanari_crypto::keys::{CurveType, generate_keypair};
+
+    #[test]
+    fn test_beacon_chain() {
+        let keypair = generate_keypair(CurveType::Ed25519).unwrap();
+        let signer = BlockSigner::new(&keypair.private_key, CurveType::Ed25519).unwrap();
+        let parent = BlockHeader::new(4, vec![], vec![], 0);
+        let validators = ValidatorSet::new(vec![signer.validator()]).unwrap();
+        let validators = Some(&validators);
+
+        let signed = RandomBeacon::produce(&parent, Some(&signer)).unwrap();
+        // Deterministic signatures leave the producer a single beacon
+        assert_eq!(
+            signed,
+            RandomBeacon::produce(&parent, Some(&signer)).unwrap()
+        );
+
+        let mut header = BlockHeader::new(5, vec![], vec![], 0);
+        header.producer = Some(signer.address.clone());
+        header.random_beacon = signed.beacon.clone();
+        header.random_proof = signed.proof.clone();
+        RandomBeacon::verify(&parent, &header, validators).unwrap();
+        assert!(RandomBeacon::verify(&parent, &header, None).is_err());
+
+        // A beacon the proof doesn't hash to, or a proof by another key
+        header.random_beacon = vec![0; 32];
+        assert!(RandomBeacon::verify(&parent, &header, validators).is_err());
+        let other = BlockSigner::new(
+            &generate_keypair(CurveType::Ed25519).unwrap().private_key,
+            CurveType::Ed25519,
+        )
+        .unwrap();
+        let impostor = RandomBeacon::produce(&parent, Some(&other)).unwrap();
+        header.random_beacon = impostor.beacon;
+        header.random_proof = impostor.proof;
+        assert!(RandomBeacon::verify(&parent, &header, validators).is_err());
+
+        // Unsigned blocks use the hash chain, unless a proof is required
+        let unsigned = RandomBeacon::produce(&parent, None).unwrap();
+        header.random_beacon = unsigned.beacon;
+        header.random_proof = unsigned.proof;
+        RandomBeacon::verify(&parent, &header, None).unwrap();
+        assert!(RandomBeacon::verify(&parent, &header, validators).is_err());
+
+        assert_ne!(
+            tx_seed(&header.random_beacon, b"a"),
+            tx_seed(&header.random_beacon, b"b")
+        );
+    }
+}
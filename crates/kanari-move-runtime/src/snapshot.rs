@@ -0,0 +1,197 @@
+use crate::blockchain::BlockHeader;
+use crate::move_storage::{FieldValue, MoveStorage};
+use crate::object::Object;
+use crate::state::{Account, StateManager};
+use anyhow::{Context, Result};
+use kanari_crypto::hash_data_blake3;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::language_storage::{ModuleId, StructTag};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Current snapshot format version
+pub const SNAPSHOT_VERSION: u32 = 5;
+
+/// Upper bound for a decompressed snapshot (1 GiB) to guard against decompression bombs
+const MAX_SNAPSHOT_SIZE: usize = 1 << 30;
+
+/// Combined state root over account state and Move storage.
+/// This is the value committed to in `BlockHeader::state_root`.
+pub fn compute_state_root(state: &StateManager, storage: &MoveStorage) -> Vec<u8> {
+    let mut data = state.compute_state_root();
+    data.extend_from_slice(&storage.compute_root());
+    hash_data_blake3(&data)
+}
+
+/// Full copy of the chain state at a given block height.
+///
+/// Serialized with BCS and compressed with zstd so a node can be bootstrapped
+/// (or backed up) from a single file instead of replaying every block.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateSnapshot {
+    pub version: u32,
+    pub header: BlockHeader,
+    pub accounts: Vec<Account>,
+    pub total_supply: u64,
+    pub modules: Vec<(ModuleId, Vec<u8>)>,
+    pub resources: Vec<(AccountAddress, StructTag, Vec<u8>)>,
+    /// Dynamic fields as (parent, field id, value)
+    pub fields: Vec<(AccountAddress, AccountAddress, FieldValue)>,
+    pub objects: Vec<Object>,
+}
+
+impl StateSnapshot {
+    /// Capture the given state as of `header`
+    pub fn capture(header: BlockHeader, state: &StateManager, storage: &MoveStorage) -> Self {
+        Self {
+            version: SNAPSHOT_VERSION,
+            header,
+            accounts: state.accounts.values().cloned().collect(),
+            total_supply: state.total_supply,
+            modules: storage
+                .modules()
+                .map(|(id, blob)| (id.clone(), blob.clone()))
+                .collect(),
+            resources: storage
+                .resources()
+                .map(|(addr, tag, blob)| (*addr, tag.clone(), blob.clone()))
+                .collect(),
+            fields: storage
+                .fields()
+                .map(|((parent, id), value)| (*parent, *id, value.clone()))
+                .collect(),
+            objects: storage.objects().cloned().collect(),
+        }
+    }
+
+    pub fn height(&self) -> u64 {
+        self.header.height
+    }
+
+    /// Rebuild the account state held in this snapshot
+    pub fn to_state(&self) -> StateManager {
+        StateManager::from_accounts(self.accounts.clone(), self.total_supply)
+    }
+
+    /// Rebuild the Move storage held in this snapshot
+    pub fn to_move_storage(&self) -> MoveStorage {
+        let mut storage = MoveStorage::new();
+        for (id, blob) in &self.modules {
+            storage.publish_or_overwrite_module(id.clone(), blob.clone());
+        }
+        for (addr, tag, blob) in &self.resources {
+            storage.set_resource(*addr, tag.clone(), blob.clone());
+        }
+        for (parent, id, value) in &self.fields {
+            storage.set_field(*parent, *id, value.clone());
+        }
+        for object in &self.objects {
+            storage.set_object(object.clone());
+        }
+        storage
+    }
+
+    /// Recompute the state root and check it against the header
+    pub fn verify(&self) -> Result<()> {
+        if self.version != SNAPSHOT_VERSION {
+            anyhow::bail!(
+                "Unsupported snapshot version {} (expected {})",
+                self.version,
+                SNAPSHOT_VERSION
+            );
+        }
+
+        let root = compute_state_root(&self.to_state(), &self.to_move_storage());
+        if root != self.header.state_root {
+            anyhow::bail!(
+                "Snapshot state root mismatch at height {}: header {}, computed {}",
+                self.header.height,
+                hex::encode(&self.header.state_root),
+                hex::encode(&root)
+            );
+        }
+        Ok(())
+    }
+
+    /// Encode as compressed BCS
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let encoded = bcs::to_bytes(self).context("Failed to serialize snapshot")?;
+        kanari_crypto::compress_data(&encoded).context("Failed to compress snapshot")
+    }
+
+    /// Decode from compressed BCS. Does not verify the state root.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let encoded = kanari_crypto::decompress_data_with_limit(bytes, MAX_SNAPSHOT_SIZE)
+            .context("Failed to decompress snapshot")?;
+        bcs::from_bytes(&encoded).context("Failed to deserialize snapshot")
+    }
+
+    pub fn write_to_file(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        std::fs::write(path, self.to_bytes()?)
+            .with_context(|| format!("Failed to write snapshot to {}", path.display()))
+    }
+
+    pub fn read_from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path)
+            .with_context(|| format!("Failed to read snapshot from {}", path.display()))?;
+        Self::from_bytes(&bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_core_types::identifier::Identifier;
+    use move_core_types::language_storage::TypeTag;
+
+    fn sample_snapshot() -> StateSnapshot {
+        let state = StateManager::new();
+        let mut storage = MoveStorage::new();
+        storage.publish_or_overwrite_module(
+            ModuleId::new(AccountAddress::TWO, Identifier::new("m").unwrap()),
+            vec![1, 2, 3],
+        );
+        storage.set_field(
+            AccountAddress::ONE,
+            AccountAddress::TWO,
+            FieldValue {
+                type_tag: TypeTag::U64,
+                bytes: vec![7; 8],
+            },
+        );
+        storage.set_object(Object {
+            id: AccountAddress::ONE,
+            owner: AccountAddress::TWO,
+            ownership: crate::object::Ownership::Address,
+            type_tag: "0x42::pet::Pet".to_string(),
+            contents: vec![1],
+        });
+
+        let root = compute_state_root(&state, &storage);
+        let header = BlockHeader::new(5, vec![0u8; 32], root, 0);
+        StateSnapshot::capture(header, &state, &storage)
+    }
+
+    #[test]
+    fn test_snapshot_roundtrip_and_verify() {
+        let snapshot = sample_snapshot();
+        snapshot.verify().unwrap();
+
+        let decoded = StateSnapshot::from_bytes(&snapshot.to_bytes().unwrap()).unwrap();
+        assert_eq!(decoded.height(), 5);
+        assert_eq!(decoded.modules.len(), 1);
+        assert_eq!(decoded.fields.len(), 1);
+        assert_eq!(decoded.objects.len(), 1);
+        assert_eq!(decoded.accounts.len(), snapshot.accounts.len());
+        decoded.verify().unwrap();
+    }
+
+    #[test]
+    fn test_tampered_snapshot_rejected() {
+        let mut snapshot = sample_snapshot();
+        snapshot.accounts[0].balance += 1;
+        assert!(snapshot.verify().is_err());
+    }
+}
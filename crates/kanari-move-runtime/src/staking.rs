@@ -0,0 +1,380 @@
+use crate::changeset::ChangeSet;
+use crate::move_runtime::MoveRuntime;
+use crate::move_storage::MoveStorage;
+use anyhow::{Context, Result};
+use kanari_types::address::Address as KanariAddress;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::StructTag;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Self-stake a validator needs to be elected, in Mist (1,000 KANARI)
+pub const MIN_VALIDATOR_STAKE: u64 = 1_000_000_000_000;
+
+/// Maximum number of validators elected per epoch
+pub const MAX_VALIDATORS: usize = 100;
+
+/// Epochs between unstaking and withdrawal
+pub const UNBONDING_EPOCHS: u64 = 2;
+
+/// Share of a validator's rewards it keeps, in basis points
+pub const VALIDATOR_COMMISSION_BPS: u64 = 1_000;
+
+/// Share of each block's tips pooled for stakers once validators are elected,
+/// in basis points; the block producer keeps the rest
+pub const STAKER_TIP_SHARE_BPS: u64 = 5_000;
+
+/// Staking operation carried by `Transaction::Staking`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StakingAction {
+    /// Stake `amount` to `validator`; staking to oneself registers as a validator
+    Stake { validator: String, amount: u64 },
+    /// Start unbonding `amount` staked to `validator`
+    Unstake { validator: String, amount: u64 },
+    /// Withdraw all stake whose unbonding delay has passed
+    Withdraw,
+}
+
+/// KANARI staked by `staker` to `validator`, earning rewards from `activation_epoch` on
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Stake {
+    pub staker: AccountAddress,
+    pub validator: AccountAddress,
+    pub amount: u64,
+    pub activation_epoch: u64,
+}
+
+/// Unstaked KANARI waiting for its unbonding delay
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Unbonding {
+    pub staker: AccountAddress,
+    pub amount: u64,
+    pub withdrawable_epoch: u64,
+}
+
+/// Validator elected for the current epoch and its stake at election
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActiveValidator {
+    pub validator: AccountAddress,
+    pub stake: u64,
+}
+
+/// The `0x2::staking::StakingPool` value kept in Move storage.
+/// Staked KANARI is held by the `0x2` account until withdrawn.
+///
+/// The elected `active_validators` earn staking rewards but don't produce
+/// blocks: block producers are the proof-of-authority `ValidatorSet` the node
+/// is configured with, and deriving that set from elections is out of scope.
+/// Producers therefore keep part of every tip (see `STAKER_TIP_SHARE_BPS`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StakingPool {
+    pub stakes: Vec<Stake>,
+    pub unbonding: Vec<Unbonding>,
+    pub active_validators: Vec<ActiveValidator>,
+    /// Fees collected in the current epoch, paid out at its end
+    pub rewards: u64,
+}
+
+impl StakingPool {
+    pub const MODULE_NAME: &'static str = "staking";
+    pub const STRUCT_NAME: &'static str = "StakingPool";
+
+    /// Account holding the pool and the staked KANARI, `0x2`
+    pub fn address() -> AccountAddress {
+        AccountAddress::from_hex_literal(KanariAddress::KANARI_SYSTEM_ADDRESS).unwrap()
+    }
+
+    pub fn struct_tag() -> StructTag {
+        StructTag {
+            address: Self::address(),
+            module: Identifier::new(Self::MODULE_NAME).unwrap(),
+            name: Identifier::new(Self::STRUCT_NAME).unwrap(),
+            type_params: vec![],
+        }
+    }
+
+    /// Read the pool from Move storage
+    pub fn load(storage: &MoveStorage) -> Result<Self> {
+        let bytes = storage
+            .resource(&Self::address(), &Self::struct_tag())
+            .context("Staking pool is missing from Move storage")?;
+        bcs::from_bytes(bytes).context("Failed to decode staking pool")
+    }
+
+    /// Write the pool into the runtime's Move storage
+    pub fn save(&self, runtime: &mut MoveRuntime) -> Result<()> {
+        runtime.set_resource(Self::address(), Self::struct_tag(), bcs::to_bytes(self)?);
+        Ok(())
+    }
+
+    /// Emit the pool as a resource write of `changeset`
+    pub fn write_to(&self, changeset: &mut ChangeSet) -> Result<()> {
+        changeset.write_resource(Self::address(), Self::struct_tag(), bcs::to_bytes(self)?);
+        Ok(())
+    }
+
+    /// Stake of `validator` that earns rewards in `epoch`
+    pub fn active_stake(&self, validator: &AccountAddress, epoch: u64) -> u64 {
+        self.stakes
+            .iter()
+            .filter(|s| s.validator == *validator && s.activation_epoch <= epoch)
+            .map(|s| s.amount)
+            .sum()
+    }
+
+    /// Part of `active_stake` the validator staked itself
+    pub fn self_stake(&self, validator: &AccountAddress, epoch: u64) -> u64 {
+        self.stakes
+            .iter()
+            .filter(|s| {
+                s.staker == *validator && s.validator == *validator && s.activation_epoch <= epoch
+            })
+            .map(|s| s.amount)
+            .sum()
+    }
+
+    /// Total KANARI `staker` has staked, active or not, excluding unbonding
+    pub fn staked_by(&self, staker: &AccountAddress) -> u64 {
+        self.stakes
+            .iter()
+            .filter(|s| s.staker == *staker)
+            .map(|s| s.amount)
+            .sum()
+    }
+
+    /// Whether `validator` has registered by staking to itself
+    pub fn is_candidate(&self, validator: &AccountAddress) -> bool {
+        self.stakes
+            .iter()
+            .any(|s| s.staker == *validator && s.validator == *validator)
+    }
+
+    pub fn is_active_validator(&self, validator: &AccountAddress) -> bool {
+        self.active_validators
+            .iter()
+            .any(|v| v.validator == *validator)
+    }
+
+    /// Stake `amount` from `staker` to `validator` in `epoch`; it becomes active
+    /// at the next epoch
+    pub fn stake(
+        &mut self,
+        staker: AccountAddress,
+        validator: AccountAddress,
+        amount: u64,
+        epoch: u64,
+    ) -> Result<()> {
+        if amount == 0 {
+            anyhow::bail!("Stake amount must be positive");
+        }
+        if staker != validator && !self.is_candidate(&validator) {
+            anyhow::bail!("{} is not a registered validator", validator);
+        }
+        let activation_epoch = epoch + 1;
+        match self.stakes.iter_mut().find(|s| {
+            s.staker == staker && s.validator == validator && s.activation_epoch == activation_epoch
+        }) {
+            Some(existing) => existing.amount += amount,
+            None => self.stakes.push(Stake {
+                staker,
+                validator,
+                amount,
+                activation_epoch,
+            }),
+        }
+        Ok(())
+    }
+
+    /// Move `amount` staked by `staker` to `validator` into unbonding. Stake not
+    /// yet active is taken first.
+    pub fn unstake(
+        &mut self,
+        staker: AccountAddress,
+        validator: AccountAddress,
+        amount: u64,
+        epoch: u64,
+    ) -> Result<()> {
+        if amount == 0 {
+            anyhow::bail!("Unstake amount must be positive");
+        }
+        let staked: u64 = self
+            .stakes
+            .iter()
+            .filter(|s| s.staker == staker && s.validator == validator)
+            .map(|s| s.amount)
+            .sum();
+        if staked < amount {
+            anyhow::bail!(
+                "Cannot unstake {} from {}: only {} staked",
+                amount,
+                validator,
+                staked
+            );
+        }
+
+        let mut remaining = amount;
+        let mut records: Vec<&mut Stake> = self
+            .stakes
+            .iter_mut()
+            .filter(|s| s.staker == staker && s.validator == validator)
+            .collect();
+        records.sort_by_key(|s| std::cmp::Reverse(s.activation_epoch));
+        for record in records {
+            let taken = record.amount.min(remaining);
+            record.amount -= taken;
+            remaining -= taken;
+            if remaining == 0 {
+                break;
+            }
+        }
+        self.stakes.retain(|s| s.amount > 0);
+        self.unbonding.push(Unbonding {
+            staker,
+            amount,
+            withdrawable_epoch: epoch + UNBONDING_EPOCHS,
+        });
+        Ok(())
+    }
+
+    /// Remove and return the unbonded stake `staker` may withdraw in `epoch`
+    pub fn withdraw(&mut self, staker: AccountAddress, epoch: u64) -> Result<u64> {
+        let withdrawable: u64 = self
+            .unbonding
+            .iter()
+            .filter(|u| u.staker == staker && u.withdrawable_epoch <= epoch)
+            .map(|u| u.amount)
+            .sum();
+        if withdrawable == 0 {
+            anyhow::bail!("No unbonded stake to withdraw");
+        }
+        self.unbonding
+            .retain(|u| u.staker != staker || u.withdrawable_epoch > epoch);
+        Ok(withdrawable)
+    }
+
+    /// Pool at the start of `epoch`: the rewards of the ending epoch are paid out
+    /// and the validator set is elected. Returns the pool and the payouts owed
+    /// from the `0x2` account; rounding dust stays in `rewards`.
+    pub fn begin_epoch(&self, epoch: u64) -> (Self, BTreeMap<AccountAddress, u64>) {
+        let mut pool = self.clone();
+        let mut payouts: BTreeMap<AccountAddress, u64> = BTreeMap::new();
+        let ended = epoch.saturating_sub(1);
+
+        let total_stake: u128 = pool.active_validators.iter().map(|v| v.stake as u128).sum();
+        if total_stake > 0 && pool.rewards > 0 {
+            let rewards = pool.rewards as u128;
+            let mut paid = 0u128;
+            for active in &pool.active_validators {
+                let share = rewards * active.stake as u128 / total_stake;
+                let commission = share * VALIDATOR_COMMISSION_BPS as u128 / 10_000;
+                let delegated = share - commission;
+                *payouts.entry(active.validator).or_default() += commission as u64;
+                paid += commission;
+
+                for stake in pool
+                    .stakes
+                    .iter()
+                    .filter(|s| s.validator == active.validator && s.activation_epoch <= ended)
+                {
+                    let reward = delegated * stake.amount as u128 / active.stake.max(1) as u128;
+                    *payouts.entry(stake.staker).or_default() += reward as u64;
+                    paid += reward;
+                }
+            }
+            pool.rewards -= paid as u64;
+        }
+        payouts.retain(|_, amount| *amount > 0);
+
+        let mut candidates: Vec<AccountAddress> = pool
+            .stakes
+            .iter()
+            .filter(|s| s.staker == s.validator)
+            .map(|s| s.validator)
+            .collect();
+        candidates.sort();
+        candidates.dedup();
+        let mut elected: Vec<ActiveValidator> = candidates
+            .into_iter()
+            .filter(|v| pool.self_stake(v, epoch) >= MIN_VALIDATOR_STAKE)
+            .map(|validator| ActiveValidator {
+                validator,
+                stake: pool.active_stake(&validator, epoch),
+            })
+            .collect();
+        elected.sort_by(|a, b| {
+            b.stake
+                .cmp(&a.stake)
+                .then_with(|| a.validator.cmp(&b.validator))
+        });
+        elected.truncate(MAX_VALIDATORS);
+        pool.active_validators = elected;
+
+        (pool, payouts)
+    }
+}
+
+/// Part of `tip` pooled for stakers when validators are elected
+pub fn staker_tip_share(tip: u64) -> u64 {
+    (tip as u128 * STAKER_TIP_SHARE_BPS as u128 / 10_000) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn addr(n: u8) -> AccountAddress {
+        AccountAddress::new([n; AccountAddress::LENGTH])
+    }
+
+    #[test]
+    fn test_election_and_reward_distribution() {
+        let (validator, delegator, other) = (addr(1), addr(2), addr(3));
+        let mut pool = StakingPool::default();
+
+        assert!(pool.stake(delegator, validator, 10, 0).is_err());
+        pool.stake(validator, validator, MIN_VALIDATOR_STAKE, 0)
+            .unwrap();
+        pool.stake(delegator, validator, MIN_VALIDATOR_STAKE, 0)
+            .unwrap();
+        // Below the minimum self-stake
+        pool.stake(other, other, MIN_VALIDATOR_STAKE - 1, 0)
+            .unwrap();
+
+        // Stake is not active in the epoch it was added
+        let (pool, payouts) = pool.begin_epoch(0);
+        assert!(pool.active_validators.is_empty());
+        assert!(payouts.is_empty());
+
+        let (mut pool, _) = pool.begin_epoch(1);
+        assert_eq!(
+            pool.active_validators,
+            vec![ActiveValidator {
+                validator,
+                stake: 2 * MIN_VALIDATOR_STAKE,
+            }]
+        );
+
+        pool.rewards = 1_000;
+        let (pool, payouts) = pool.begin_epoch(2);
+        // 10% commission, then split evenly between the two equal stakes
+        assert_eq!(payouts[&validator], 100 + 450);
+        assert_eq!(payouts[&delegator], 450);
+        assert_eq!(pool.rewards, 0);
+    }
+
+    #[test]
+    fn test_unbonding_delay() {
+        let staker = addr(1);
+        let mut pool = StakingPool::default();
+        pool.stake(staker, staker, 100, 0).unwrap();
+
+        assert!(pool.unstake(staker, staker, 101, 1).is_err());
+        pool.unstake(staker, staker, 40, 1).unwrap();
+        assert_eq!(pool.active_stake(&staker, 1), 60);
+
+        assert!(pool.withdraw(staker, 1 + UNBONDING_EPOCHS - 1).is_err());
+        assert_eq!(pool.withdraw(staker, 1 + UNBONDING_EPOCHS).unwrap(), 40);
+        assert!(pool.unbonding.is_empty());
+    }
+}
@@ -1,10 +1,12 @@
 use crate::changeset::{ChangeSet, Event};
+use crate::coin::{coin_key, is_kanari, kanari_coin_type};
 use anyhow::Result;
 use kanari_crypto::hash_data_blake3;
 use kanari_types::address::Address as KanariAddress;
 use move_core_types::account_address::AccountAddress;
+use move_core_types::language_storage::TypeTag;
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet};
 
 /// Account state in the blockchain
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -12,7 +14,16 @@ pub struct Account {
     pub address: AccountAddress,
     pub balance: u64,
     pub sequence_number: u64,
-    pub modules: HashSet<String>,
+    pub modules: BTreeSet<String>,
+    /// Authentication key set by `RotateAuthenticationKey`. `None` means the key
+    /// was never rotated and the account is still controlled by the key whose
+    /// public key is the address.
+    #[serde(default)]
+    pub authentication_key: Option<Vec<u8>>,
+    /// Balances of coins other than KANARI, keyed by coin type (see `coin_key`).
+    /// `balance` holds the KANARI balance.
+    #[serde(default)]
+    pub coin_balances: BTreeMap<String, u64>,
 }
 
 impl Account {
@@ -21,10 +32,36 @@ impl Account {
             address,
             balance,
             sequence_number: 0,
-            modules: HashSet::new(),
+            modules: BTreeSet::new(),
+            authentication_key: None,
+            coin_balances: BTreeMap::new(),
         }
     }
 
+    /// Balance of `coin_type`, KANARI included
+    pub fn balance_of(&self, coin_type: &TypeTag) -> u64 {
+        if is_kanari(coin_type) {
+            self.balance
+        } else {
+            self.coin_balances
+                .get(&coin_key(coin_type))
+                .copied()
+                .unwrap_or(0)
+        }
+    }
+
+    /// Every non-zero balance keyed by coin type, KANARI first
+    pub fn balances(&self) -> Vec<(String, u64)> {
+        std::iter::once((coin_key(&kanari_coin_type()), self.balance))
+            .filter(|(_, balance)| *balance > 0)
+            .chain(
+                self.coin_balances
+                    .iter()
+                    .map(|(coin, balance)| (coin.clone(), *balance)),
+            )
+            .collect()
+    }
+
     pub fn add_module(&mut self, module_name: String) {
         self.modules.insert(module_name);
     }
@@ -42,7 +79,7 @@ impl Account {
 /// This is a pure data layer that applies ChangeSet from Move VM execution
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StateManager {
-    pub accounts: HashMap<AccountAddress, Account>,
+    pub accounts: BTreeMap<AccountAddress, Account>,
     pub total_supply: u64,
     pub events: Vec<Event>,
 }
@@ -52,7 +89,7 @@ impl StateManager {
     /// Total supply: 100 million KANARI = 100,000,000,000,000,000 Mist
     /// Dev address gets entire supply according to kanari.move
     pub fn new() -> Self {
-        let mut accounts = HashMap::new();
+        let mut accounts = BTreeMap::new();
 
         // Total supply in Mist (100 million KANARI * 10^9)
         const TOTAL_SUPPLY_MIST: u64 = 100_000_000_000_000_000;
@@ -79,6 +116,18 @@ impl StateManager {
         }
     }
 
+    /// Rebuild state from a list of accounts, e.g. when importing a snapshot
+    pub fn from_accounts(accounts: Vec<Account>, total_supply: u64) -> Self {
+        Self {
+            accounts: accounts
+                .into_iter()
+                .map(|account| (account.address, account))
+                .collect(),
+            total_supply,
+            events: Vec::new(),
+        }
+    }
+
     pub fn get_or_create_account(&mut self, address: AccountAddress) -> &mut Account {
         self.accounts
             .entry(address)
@@ -135,6 +184,29 @@ impl StateManager {
                 supply_delta += change.balance_delta;
             }
 
+            // Apply non-KANARI coin deltas; these never count toward the KANARI supply
+            for (coin, delta) in &change.coin_deltas {
+                let balance = account.coin_balances.entry(coin.clone()).or_default();
+                if *delta >= 0 {
+                    *balance = balance
+                        .checked_add(*delta as u64)
+                        .ok_or_else(|| anyhow::anyhow!("Balance overflow"))?;
+                } else {
+                    let debit = delta.unsigned_abs();
+                    if *balance < debit {
+                        anyhow::bail!(
+                            "Insufficient {} balance for address {:#x}: need {} but have {}",
+                            coin,
+                            address,
+                            debit,
+                            balance
+                        );
+                    }
+                    *balance -= debit;
+                }
+            }
+            account.coin_balances.retain(|_, balance| *balance > 0);
+
             // Apply sequence number increment
             account.sequence_number += change.sequence_increment;
 
@@ -142,6 +214,10 @@ impl StateManager {
             for module_name in &change.modules_added {
                 account.add_module(module_name.clone());
             }
+
+            if let Some(key) = &change.authentication_key {
+                account.authentication_key = Some(key.clone());
+            }
         }
 
         // Update total supply if there was mint/burn (supply_delta != 0)
@@ -202,12 +278,22 @@ impl StateManager {
         }
     }
 
+    /// Balance of `coin_type` held by `address`, KANARI included
+    pub fn get_coin_balance(&self, address: &AccountAddress, coin_type: &TypeTag) -> u64 {
+        self.accounts
+            .get(address)
+            .map(|acc| acc.balance_of(coin_type))
+            .unwrap_or(0)
+    }
+
     pub fn account_count(&self) -> usize {
         self.accounts.len()
     }
 
+    /// Accounts are kept in ordered maps/sets so the serialized form, and thus
+    /// the root, is identical on every node holding the same state.
     pub fn compute_state_root(&self) -> Vec<u8> {
-        let serialized = serde_json::to_vec(&self.accounts).unwrap();
+        let serialized = serde_json::to_vec(&(&self.accounts, self.total_supply)).unwrap();
         hash_data_blake3(&serialized)
     }
 
@@ -238,6 +324,25 @@ mod tests {
         assert!(state.accounts.contains_key(&dev_addr));
     }
 
+    #[test]
+    fn test_state_root_deterministic() {
+        let mut a = StateManager::new();
+        let mut b = StateManager::new();
+        for addr in ["0x10", "0x20", "0x30"] {
+            let addr = AccountAddress::from_hex_literal(addr).unwrap();
+            a.get_or_create_account(addr).add_module("m1".to_string());
+        }
+        for addr in ["0x30", "0x20", "0x10"] {
+            let addr = AccountAddress::from_hex_literal(addr).unwrap();
+            b.get_or_create_account(addr).add_module("m1".to_string());
+        }
+        assert_eq!(a.compute_state_root(), b.compute_state_root());
+
+        let rebuilt =
+            StateManager::from_accounts(a.accounts.values().cloned().collect(), a.total_supply);
+        assert_eq!(rebuilt.compute_state_root(), a.compute_state_root());
+    }
+
     #[test]
     fn test_get_or_create_account() {
         let mut state = StateManager::new();
@@ -0,0 +1,165 @@
+// Results of read-only view function calls and JSON rendering of Move values.
+
+use move_core_types::annotated_value::{MoveStruct, MoveValue};
+use move_core_types::language_storage::StructTag;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Result of calling a view function
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ViewResult {
+    /// Height of the state the function was executed against
+    pub height: u64,
+    pub return_values: Vec<ViewValue>,
+}
+
+/// A single value returned by a view function
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ViewValue {
+    /// Fully qualified return type, e.g. `u64` or `0x2::coin::Coin<0x2::kanari::KANARI>`
+    pub type_tag: String,
+    /// BCS encoding of the value
+    pub bcs: Vec<u8>,
+    /// The value decoded according to its type
+    pub json: Value,
+}
+
+/// A resource stored under an account
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MoveResource {
+    /// Fully qualified struct tag, e.g. `0x2::coin::CoinMetadata<0x2::kanari::KANARI>`
+    pub struct_tag: String,
+    /// BCS encoding of the resource
+    pub bcs: Vec<u8>,
+    /// The resource decoded with the layout of its published module, or `null`
+    /// if the module is not available
+    pub json: Value,
+}
+
+/// Render a decoded Move value as JSON.
+///
+/// Integers up to `u64` become JSON numbers, wider integers become decimal strings,
+/// addresses are `0x`-prefixed hex, and structs become objects keyed by field name.
+/// `std::string::String`, `std::ascii::String` and `std::option::Option` are
+/// rendered as a string, and as `null` or the inner value, respectively.
+pub fn move_value_to_json(value: &MoveValue) -> Value {
+    match value {
+        MoveValue::Bool(b) => Value::Bool(*b),
+        MoveValue::U8(n) => Value::from(*n),
+        MoveValue::U16(n) => Value::from(*n),
+        MoveValue::U32(n) => Value::from(*n),
+        MoveValue::U64(n) => Value::from(*n),
+        MoveValue::U128(n) => Value::String(n.to_string()),
+        MoveValue::U256(n) => Value::String(n.to_string()),
+        MoveValue::Address(a) | MoveValue::Signer(a) => Value::String(format!("{:#x}", a)),
+        MoveValue::Vector(values) => Value::Array(values.iter().map(move_value_to_json).collect()),
+        MoveValue::Struct(s) => struct_to_json(s),
+    }
+}
+
+fn struct_to_json(s: &MoveStruct) -> Value {
+    if (is_std_type(&s.type_, "string", "String") || is_std_type(&s.type_, "ascii", "String"))
+        && let [(_, MoveValue::Vector(bytes))] = s.fields.as_slice()
+    {
+        let bytes: Vec<u8> = bytes
+            .iter()
+            .filter_map(|b| match b {
+                MoveValue::U8(b) => Some(*b),
+                _ => None,
+            })
+            .collect();
+        if let Ok(string) = String::from_utf8(bytes) {
+            return Value::String(string);
+        }
+    }
+    if is_std_type(&s.type_, "option", "Option")
+        && let [(_, MoveValue::Vector(inner))] = s.fields.as_slice()
+    {
+        return inner.first().map(move_value_to_json).unwrap_or(Value::Null);
+    }
+
+    let fields: Map<String, Value> = s
+        .fields
+        .iter()
+        .map(|(name, value)| (name.to_string(), move_value_to_json(value)))
+        .collect();
+    Value::Object(fields)
+}
+
+fn is_std_type(tag: &StructTag, module: &str, name: &str) -> bool {
+    tag.address == move_core_types::account_address::AccountAddress::ONE
+        && tag.module.as_str() == module
+        && tag.name.as_str() == name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_core_types::account_address::AccountAddress;
+    use move_core_types::identifier::Identifier;
+
+    fn std_struct(module: &str, name: &str, fields: Vec<(&str, MoveValue)>) -> MoveValue {
+        MoveValue::Struct(MoveStruct::new(
+            StructTag {
+                address: AccountAddress::ONE,
+                module: Identifier::new(module).unwrap(),
+                name: Identifier::new(name).unwrap(),
+                type_params: vec![],
+            },
+            fields
+                .into_iter()
+                .map(|(n, v)| (Identifier::new(n).unwrap(), v))
+                .collect(),
+        ))
+    }
+
+    #[test]
+    fn test_move_value_to_json() {
+        let name = std_struct(
+            "string",
+            "String",
+            vec![(
+                "bytes",
+                MoveValue::Vector(b"kanari".iter().map(|b| MoveValue::U8(*b)).collect()),
+            )],
+        );
+        let none = std_struct("option", "Option", vec![("vec", MoveValue::Vector(vec![]))]);
+        let some = std_struct(
+            "option",
+            "Option",
+            vec![("vec", MoveValue::Vector(vec![MoveValue::U64(7)]))],
+        );
+        let value = MoveValue::Struct(MoveStruct::new(
+            StructTag {
+                address: AccountAddress::TWO,
+                module: Identifier::new("coin").unwrap(),
+                name: Identifier::new("Info").unwrap(),
+                type_params: vec![],
+            },
+            vec![
+                (Identifier::new("name").unwrap(), name),
+                (
+                    Identifier::new("supply").unwrap(),
+                    MoveValue::U128(u128::MAX),
+                ),
+                (Identifier::new("icon").unwrap(), none),
+                (Identifier::new("decimals").unwrap(), some),
+                (
+                    Identifier::new("owner").unwrap(),
+                    MoveValue::Address(AccountAddress::TWO),
+                ),
+            ],
+        ));
+
+        assert_eq!(
+            move_value_to_json(&value),
+            serde_json::json!({
+                "name": "kanari",
+                "supply": u128::MAX.to_string(),
+                "icon": null,
+                "decimals": 7,
+                "owner": format!("{:#x}", AccountAddress::TWO),
+            })
+        );
+    }
+}
@@ -1,6 +1,11 @@
 use anyhow::Result;
+use kanari_crypto::keys::CurveType;
 use kanari_crypto::wallet::list_wallet_files;
-use kanari_move_runtime::{BlockchainEngine, MoveRuntime};
+use kanari_move_runtime::{
+    BlockSigner, BlockchainEngine, MoveRuntime, RetentionMode, StateSnapshot, ValidatorSet,
+};
+use kanari_p2p::{ChainSync, DEFAULT_P2P_PORT, NodeKey, P2pConfig, P2pNetwork, RpcSource};
+use kanari_rpc_client::RpcClient;
 use kanari_rpc_server::start_server;
 use kanari_types::framework_path::FrameworkPath;
 use kanari_types::module_registry::ModuleRegistry;
@@ -8,6 +13,7 @@ use kanari_types::module_registry::ModuleRegistry;
 use move_core_types::account_address::AccountAddress;
 
 use chrono::Local;
+use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::{env, time::Duration};
@@ -16,13 +22,33 @@ use tracing_subscriber;
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    // CLI: subcommands: run | publish-all | list-wallets | publish-file <path> | stats | account <addr> | block <height> | modules
+    // CLI: subcommands: run | publish-all | list-wallets | publish-file <path> | stats | account <addr> | block <height> | modules | snapshot <export|import>
     let args: Vec<String> = env::args().collect();
-    let cmd = args.get(1).map(|s| s.as_str()).unwrap_or("run");
+    // Options (e.g. `--retention`) may follow the command or be given alone for `run`
+    let cmd = args
+        .get(1)
+        .map(|s| s.as_str())
+        .filter(|s| !s.starts_with("--"))
+        .unwrap_or("run");
 
     // Initialize blockchain engine
     let engine = BlockchainEngine::new()?;
 
+    // Block/state retention for long-running nodes, e.g. `--retention keep-last:10000`
+    if let Some(mode) = flag_value(&args, "--retention") {
+        engine.set_retention_mode(mode.parse::<RetentionMode>()?);
+    }
+
+    // Proof-of-authority: `--validators ed25519:<public key>,...` enables the round-robin
+    // schedule, and KANARI_VALIDATOR_KEY holds this node's signing key when it is a validator
+    if let Some(list) = flag_value(&args, "--validators") {
+        engine.set_validators(ValidatorSet::from_list(list, parse_curve)?);
+    }
+    if let Ok(private_key) = env::var("KANARI_VALIDATOR_KEY") {
+        let curve = env::var("KANARI_VALIDATOR_CURVE").unwrap_or_else(|_| "ed25519".to_string());
+        engine.set_block_signer(BlockSigner::new(&private_key, parse_curve(&curve)?)?);
+    }
+
     match cmd {
         "list-wallets" => {
             let wallets = list_wallet_files()?;
@@ -75,6 +101,21 @@ async fn main() -> Result<()> {
                     println!("  Hash: {}", block.hash);
                     println!("  Prev Hash: {}", block.prev_hash);
                     println!("  Transactions: {}", block.tx_count);
+                    if let Some(producer) = &block.producer {
+                        println!("  Producer: {}", producer);
+                    }
+                    if !block.events.is_empty() {
+                        println!("  Events:");
+                        for event in &block.events {
+                            println!("    {} #{}", event.type_tag, event.sequence_number);
+                            let parsed = engine.decode_event(event);
+                            if parsed.is_null() {
+                                println!("      data: 0x{}", hex::encode(&event.event_data));
+                            } else {
+                                println!("      {}", parsed);
+                            }
+                        }
+                    }
                 }
                 None => println!("Block not found: {}", height),
             }
@@ -198,6 +239,59 @@ async fn main() -> Result<()> {
             return Ok(());
         }
 
+        "snapshot" => match args.get(2).map(|s| s.as_str()) {
+            Some("export") => {
+                // Chain state lives in the running node, so export goes through its RPC
+                let height: u64 = flag_value(&args, "--height")
+                    .ok_or_else(|| anyhow::anyhow!("Usage: snapshot export --height <N>"))?
+                    .parse()?;
+                let output = flag_value(&args, "--output")
+                    .map(PathBuf::from)
+                    .unwrap_or_else(|| PathBuf::from(format!("kanari-snapshot-{}.bin", height)));
+                let rpc_url = flag_value(&args, "--rpc").unwrap_or("http://127.0.0.1:3000");
+
+                let client = RpcClient::new(rpc_url);
+                let exported = client.export_snapshot(height).await?;
+                let bytes = hex::decode(&exported.data)?;
+
+                // Make sure what we write is a valid snapshot before saving it
+                let snapshot = StateSnapshot::from_bytes(&bytes)?;
+                snapshot.verify()?;
+                std::fs::write(&output, &bytes)?;
+
+                println!("Exported snapshot at height {}", snapshot.height());
+                println!("  State root: {}", exported.state_root);
+                println!("  Accounts: {}", snapshot.accounts.len());
+                println!("  Modules: {}", snapshot.modules.len());
+                println!("  Resources: {}", snapshot.resources.len());
+                println!("  File: {} ({} bytes)", output.display(), bytes.len());
+                return Ok(());
+            }
+            Some("import") => {
+                let path = match args.get(3) {
+                    Some(p) => PathBuf::from(p),
+                    None => {
+                        eprintln!("Usage: snapshot import <path-to-snapshot>");
+                        std::process::exit(2);
+                    }
+                };
+                let snapshot = StateSnapshot::read_from_file(&path)?;
+                let height = snapshot.height();
+                let state_root = hex::encode(&snapshot.header.state_root);
+                engine.import_snapshot(snapshot)?;
+                println!(
+                    "Imported snapshot at height {} (state root {})",
+                    height, state_root
+                );
+                // fallthrough to blockchain node run from the imported state
+            }
+            _ => {
+                eprintln!("Usage: snapshot export --height <N> [--output <file>] [--rpc <url>]");
+                eprintln!("       snapshot import <file>");
+                std::process::exit(2);
+            }
+        },
+
         "run" => {
             // fallthrough to blockchain node run
         }
@@ -216,6 +310,32 @@ async fn main() -> Result<()> {
             eprintln!("  publish-file <path>      - Publish specific module");
             eprintln!("  inspect <path>           - Inspect module bytecode");
             eprintln!("  list-wallets             - List available wallets");
+            eprintln!("  snapshot export --height <N> [--output <file>] [--rpc <url>]");
+            eprintln!("                           - Export state at height N from a running node");
+            eprintln!("  snapshot import <file>   - Verify snapshot and start node from it");
+            eprintln!();
+            eprintln!("Options:");
+            eprintln!(
+                "  --retention <mode>       - archive | keep-last:<n> | checkpoints:<interval>:<keep_last>"
+            );
+            eprintln!(
+                "  --validators <curve:key,...> - Enable proof-of-authority with these validator public keys"
+            );
+            eprintln!("  --rpc-addr <host:port>   - RPC listen address (default 127.0.0.1:3000)");
+            eprintln!(
+                "  --p2p <host:port>        - Accept peer connections on this address (e.g. 127.0.0.1:{})",
+                DEFAULT_P2P_PORT
+            );
+            eprintln!("  --seeds <host:port,...>  - Static peers to connect to (enables P2P)");
+            eprintln!(
+                "  --sync-rpc <url>         - Catch up from another node's RPC before starting"
+            );
+            eprintln!();
+            eprintln!("Environment:");
+            eprintln!("  KANARI_VALIDATOR_KEY     - Private key used to sign produced blocks");
+            eprintln!("  KANARI_VALIDATOR_CURVE   - ed25519 (default) | k256 | p256");
+            eprintln!("  KANARI_NODE_KEY          - Private key identifying this node to peers");
+            eprintln!("  KANARI_NODE_CURVE        - ed25519 (default) | k256 | p256");
             std::process::exit(2);
         }
     }
@@ -278,7 +398,9 @@ async fn main() -> Result<()> {
     println!();
 
     // Start RPC server in background with cloned Arc
-    let rpc_addr = "127.0.0.1:3000";
+    let rpc_addr = flag_value(&args, "--rpc-addr")
+        .unwrap_or("127.0.0.1:3000")
+        .to_string();
     println!(
         "{} INFO kanari_rpc_server: Starting RPC server on http://{}",
         Local::now().format("%Y-%m-%dT%H:%M:%S%.6fZ"),
@@ -287,7 +409,7 @@ async fn main() -> Result<()> {
 
     let engine_for_rpc = engine.clone();
     tokio::spawn(async move {
-        if let Err(e) = start_server(engine_for_rpc, rpc_addr).await {
+        if let Err(e) = start_server(engine_for_rpc, &rpc_addr).await {
             eprintln!(
                 "{} ERROR kanari_rpc_server: RPC server error: {}",
                 Local::now().format("%Y-%m-%dT%H:%M:%S%.6fZ"),
@@ -304,6 +426,101 @@ async fn main() -> Result<()> {
     );
     println!();
 
+    // Peer-to-peer gossip, enabled by `--p2p <listen addr>` and/or `--seeds`
+    let seeds = flag_value(&args, "--seeds")
+        .map(|list| {
+            list.split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(|s| s.parse::<SocketAddr>())
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .transpose()?
+        .unwrap_or_default();
+    // Catch up from another node's RPC before producing or following gossip
+    if let Some(url) = flag_value(&args, "--sync-rpc") {
+        let report = ChainSync::new(engine.clone())
+            .sync(&RpcSource::new(url))
+            .await?;
+        println!(
+            "{} INFO kanari_p2p::sync: Synced {} blocks from {} (height {} -> {})",
+            Local::now().format("%Y-%m-%dT%H:%M:%S%.6fZ"),
+            report.imported,
+            url,
+            report.start_height,
+            report.end_height
+        );
+    }
+
+    let p2p_listen = flag_value(&args, "--p2p");
+    let network = if p2p_listen.is_some() || !seeds.is_empty() {
+        let listen_addr: SocketAddr = match p2p_listen {
+            Some(addr) => addr.parse()?,
+            None => SocketAddr::from(([0, 0, 0, 0], DEFAULT_P2P_PORT)),
+        };
+        let node_key = match env::var("KANARI_NODE_KEY") {
+            Ok(private_key) => {
+                let curve = env::var("KANARI_NODE_CURVE").unwrap_or_else(|_| "ed25519".to_string());
+                NodeKey::from_private_key(&private_key, parse_curve(&curve)?)?
+            }
+            Err(_) => NodeKey::generate()?,
+        };
+        let network = P2pNetwork::new(
+            engine.clone(),
+            node_key,
+            P2pConfig::new(listen_addr).with_seeds(seeds),
+        );
+        let bound = network.start().await?;
+        println!(
+            "{} INFO kanari_p2p: Listening for peers on {} as {}",
+            Local::now().format("%Y-%m-%dT%H:%M:%S%.6fZ"),
+            bound,
+            network.address()
+        );
+        Some(network)
+    } else {
+        None
+    };
+
+    // Background pruning of old blocks and historical state
+    let retention = *engine.retention.read().unwrap();
+    println!(
+        "{} INFO kanari_node::pruner: Retention mode: {}",
+        Local::now().format("%Y-%m-%dT%H:%M:%S%.6fZ"),
+        retention
+    );
+    if retention != RetentionMode::Archive {
+        let engine_for_pruner = engine.clone();
+        tokio::spawn(async move {
+            loop {
+                sleep(Duration::from_secs(30)).await;
+                let stats = engine_for_pruner.prune();
+                if !stats.is_empty() {
+                    println!(
+                        "{} INFO kanari_node::pruner: Pruned {} blocks, {} bodies, {} state versions",
+                        Local::now().format("%Y-%m-%dT%H:%M:%S%.6fZ"),
+                        stats.blocks_removed,
+                        stats.bodies_pruned,
+                        stats.states_removed
+                    );
+                }
+            }
+        });
+    }
+
+    if let Some(validators) = engine.blockchain.read().unwrap().validators.as_ref() {
+        let signer = engine.block_signer.read().unwrap();
+        println!(
+            "{} INFO kanari_node::consensus: Proof-of-authority with {} validators, local validator: {}",
+            Local::now().format("%Y-%m-%dT%H:%M:%S%.6fZ"),
+            validators.len(),
+            signer
+                .as_ref()
+                .map(|s| s.address.as_str())
+                .unwrap_or("none")
+        );
+    }
+
     let mut _tick: u64 = 0;
     loop {
         _tick += 1;
@@ -321,8 +538,10 @@ async fn main() -> Result<()> {
             wallets.len()
         );
 
-        // Try to produce block if there are pending transactions
-        if stats.pending_transactions > 0 {
+        // Try to produce block if there are pending transactions and it is our turn
+        // (never while still catching up with peers)
+        let syncing = network.as_ref().is_some_and(|n| n.is_syncing());
+        if stats.pending_transactions > 0 && !syncing && engine.is_leader_for_next_block() {
             match engine.produce_block() {
                 Ok(block_info) => {
                     println!(
@@ -347,3 +566,21 @@ async fn main() -> Result<()> {
         sleep(Duration::from_secs(5)).await;
     }
 }
+
+/// Value following `flag` in the argument list, e.g. `--height 10`
+fn flag_value<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|a| a == flag)
+        .and_then(|i| args.get(i + 1))
+        .map(|s| s.as_str())
+}
+
+/// Curve for the validator key, matching the names accepted by the CLI
+fn parse_curve(name: &str) -> Result<CurveType> {
+    match name.to_lowercase().as_str() {
+        "ed25519" => Ok(CurveType::Ed25519),
+        "k256" | "secp256k1" => Ok(CurveType::K256),
+        "p256" | "secp256r1" => Ok(CurveType::P256),
+        other => anyhow::bail!("Unsupported validator curve '{}'", other),
+    }
+}
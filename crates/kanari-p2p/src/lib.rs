@@ -0,0 +1,77 @@
+//! Kanari P2P
+//!
+//! TCP networking between Kanari nodes: static seed discovery with peer exchange,
+//! handshakes authenticated by node keys, and gossip of transactions and blocks
+
+pub mod message;
+pub mod network;
+pub mod sync;
+
+pub use message::{MAX_FRAME_SIZE, Message, PROTOCOL_VERSION};
+pub use network::{
+    DEFAULT_MAX_PEERS, DEFAULT_P2P_PORT, DISCOVERY_INTERVAL, NodeKey, P2pConfig, P2pNetwork,
+    PeerInfo,
+};
+pub use sync::{BlockSource, ChainSync, PeerSource, RpcSource, SyncReport};
+
+#[cfg(test)]
+mod test_utils {
+    use kanari_crypto::keys::{CurveType, KeyPair, authentication_key, generate_keypair};
+    use kanari_move_runtime::{BlockchainEngine, SignedTransaction, Transaction};
+    use kanari_types::address::Address as KanariAddress;
+    use std::sync::{Arc, Mutex, OnceLock};
+
+    /// Engines read their Move storage path from the environment when created
+    static ENGINE_LOCK: Mutex<()> = Mutex::new(());
+
+    pub fn new_engine(dir: &tempfile::TempDir) -> Arc<BlockchainEngine> {
+        let _guard = ENGINE_LOCK.lock().unwrap();
+        unsafe {
+            std::env::set_var("KANARI_MOVE_VM_DB", dir.path());
+        }
+        let public_key = hex::decode(&dev_key().public_key).unwrap();
+        Arc::new(
+            BlockchainEngine::with_dev_authentication_key(authentication_key(
+                CurveType::Ed25519,
+                &public_key,
+            ))
+            .unwrap(),
+        )
+    }
+
+    /// Key controlling the genesis account of every test engine
+    fn dev_key() -> &'static KeyPair {
+        static KEY: OnceLock<KeyPair> = OnceLock::new();
+        KEY.get_or_init(|| generate_keypair(CurveType::Ed25519).unwrap())
+    }
+
+    /// Sign a transaction sent from the genesis account
+    pub fn sign_as_dev(tx: Transaction) -> SignedTransaction {
+        let mut signed = SignedTransaction::new(tx);
+        signed
+            .sign(&dev_key().private_key, CurveType::Ed25519)
+            .unwrap();
+        signed
+    }
+
+    /// Produce a block with a single transfer from the genesis account
+    pub fn produce_transfer(engine: &BlockchainEngine, amount: u64) {
+        let from = KanariAddress::DEV_ADDRESS.to_string();
+        let sequence_number = engine.get_account_info(&from).unwrap().sequence_number;
+        engine
+            .pending_txs
+            .write()
+            .unwrap()
+            .push(sign_as_dev(Transaction::Transfer {
+                from,
+                to: "0x42".to_string(),
+                amount,
+                coin_type: None,
+                gas_limit: 100_000,
+                gas_price: 1000,
+                priority_fee: 0,
+                sequence_number,
+            }));
+        engine.produce_block().unwrap();
+    }
+}
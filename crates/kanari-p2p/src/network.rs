@@ -0,0 +1,804 @@
+use crate::message::{Message, PROTOCOL_VERSION, handshake_payload, read_message, write_message};
+use crate::sync::{ChainSync, MAX_SYNC_BATCH, PeerSource};
+use anyhow::{Context, Result};
+use kanari_crypto::keys::{CurveType, generate_keypair, keypair_from_private_key};
+use kanari_move_runtime::{BlockchainEngine, ChainEvent};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
+use std::time::Duration;
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender, unbounded_channel};
+use tokio::sync::oneshot;
+use tracing::{debug, info, warn};
+
+/// Default TCP port for peer connections
+pub const DEFAULT_P2P_PORT: u16 = 4000;
+/// Default upper bound on connected peers
+pub const DEFAULT_MAX_PEERS: usize = 32;
+/// How often seeds are redialed and peers are asked for more peers
+pub const DISCOVERY_INTERVAL: Duration = Duration::from_secs(5);
+
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+/// Recently gossiped transaction and block hashes remembered to stop echo loops
+const SEEN_CACHE_SIZE: usize = 10_000;
+
+/// Networking options for a node
+#[derive(Debug, Clone)]
+pub struct P2pConfig {
+    /// Address to accept peer connections on; port 0 picks a free port
+    pub listen_addr: SocketAddr,
+    /// Static peers dialed at startup and redialed while disconnected
+    pub seeds: Vec<SocketAddr>,
+    pub max_peers: usize,
+}
+
+impl P2pConfig {
+    pub fn new(listen_addr: SocketAddr) -> Self {
+        Self {
+            listen_addr,
+            seeds: Vec::new(),
+            max_peers: DEFAULT_MAX_PEERS,
+        }
+    }
+
+    pub fn with_seeds(mut self, seeds: Vec<SocketAddr>) -> Self {
+        self.seeds = seeds;
+        self
+    }
+}
+
+/// Key identifying a node to its peers. The node address doubles as the public key
+/// that peers use to verify the handshake signature.
+#[derive(Clone)]
+pub struct NodeKey {
+    pub address: String,
+    private_key: String,
+    pub curve_type: CurveType,
+}
+
+impl NodeKey {
+    pub fn from_private_key(private_key: &str, curve_type: CurveType) -> Result<Self> {
+        let keypair = keypair_from_private_key(private_key, curve_type)
+            .map_err(|e| anyhow::anyhow!("Invalid node key: {}", e))?;
+        Ok(Self {
+            address: keypair.address,
+            private_key: keypair.private_key,
+            curve_type,
+        })
+    }
+
+    /// Fresh Ed25519 identity, for nodes without a configured key
+    pub fn generate() -> Result<Self> {
+        let keypair = generate_keypair(CurveType::Ed25519)
+            .map_err(|e| anyhow::anyhow!("Failed to generate node key: {}", e))?;
+        Ok(Self {
+            address: keypair.address,
+            private_key: keypair.private_key,
+            curve_type: CurveType::Ed25519,
+        })
+    }
+
+    fn sign(&self, message: &[u8]) -> Result<Vec<u8>> {
+        kanari_crypto::sign_message(&self.private_key, message, self.curve_type)
+            .map_err(|e| anyhow::anyhow!("Failed to sign handshake: {}", e))
+    }
+}
+
+impl std::fmt::Debug for NodeKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Never print the private key
+        f.debug_struct("NodeKey")
+            .field("address", &self.address)
+            .field("curve_type", &self.curve_type)
+            .finish()
+    }
+}
+
+/// A connected, authenticated peer
+#[derive(Debug, Clone)]
+pub struct PeerInfo {
+    /// Authenticated node address
+    pub address: String,
+    pub remote_addr: SocketAddr,
+    /// Address the peer accepts connections on, if it announced one
+    pub listen_addr: Option<SocketAddr>,
+    /// Whether this node dialed the connection
+    pub outbound: bool,
+}
+
+struct Peer {
+    info: PeerInfo,
+    /// Node address of the side that dialed, used to break ties between duplicate connections
+    initiator: String,
+    connection_id: u64,
+    sender: UnboundedSender<Message>,
+}
+
+/// Bounded set of recently seen hashes
+#[derive(Default)]
+struct SeenCache {
+    set: HashSet<Vec<u8>>,
+    order: VecDeque<Vec<u8>>,
+}
+
+impl SeenCache {
+    /// Returns true if the hash was not seen before
+    fn insert(&mut self, hash: Vec<u8>) -> bool {
+        if !self.set.insert(hash.clone()) {
+            return false;
+        }
+        self.order.push_back(hash);
+        if self.order.len() > SEEN_CACHE_SIZE
+            && let Some(oldest) = self.order.pop_front()
+        {
+            self.set.remove(&oldest);
+        }
+        true
+    }
+}
+
+/// TCP gossip network.
+///
+/// Transactions accepted into the local mempool and new blocks (produced or imported)
+/// are pushed to every peer; messages received from peers are handed to the engine,
+/// which re-announces them if they were new.
+pub struct P2pNetwork {
+    engine: Arc<BlockchainEngine>,
+    key: NodeKey,
+    config: P2pConfig,
+    genesis_hash: String,
+    local_addr: OnceLock<SocketAddr>,
+    peers: RwLock<HashMap<String, Peer>>,
+    seen: Mutex<SeenCache>,
+    next_connection_id: AtomicU64,
+    /// Addresses learned from peer exchange, dialed by the task spawned in `start`
+    dial_requests: UnboundedSender<SocketAddr>,
+    dial_queue: Mutex<Option<UnboundedReceiver<SocketAddr>>>,
+    /// Outstanding sync request per peer, with the sender for its response
+    pending_requests: Mutex<HashMap<String, (Message, oneshot::Sender<Message>)>>,
+    syncing: AtomicBool,
+}
+
+impl P2pNetwork {
+    pub fn new(engine: Arc<BlockchainEngine>, key: NodeKey, config: P2pConfig) -> Arc<Self> {
+        let genesis_hash = {
+            let chain = engine.blockchain.read().unwrap();
+            hex::encode(chain.blocks[0].hash())
+        };
+        let (dial_requests, dial_queue) = unbounded_channel();
+        Arc::new(Self {
+            engine,
+            key,
+            config,
+            genesis_hash,
+            local_addr: OnceLock::new(),
+            peers: RwLock::new(HashMap::new()),
+            seen: Mutex::new(SeenCache::default()),
+            next_connection_id: AtomicU64::new(0),
+            dial_requests,
+            dial_queue: Mutex::new(Some(dial_queue)),
+            pending_requests: Mutex::new(HashMap::new()),
+            syncing: AtomicBool::new(false),
+        })
+    }
+
+    pub fn address(&self) -> &str {
+        &self.key.address
+    }
+
+    /// Listening address, available once `start` has returned
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.local_addr.get().copied()
+    }
+
+    pub fn peers(&self) -> Vec<PeerInfo> {
+        self.peers
+            .read()
+            .unwrap()
+            .values()
+            .map(|p| p.info.clone())
+            .collect()
+    }
+
+    pub fn peer_count(&self) -> usize {
+        self.peers.read().unwrap().len()
+    }
+
+    /// Whether a block sync is in progress; the node follows gossip otherwise
+    pub fn is_syncing(&self) -> bool {
+        self.syncing.load(Ordering::SeqCst)
+    }
+
+    /// Send a request to a peer and wait for its response
+    pub async fn request(&self, peer: &str, message: Message) -> Result<Message> {
+        let (response_tx, response_rx) = oneshot::channel();
+        {
+            let mut pending = self.pending_requests.lock().unwrap();
+            if pending.contains_key(peer) {
+                anyhow::bail!("A request to {} is already in flight", peer);
+            }
+            pending.insert(peer.to_string(), (message.clone(), response_tx));
+        }
+        let sent = self
+            .peers
+            .read()
+            .unwrap()
+            .get(peer)
+            .is_some_and(|p| p.sender.send(message).is_ok());
+        if !sent {
+            self.pending_requests.lock().unwrap().remove(peer);
+            anyhow::bail!("Peer {} is not connected", peer);
+        }
+
+        match tokio::time::timeout(REQUEST_TIMEOUT, response_rx).await {
+            Ok(Ok(response)) => Ok(response),
+            Ok(Err(_)) => anyhow::bail!("Peer {} disconnected", peer),
+            Err(_) => {
+                self.pending_requests.lock().unwrap().remove(peer);
+                anyhow::bail!("Request to {} timed out", peer)
+            }
+        }
+    }
+
+    /// Hand a response to the waiting request. Returns the message if nothing was waiting for it.
+    fn deliver_response(&self, peer: &str, message: Message) -> Option<Message> {
+        let mut pending = self.pending_requests.lock().unwrap();
+        match pending.get(peer) {
+            Some((request, _)) if message.answers(request) => {
+                let (_, response_tx) = pending.remove(peer).unwrap();
+                let _ = response_tx.send(message);
+                None
+            }
+            _ => Some(message),
+        }
+    }
+
+    /// Start catching up from `peer` unless a sync is already running
+    fn start_sync(self: &Arc<Self>, peer: &str) {
+        if self.syncing.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let network = self.clone();
+        let peer = peer.to_string();
+        tokio::spawn(async move {
+            let source = PeerSource::new(network.clone(), peer.clone());
+            match ChainSync::new(network.engine.clone()).sync(&source).await {
+                Ok(report) => info!(
+                    "Synced {} blocks from {} (height {} -> {}), following live gossip",
+                    report.imported, peer, report.start_height, report.end_height
+                ),
+                Err(e) => warn!("Sync from {} failed: {}", peer, e),
+            }
+            network.syncing.store(false, Ordering::SeqCst);
+        });
+    }
+
+    fn status(&self) -> Message {
+        let chain = self.engine.blockchain.read().unwrap();
+        Message::Status {
+            height: chain.height(),
+            tip_hash: hex::encode(chain.latest_block().hash()),
+        }
+    }
+
+    fn local_height(&self) -> u64 {
+        self.engine.blockchain.read().unwrap().height()
+    }
+
+    /// Bind the listener, dial the seeds and start relaying chain events.
+    /// Returns the bound address.
+    pub async fn start(self: &Arc<Self>) -> Result<SocketAddr> {
+        let listener = TcpListener::bind(self.config.listen_addr)
+            .await
+            .with_context(|| {
+                format!("Failed to bind P2P listener on {}", self.config.listen_addr)
+            })?;
+        let local_addr = listener.local_addr()?;
+        let _ = self.local_addr.set(local_addr);
+        info!("P2P listening on {} as {}", local_addr, self.key.address);
+
+        let network = self.clone();
+        tokio::spawn(async move {
+            loop {
+                match listener.accept().await {
+                    Ok((stream, remote)) => {
+                        let network = network.clone();
+                        tokio::spawn(async move {
+                            if let Err(e) = network.run_connection(stream, remote, false).await {
+                                debug!("Inbound connection from {} closed: {}", remote, e);
+                            }
+                        });
+                    }
+                    Err(e) => warn!("Failed to accept peer connection: {}", e),
+                }
+            }
+        });
+
+        // The engine notifies through a blocking channel, so relay from a plain thread
+        let events = self.engine.subscribe();
+        let network = self.clone();
+        std::thread::spawn(move || {
+            for event in events {
+                network.relay(event);
+            }
+        });
+
+        if let Some(mut dial_queue) = self.dial_queue.lock().unwrap().take() {
+            let network = self.clone();
+            tokio::spawn(async move {
+                while let Some(addr) = dial_queue.recv().await {
+                    let network = network.clone();
+                    tokio::spawn(async move {
+                        if let Err(e) = network.connect(addr).await {
+                            debug!("Failed to connect to discovered peer {}: {}", addr, e);
+                        }
+                    });
+                }
+            });
+        }
+
+        let network = self.clone();
+        tokio::spawn(async move {
+            loop {
+                network.discover().await;
+                tokio::time::sleep(DISCOVERY_INTERVAL).await;
+            }
+        });
+
+        Ok(local_addr)
+    }
+
+    /// Dial a peer and complete the handshake. Returns the peer's node address.
+    pub async fn connect(self: &Arc<Self>, addr: SocketAddr) -> Result<String> {
+        let stream = tokio::time::timeout(HANDSHAKE_TIMEOUT, TcpStream::connect(addr))
+            .await
+            .context("Connection timed out")??;
+        let (address_tx, address_rx) = tokio::sync::oneshot::channel();
+        let network = self.clone();
+        tokio::spawn(async move {
+            let result = network
+                .run_connection_with(stream, addr, true, Some(address_tx))
+                .await;
+            if let Err(e) = result {
+                debug!("Outbound connection to {} closed: {}", addr, e);
+            }
+        });
+        address_rx
+            .await
+            .map_err(|_| anyhow::anyhow!("Handshake with {} failed", addr))
+    }
+
+    /// Redial disconnected seeds and ask peers for more peers
+    async fn discover(self: &Arc<Self>) {
+        for seed in self.config.seeds.clone() {
+            if self.is_connected_to(seed) || Some(seed) == self.local_addr() {
+                continue;
+            }
+            if let Err(e) = self.connect(seed).await {
+                debug!("Failed to connect to seed {}: {}", seed, e);
+            }
+        }
+        if self.peer_count() < self.config.max_peers {
+            self.broadcast(Message::GetPeers);
+        }
+    }
+
+    fn is_connected_to(&self, addr: SocketAddr) -> bool {
+        self.peers
+            .read()
+            .unwrap()
+            .values()
+            .any(|p| p.info.listen_addr == Some(addr) || p.info.remote_addr == addr)
+    }
+
+    fn broadcast(&self, message: Message) {
+        for peer in self.peers.read().unwrap().values() {
+            let _ = peer.sender.send(message.clone());
+        }
+    }
+
+    /// Push locally accepted transactions and blocks to all peers
+    fn relay(&self, event: ChainEvent) {
+        match event {
+            ChainEvent::Transaction(tx) => {
+                self.seen.lock().unwrap().insert(tx.hash());
+                self.broadcast(Message::Transaction(tx));
+            }
+            ChainEvent::Block(block) => {
+                self.seen.lock().unwrap().insert(block.hash());
+                self.broadcast(Message::Block(block));
+            }
+        }
+    }
+
+    async fn run_connection(
+        self: Arc<Self>,
+        stream: TcpStream,
+        remote: SocketAddr,
+        outbound: bool,
+    ) -> Result<()> {
+        self.run_connection_with(stream, remote, outbound, None)
+            .await
+    }
+
+    async fn run_connection_with(
+        self: Arc<Self>,
+        stream: TcpStream,
+        remote: SocketAddr,
+        outbound: bool,
+        on_ready: Option<tokio::sync::oneshot::Sender<String>>,
+    ) -> Result<()> {
+        let (mut reader, mut writer) = stream.into_split();
+        let (address, listen_addr) = tokio::time::timeout(
+            HANDSHAKE_TIMEOUT,
+            self.handshake(&mut reader, &mut writer, remote),
+        )
+        .await
+        .context("Handshake timed out")??;
+
+        let connection_id = self.next_connection_id.fetch_add(1, Ordering::Relaxed);
+        let (sender, mut outgoing) = unbounded_channel::<Message>();
+        let initiator = if outbound {
+            self.key.address.clone()
+        } else {
+            address.clone()
+        };
+        self.register(Peer {
+            info: PeerInfo {
+                address: address.clone(),
+                remote_addr: remote,
+                listen_addr,
+                outbound,
+            },
+            initiator,
+            connection_id,
+            sender: sender.clone(),
+        })?;
+        info!("Connected to peer {} at {}", address, remote);
+        if let Some(on_ready) = on_ready {
+            let _ = on_ready.send(address.clone());
+        }
+
+        tokio::spawn(async move {
+            while let Some(message) = outgoing.recv().await {
+                if write_message(&mut writer, &message).await.is_err() {
+                    break;
+                }
+            }
+        });
+        let _ = sender.send(self.status());
+        let _ = sender.send(Message::GetPeers);
+        drop(sender);
+
+        let result = self.read_loop(&mut reader, &address).await;
+        self.unregister(&address, connection_id);
+        // Fail any request still waiting on this peer
+        self.pending_requests.lock().unwrap().remove(&address);
+        info!("Disconnected from peer {}", address);
+        result
+    }
+
+    /// Exchange `Hello` and `Auth` messages. Returns the peer's authenticated
+    /// address and announced listen address.
+    async fn handshake(
+        &self,
+        reader: &mut OwnedReadHalf,
+        writer: &mut OwnedWriteHalf,
+        remote: SocketAddr,
+    ) -> Result<(String, Option<SocketAddr>)> {
+        let nonce: [u8; 32] = rand::random();
+        write_message(
+            writer,
+            &Message::Hello {
+                version: PROTOCOL_VERSION,
+                address: self.key.address.clone(),
+                genesis_hash: self.genesis_hash.clone(),
+                listen_addr: self.local_addr().map(|a| a.to_string()),
+                nonce: nonce.to_vec(),
+            },
+        )
+        .await?;
+
+        let Message::Hello {
+            version,
+            address,
+            genesis_hash,
+            listen_addr,
+            nonce: peer_nonce,
+        } = read_message(reader).await?
+        else {
+            anyhow::bail!("Expected Hello from {}", remote);
+        };
+        if version != PROTOCOL_VERSION {
+            anyhow::bail!("Unsupported protocol version {}", version);
+        }
+        if genesis_hash != self.genesis_hash {
+            anyhow::bail!("Peer {} is on a different chain", address);
+        }
+        if address == self.key.address {
+            anyhow::bail!("Refusing connection to self");
+        }
+
+        let signature = self
+            .key
+            .sign(&handshake_payload(&peer_nonce, &self.key.address))?;
+        write_message(writer, &Message::Auth { signature }).await?;
+
+        let Message::Auth { signature } = read_message(reader).await? else {
+            anyhow::bail!("Expected Auth from {}", remote);
+        };
+        let valid = kanari_crypto::verify_signature(
+            &address,
+            &handshake_payload(&nonce, &address),
+            &signature,
+        )
+        .unwrap_or(false);
+        if !valid {
+            anyhow::bail!("Peer {} failed authentication", address);
+        }
+
+        // Peers bound to a wildcard address are reachable on the IP they connected from
+        let listen_addr = listen_addr
+            .and_then(|a| a.parse::<SocketAddr>().ok())
+            .map(|mut a| {
+                if a.ip().is_unspecified() {
+                    a.set_ip(remote.ip());
+                }
+                a
+            });
+        Ok((address, listen_addr))
+    }
+
+    fn register(&self, peer: Peer) -> Result<()> {
+        let mut peers = self.peers.write().unwrap();
+        if let Some(existing) = peers.get(&peer.info.address) {
+            // Both nodes dialed each other: both sides keep the connection opened by
+            // the lower address, so they agree on which one survives
+            if peer.initiator >= existing.initiator {
+                anyhow::bail!("Already connected to {}", peer.info.address);
+            }
+        } else if peers.len() >= self.config.max_peers {
+            anyhow::bail!("Peer limit of {} reached", self.config.max_peers);
+        }
+        peers.insert(peer.info.address.clone(), peer);
+        Ok(())
+    }
+
+    fn unregister(&self, address: &str, connection_id: u64) {
+        let mut peers = self.peers.write().unwrap();
+        if peers
+            .get(address)
+            .is_some_and(|p| p.connection_id == connection_id)
+        {
+            peers.remove(address);
+        }
+    }
+
+    fn reply(&self, peer: &str, message: Message) {
+        if let Some(p) = self.peers.read().unwrap().get(peer) {
+            let _ = p.sender.send(message);
+        }
+    }
+
+    async fn read_loop(self: &Arc<Self>, reader: &mut OwnedReadHalf, peer: &str) -> Result<()> {
+        loop {
+            match read_message(reader).await? {
+                // Verifying and executing run on the blocking pool, off the reactor
+                Message::Transaction(tx) => {
+                    if self.seen.lock().unwrap().insert(tx.hash()) {
+                        let engine = self.engine.clone();
+                        if let Err(e) =
+                            tokio::task::spawn_blocking(move || engine.submit_transaction(tx))
+                                .await?
+                        {
+                            debug!("Rejected transaction from {}: {}", peer, e);
+                        }
+                    }
+                }
+                Message::Block(block) => {
+                    if self.seen.lock().unwrap().insert(block.hash()) {
+                        let height = block.header.height;
+                        let engine = self.engine.clone();
+                        match tokio::task::spawn_blocking(move || engine.import_block(block))
+                            .await?
+                        {
+                            Ok(import) => {
+                                debug!("Block {} from {}: {:?}", height, peer, import);
+                                // A block beyond our tip means we missed some; fetch them
+                                if height > self.local_height() {
+                                    self.start_sync(peer);
+                                }
+                            }
+                            Err(e) => warn!("Rejected block {} from {}: {}", height, peer, e),
+                        }
+                    }
+                }
+                Message::GetStatus => self.reply(peer, self.status()),
+                Message::GetHeaders { from, limit } => {
+                    let mut headers = self.engine.get_blocks(from, limit.min(MAX_SYNC_BATCH));
+                    for header in &mut headers {
+                        header.prune_body();
+                    }
+                    self.reply(peer, Message::Headers(headers));
+                }
+                Message::GetBlocks { from, limit } => {
+                    let blocks = self.engine.get_blocks(from, limit.min(MAX_SYNC_BATCH));
+                    self.reply(peer, Message::Blocks(blocks));
+                }
+                message @ (Message::Status { .. } | Message::Headers(_) | Message::Blocks(_)) => {
+                    // Unsolicited status is sent on connect; catch up if the peer is ahead
+                    if let Some(Message::Status { height, .. }) =
+                        self.deliver_response(peer, message)
+                        && height > self.local_height()
+                    {
+                        self.start_sync(peer);
+                    }
+                }
+                Message::GetPeers => {
+                    let addrs = self
+                        .peers
+                        .read()
+                        .unwrap()
+                        .values()
+                        .filter(|p| p.info.address != peer)
+                        .filter_map(|p| p.info.listen_addr.map(|a| a.to_string()))
+                        .collect();
+                    self.reply(peer, Message::Peers(addrs));
+                }
+                Message::Peers(addrs) => {
+                    for addr in addrs.iter().filter_map(|a| a.parse::<SocketAddr>().ok()) {
+                        if self.peer_count() >= self.config.max_peers {
+                            break;
+                        }
+                        if self.is_connected_to(addr) || Some(addr) == self.local_addr() {
+                            continue;
+                        }
+                        let _ = self.dial_requests.send(addr);
+                    }
+                }
+                Message::Hello { .. } | Message::Auth { .. } => {
+                    anyhow::bail!("Unexpected handshake message from {}", peer);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{new_engine, produce_transfer, sign_as_dev};
+    use kanari_move_runtime::{SignedTransaction, Transaction};
+    use kanari_types::address::Address as KanariAddress;
+
+    async fn start_node(
+        dir: &tempfile::TempDir,
+        seeds: Vec<SocketAddr>,
+    ) -> (Arc<BlockchainEngine>, Arc<P2pNetwork>) {
+        let engine = new_engine(dir);
+        let config = P2pConfig::new("127.0.0.1:0".parse().unwrap()).with_seeds(seeds);
+        let network = P2pNetwork::new(engine.clone(), NodeKey::generate().unwrap(), config);
+        network.start().await.unwrap();
+        (engine, network)
+    }
+
+    async fn wait_until(mut condition: impl FnMut() -> bool) {
+        for _ in 0..200 {
+            if condition() {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        panic!("condition not reached in time");
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_gossip_across_three_nodes() {
+        let dirs: Vec<_> = (0..3).map(|_| tempfile::tempdir().unwrap()).collect();
+
+        // Line topology a <- b <- c; c learns about a through peer exchange
+        let (engine_a, net_a) = start_node(&dirs[0], vec![]).await;
+        let (_engine_b, net_b) = start_node(&dirs[1], vec![net_a.local_addr().unwrap()]).await;
+        let (engine_c, net_c) = start_node(&dirs[2], vec![net_b.local_addr().unwrap()]).await;
+
+        wait_until(|| net_b.peer_count() == 2).await;
+        wait_until(|| net_c.peer_count() == 2 && net_a.peer_count() == 2).await;
+        assert!(net_c.peers().iter().any(|p| p.address == net_a.address()));
+
+        // Fund a fresh account with a block produced on a
+        let key = generate_keypair(CurveType::Ed25519).unwrap();
+        let dev = KanariAddress::DEV_ADDRESS.to_string();
+        let dev_sequence = engine_a.get_account_info(&dev).unwrap().sequence_number;
+        engine_a
+            .pending_txs
+            .write()
+            .unwrap()
+            .push(sign_as_dev(Transaction::Transfer {
+                from: dev,
+                to: key.address.clone(),
+                amount: 1_000_000_000_000,
+                coin_type: None,
+                gas_limit: 100_000,
+                gas_price: 1000,
+                priority_fee: 0,
+                sequence_number: dev_sequence,
+            }));
+        engine_a.produce_block().unwrap();
+        wait_until(|| engine_c.blockchain.read().unwrap().height() == 1).await;
+
+        // Transaction submitted on a reaches c's mempool
+        let mut tx = SignedTransaction::new(Transaction::new_transfer(
+            key.address.clone(),
+            "0x42".to_string(),
+            10,
+        ));
+        tx.sign(&key.private_key, CurveType::Ed25519).unwrap();
+        engine_a.submit_transaction(tx).unwrap();
+        wait_until(|| engine_c.pending_txs.read().unwrap().len() == 1).await;
+
+        // Block produced on a is imported by c, clearing its mempool
+        engine_a.produce_block().unwrap();
+        wait_until(|| engine_c.blockchain.read().unwrap().height() == 2).await;
+        assert_eq!(
+            engine_c.blockchain.read().unwrap().latest_block().hash(),
+            engine_a.blockchain.read().unwrap().latest_block().hash()
+        );
+        assert!(engine_c.pending_txs.read().unwrap().is_empty());
+        assert_eq!(
+            engine_c.get_account_info("0x42").unwrap().balance,
+            engine_a.get_account_info("0x42").unwrap().balance
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_late_joiner_syncs_then_follows_gossip() {
+        let dirs: Vec<_> = (0..2).map(|_| tempfile::tempdir().unwrap()).collect();
+        let (engine_a, net_a) = start_node(&dirs[0], vec![]).await;
+        for amount in 1..=3 {
+            produce_transfer(&engine_a, amount);
+        }
+
+        let (engine_b, net_b) = start_node(&dirs[1], vec![net_a.local_addr().unwrap()]).await;
+        wait_until(|| engine_b.blockchain.read().unwrap().height() == 3).await;
+        wait_until(|| !net_b.is_syncing()).await;
+
+        produce_transfer(&engine_a, 4);
+        wait_until(|| engine_b.blockchain.read().unwrap().height() == 4).await;
+        assert_eq!(engine_b.compute_state_root(), engine_a.compute_state_root());
+    }
+
+    #[tokio::test]
+    async fn test_handshake_rejects_other_chain() {
+        let dir = tempfile::tempdir().unwrap();
+        let (_engine, network) = start_node(&dir, vec![]).await;
+
+        let stream = TcpStream::connect(network.local_addr().unwrap())
+            .await
+            .unwrap();
+        let (mut reader, mut writer) = stream.into_split();
+        write_message(
+            &mut writer,
+            &Message::Hello {
+                version: PROTOCOL_VERSION,
+                address: NodeKey::generate().unwrap().address,
+                genesis_hash: "00".repeat(32),
+                listen_addr: None,
+                nonce: vec![0; 32],
+            },
+        )
+        .await
+        .unwrap();
+
+        // The node sends its own Hello, then drops the connection
+        assert!(matches!(
+            read_message(&mut reader).await.unwrap(),
+            Message::Hello { .. }
+        ));
+        assert!(read_message(&mut reader).await.is_err());
+        assert_eq!(network.peer_count(), 0);
+    }
+}
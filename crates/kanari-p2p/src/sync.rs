@@ -0,0 +1,445 @@
+use crate::message::Message;
+use crate::network::P2pNetwork;
+use anyhow::{Context, Result};
+use kanari_move_runtime::{Block, BlockImport, BlockchainEngine};
+use kanari_rpc_api::RawBlockData;
+use kanari_rpc_client::RpcClient;
+use std::future::Future;
+use std::sync::Arc;
+use tracing::info;
+
+/// Headers requested per round trip
+pub const HEADER_BATCH: u64 = 128;
+/// Full blocks requested per round trip
+pub const BODY_BATCH: u64 = 32;
+/// Most blocks served for a single peer request
+pub const MAX_SYNC_BATCH: u64 = 256;
+
+/// Somewhere to download canonical blocks from: a connected peer or a node's RPC
+pub trait BlockSource {
+    /// Name used in logs and errors
+    fn name(&self) -> String;
+
+    fn tip_height(&self) -> impl Future<Output = Result<u64>> + Send;
+
+    /// Consecutive canonical blocks starting at `from`, with bodies pruned
+    fn headers(&self, from: u64, limit: u64) -> impl Future<Output = Result<Vec<Block>>> + Send;
+
+    /// Consecutive full canonical blocks starting at `from`
+    fn blocks(&self, from: u64, limit: u64) -> impl Future<Output = Result<Vec<Block>>> + Send;
+}
+
+/// Download blocks from a connected peer over the P2P protocol
+pub struct PeerSource {
+    network: Arc<P2pNetwork>,
+    peer: String,
+}
+
+impl PeerSource {
+    pub fn new(network: Arc<P2pNetwork>, peer: String) -> Self {
+        Self { network, peer }
+    }
+}
+
+impl BlockSource for PeerSource {
+    fn name(&self) -> String {
+        format!("peer {}", self.peer)
+    }
+
+    async fn tip_height(&self) -> Result<u64> {
+        match self.network.request(&self.peer, Message::GetStatus).await? {
+            Message::Status { height, .. } => Ok(height),
+            other => anyhow::bail!("Unexpected response to GetStatus: {:?}", other),
+        }
+    }
+
+    async fn headers(&self, from: u64, limit: u64) -> Result<Vec<Block>> {
+        match self
+            .network
+            .request(&self.peer, Message::GetHeaders { from, limit })
+            .await?
+        {
+            Message::Headers(headers) => Ok(headers),
+            other => anyhow::bail!("Unexpected response to GetHeaders: {:?}", other),
+        }
+    }
+
+    async fn blocks(&self, from: u64, limit: u64) -> Result<Vec<Block>> {
+        match self
+            .network
+            .request(&self.peer, Message::GetBlocks { from, limit })
+            .await?
+        {
+            Message::Blocks(blocks) => Ok(blocks),
+            other => anyhow::bail!("Unexpected response to GetBlocks: {:?}", other),
+        }
+    }
+}
+
+/// Download blocks from another node's JSON-RPC endpoint
+pub struct RpcSource {
+    client: RpcClient,
+    url: String,
+}
+
+impl RpcSource {
+    pub fn new(url: &str) -> Self {
+        Self {
+            client: RpcClient::new(url),
+            url: url.to_string(),
+        }
+    }
+}
+
+impl BlockSource for RpcSource {
+    fn name(&self) -> String {
+        format!("RPC {}", self.url)
+    }
+
+    async fn tip_height(&self) -> Result<u64> {
+        self.client.get_block_height().await
+    }
+
+    async fn headers(&self, from: u64, limit: u64) -> Result<Vec<Block>> {
+        let raw = self.client.get_headers(from, limit).await?;
+        raw.iter().map(decode_raw_block).collect()
+    }
+
+    async fn blocks(&self, from: u64, limit: u64) -> Result<Vec<Block>> {
+        let raw = self.client.get_blocks(from, limit).await?;
+        raw.iter().map(decode_raw_block).collect()
+    }
+}
+
+fn decode_raw_block(raw: &RawBlockData) -> Result<Block> {
+    let bytes = hex::decode(&raw.data).context("Invalid block hex")?;
+    let block: Block = bcs::from_bytes(&bytes).context("Invalid block encoding")?;
+    if hex::encode(block.hash()) != raw.hash {
+        anyhow::bail!("Block {} does not match its advertised hash", raw.height);
+    }
+    Ok(block)
+}
+
+/// Outcome of a sync run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SyncReport {
+    pub start_height: u64,
+    pub end_height: u64,
+    pub imported: usize,
+    pub reorgs: usize,
+}
+
+/// Catches a node up with a longer chain.
+///
+/// Each round finds the common ancestor with the source, downloads and checks the
+/// headers above it (height, parent linkage and, under proof-of-authority, producer
+/// signatures), then downloads the bodies in ranges and imports them through the
+/// engine, which re-executes every block and rejects any state root mismatch.
+/// Rounds repeat until the local tip reaches the source's tip, after which the node
+/// follows the chain through live gossip.
+pub struct ChainSync {
+    engine: Arc<BlockchainEngine>,
+}
+
+impl ChainSync {
+    pub fn new(engine: Arc<BlockchainEngine>) -> Self {
+        Self { engine }
+    }
+
+    fn local_height(&self) -> u64 {
+        self.engine.blockchain.read().unwrap().height()
+    }
+
+    pub async fn sync<S: BlockSource>(&self, source: &S) -> Result<SyncReport> {
+        let start_height = self.local_height();
+        let mut report = SyncReport {
+            start_height,
+            end_height: start_height,
+            imported: 0,
+            reorgs: 0,
+        };
+
+        loop {
+            let local = self.local_height();
+            let target = source.tip_height().await?;
+            if target <= local {
+                break;
+            }
+            info!(
+                "Syncing from {}: local height {}, target {}",
+                source.name(),
+                local,
+                target
+            );
+
+            let ancestor = self.find_common_ancestor(source, local).await?;
+            let hashes = self.fetch_headers(source, ancestor, target).await?;
+            self.fetch_bodies(source, ancestor, &hashes, &mut report)
+                .await?;
+
+            if self.local_height() <= local {
+                anyhow::bail!("Sync from {} made no progress", source.name());
+            }
+        }
+
+        report.end_height = self.local_height();
+        Ok(report)
+    }
+
+    /// Highest local canonical block that the source also has
+    async fn find_common_ancestor<S: BlockSource>(&self, source: &S, local: u64) -> Result<u64> {
+        let lower = {
+            let chain = self.engine.blockchain.read().unwrap();
+            let finalized = self
+                .engine
+                .block_tree
+                .read()
+                .unwrap()
+                .finalized_height(local);
+            finalized.max(chain.base_height())
+        };
+
+        // Usually the source simply extends our tip; otherwise search further back
+        for from in [local, local.saturating_sub(HEADER_BATCH - 1).max(lower)] {
+            let headers = source.headers(from, local - from + 1).await?;
+            let chain = self.engine.blockchain.read().unwrap();
+            for header in headers.iter().rev() {
+                let height = header.header.height;
+                if chain
+                    .get_block(height)
+                    .is_some_and(|ours| ours.hash() == header.hash())
+                {
+                    return Ok(height);
+                }
+            }
+        }
+        anyhow::bail!(
+            "{} does not share a chain with this node above height {}",
+            source.name(),
+            lower
+        )
+    }
+
+    /// Download headers for `ancestor + 1 ..= target` and check that they form a chain.
+    /// Returns the header hashes in height order.
+    async fn fetch_headers<S: BlockSource>(
+        &self,
+        source: &S,
+        ancestor: u64,
+        target: u64,
+    ) -> Result<Vec<Vec<u8>>> {
+        let (mut prev_hash, validators) = {
+            let chain = self.engine.blockchain.read().unwrap();
+            let ancestor_block = chain
+                .get_block(ancestor)
+                .context("Common ancestor is no longer available")?;
+            (ancestor_block.hash(), chain.validators.clone())
+        };
+
+        let mut hashes = Vec::new();
+        let mut next = ancestor + 1;
+        while next <= target {
+            let batch = source
+                .headers(next, HEADER_BATCH.min(target - next + 1))
+                .await?;
+            if batch.is_empty() {
+                anyhow::bail!("{} returned no headers from height {}", source.name(), next);
+            }
+            for header in batch.into_iter().take((target - next + 1) as usize) {
+                if header.header.height != next {
+                    anyhow::bail!(
+                        "{} returned header {} where {} was expected",
+                        source.name(),
+                        header.header.height,
+                        next
+                    );
+                }
+                if header.header.prev_hash != prev_hash {
+                    anyhow::bail!("Header {} does not link to its parent", next);
+                }
+                if let Some(validators) = &validators {
+                    header
+                        .verify_producer(validators)
+                        .with_context(|| format!("Header {} has an invalid producer", next))?;
+                }
+                prev_hash = header.hash();
+                hashes.push(prev_hash.clone());
+                next += 1;
+            }
+        }
+        Ok(hashes)
+    }
+
+    /// Download and import the bodies for previously verified headers
+    async fn fetch_bodies<S: BlockSource>(
+        &self,
+        source: &S,
+        ancestor: u64,
+        hashes: &[Vec<u8>],
+        report: &mut SyncReport,
+    ) -> Result<()> {
+        let end = ancestor + hashes.len() as u64;
+        let mut height = ancestor + 1;
+        while height <= end {
+            let batch = source
+                .blocks(height, BODY_BATCH.min(end - height + 1))
+                .await?;
+            if batch.is_empty() {
+                anyhow::bail!(
+                    "{} returned no blocks from height {}",
+                    source.name(),
+                    height
+                );
+            }
+            for block in batch.into_iter().take((end - height + 1) as usize) {
+                let expected = &hashes[(height - ancestor - 1) as usize];
+                if block.header.height != height || &block.hash() != expected {
+                    anyhow::bail!("Block {} does not match its header", height);
+                }
+                if block.body_pruned {
+                    anyhow::bail!("{} has pruned the body of block {}", source.name(), height);
+                }
+
+                let engine = self.engine.clone();
+                let import = tokio::task::spawn_blocking(move || engine.import_block(block))
+                    .await?
+                    .with_context(|| format!("Failed to import block {}", height))?;
+                match import {
+                    BlockImport::Known => {}
+                    BlockImport::Reorg { .. } => {
+                        report.imported += 1;
+                        report.reorgs += 1;
+                    }
+                    _ => report.imported += 1,
+                }
+                height += 1;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{new_engine, produce_transfer};
+
+    /// Serves another engine's chain directly, optionally corrupting one block
+    struct LocalSource {
+        engine: Arc<BlockchainEngine>,
+        tamper_height: Option<u64>,
+    }
+
+    impl LocalSource {
+        fn serve(&self, from: u64, limit: u64, headers_only: bool) -> Vec<Block> {
+            let mut blocks = self.engine.get_blocks(from, limit);
+            for block in &mut blocks {
+                if Some(block.header.height) == self.tamper_height && !headers_only {
+                    // Same header, different body
+                    block.transactions.clear();
+                }
+                if headers_only {
+                    block.prune_body();
+                }
+            }
+            blocks
+        }
+    }
+
+    impl BlockSource for LocalSource {
+        fn name(&self) -> String {
+            "local".to_string()
+        }
+
+        async fn tip_height(&self) -> Result<u64> {
+            Ok(self.engine.blockchain.read().unwrap().height())
+        }
+
+        async fn headers(&self, from: u64, limit: u64) -> Result<Vec<Block>> {
+            Ok(self.serve(from, limit, true))
+        }
+
+        async fn blocks(&self, from: u64, limit: u64) -> Result<Vec<Block>> {
+            Ok(self.serve(from, limit, false))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sync_catches_up_and_handles_forks() {
+        let dirs: Vec<_> = (0..2).map(|_| tempfile::tempdir().unwrap()).collect();
+        let leader = new_engine(&dirs[0]);
+        let follower = new_engine(&dirs[1]);
+        for amount in 1..=5 {
+            produce_transfer(&leader, amount);
+        }
+        // The follower has a competing, shorter branch
+        produce_transfer(&follower, 100);
+
+        let source = LocalSource {
+            engine: leader.clone(),
+            tamper_height: None,
+        };
+        let report = ChainSync::new(follower.clone())
+            .sync(&source)
+            .await
+            .unwrap();
+        assert_eq!(report.start_height, 1);
+        assert_eq!(report.end_height, 5);
+        assert_eq!(report.reorgs, 1);
+        assert_eq!(
+            follower.blockchain.read().unwrap().latest_block().hash(),
+            leader.blockchain.read().unwrap().latest_block().hash()
+        );
+        assert_eq!(follower.compute_state_root(), leader.compute_state_root());
+
+        // Already caught up
+        let report = ChainSync::new(follower.clone())
+            .sync(&source)
+            .await
+            .unwrap();
+        assert_eq!(report.imported, 0);
+    }
+
+    #[tokio::test]
+    async fn test_sync_rejects_body_not_matching_header() {
+        let dirs: Vec<_> = (0..2).map(|_| tempfile::tempdir().unwrap()).collect();
+        let leader = new_engine(&dirs[0]);
+        let follower = new_engine(&dirs[1]);
+        for amount in 1..=3 {
+            produce_transfer(&leader, amount);
+        }
+
+        let source = LocalSource {
+            engine: leader.clone(),
+            tamper_height: Some(2),
+        };
+        assert!(
+            ChainSync::new(follower.clone())
+                .sync(&source)
+                .await
+                .is_err()
+        );
+        // Blocks before the bad one are kept
+        assert_eq!(follower.blockchain.read().unwrap().height(), 1);
+    }
+
+    #[test]
+    fn test_decode_raw_block() {
+        let dir = tempfile::tempdir().unwrap();
+        let engine = new_engine(&dir);
+        produce_transfer(&engine, 7);
+        let block = engine.get_blocks(1, 1).remove(0);
+
+        let mut raw = RawBlockData {
+            height: 1,
+            hash: hex::encode(block.hash()),
+            data: hex::encode(bcs::to_bytes(&block).unwrap()),
+        };
+        let decoded = decode_raw_block(&raw).unwrap();
+        assert_eq!(decoded.hash(), block.hash());
+        assert_eq!(decoded.transactions.len(), 1);
+
+        raw.hash = "00".repeat(32);
+        assert!(decode_raw_block(&raw).is_err());
+    }
+}
@@ -66,6 +66,24 @@ pub struct AccountInfo {
     pub balance: u64,
     pub sequence_number: u64,
     pub modules: Vec<String>,
+    /// Hex authentication key, if the account rotated its key
+    #[serde(default)]
+    pub authentication_key: Option<String>,
+    /// Every non-zero coin balance, KANARI included
+    #[serde(default)]
+    pub balances: Vec<CoinBalance>,
+}
+
+/// Balance of one coin type, with the symbol and decimals from its
+/// `CoinMetadata` when the currency registered one
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoinBalance {
+    /// Coin type, e.g. `0x2::kanari::KANARI`
+    pub coin_type: String,
+    pub balance: u64,
+    pub symbol: Option<String>,
+    pub name: Option<String>,
+    pub decimals: Option<u8>,
 }
 
 /// Block info response
@@ -77,6 +95,14 @@ pub struct BlockInfo {
     pub prev_hash: String,
     pub tx_count: usize,
     pub state_root: String,
+    /// Validator that signed the block, when proof-of-authority is enabled
+    #[serde(default)]
+    pub producer: Option<String>,
+    /// Fee per gas unit burned by the block's transactions
+    #[serde(default)]
+    pub base_fee: u64,
+    #[serde(default)]
+    pub gas_used: u64,
     pub events: Vec<RpcEvent>,
 }
 
@@ -87,6 +113,9 @@ pub struct RpcEvent {
     pub sequence_number: u64,
     pub type_tag: String,
     pub event_data: Vec<u8>,
+    /// `event_data` decoded with the layout of `type_tag`; `null` if the type is unknown
+    #[serde(default)]
+    pub parsed_json: serde_json::Value,
 }
 
 /// Transaction status
@@ -98,6 +127,100 @@ pub struct TransactionStatus {
     pub gas_used: Option<u64>,
 }
 
+/// Outcome and gas settlement of a transaction included in a block
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransactionReceipt {
+    pub tx_hash: String,
+    pub block_height: u64,
+    pub success: bool,
+    pub error_message: Option<String>,
+    pub gas_limit: u64,
+    /// Maximum fee per gas unit the sender authorized
+    pub gas_price: u64,
+    pub gas_used: u64,
+    /// Base fee of the block, burned per gas unit
+    pub base_fee: u64,
+    /// Price per gas unit actually paid: base fee plus tip
+    pub effective_gas_price: u64,
+    /// Mist charged to the sender, `gas_used * effective_gas_price`
+    pub gas_charged: u64,
+    /// Mist of the charge burned, `gas_used * base_fee`
+    pub gas_burned: u64,
+    /// Mist of the pre-authorized `gas_limit * gas_price` left with the sender
+    pub gas_refunded: u64,
+}
+
+/// Governance proposal and its tally
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProposalInfo {
+    pub id: u64,
+    pub proposer: String,
+    /// `TreasurySpend`, `ChangeParameters` or `UpgradeFramework` action
+    pub action: serde_json::Value,
+    /// Last block at which votes are accepted
+    pub voting_end_height: u64,
+    pub yes_votes: u64,
+    pub no_votes: u64,
+    pub voters: Vec<String>,
+    /// `Active`, `Rejected`, `Executed` or `Failed`
+    pub status: String,
+}
+
+/// Object from the object store
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObjectInfo {
+    pub id: String,
+    /// Owning address; `None` for shared and immutable objects
+    pub owner: Option<String>,
+    /// `Address`, `Shared` or `Immutable`
+    pub ownership: String,
+    pub type_tag: String,
+    /// Object fields, or `null` if its type could not be resolved
+    pub json: serde_json::Value,
+}
+
+/// NFT from the object store, with the collection it was minted in
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NftInfo {
+    pub id: String,
+    /// Owning address; `None` for shared and immutable NFTs
+    pub owner: Option<String>,
+    pub collection_id: String,
+    /// Position in the collection's mint order, starting at 1
+    pub number: u64,
+    pub name: String,
+    pub description: String,
+    pub url: String,
+    pub attributes: Vec<NftAttribute>,
+    /// `None` if the collection is not in the object store
+    pub collection: Option<CollectionInfo>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NftAttribute {
+    pub key: String,
+    pub value: String,
+}
+
+/// NFT collection
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollectionInfo {
+    pub id: String,
+    pub creator: String,
+    pub name: String,
+    pub description: String,
+    pub url: String,
+    /// Most NFTs the collection can ever mint; 0 for no cap
+    pub max_supply: u64,
+    /// NFTs minted so far, burned ones included
+    pub minted: u64,
+    /// NFTs minted and not burned
+    pub supply: u64,
+    /// Royalty owed to `royalty_recipient` on sales, in basis points
+    pub royalty_bps: u16,
+    pub royalty_recipient: String,
+}
+
 /// Blockchain statistics
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BlockchainStats {
@@ -121,10 +244,32 @@ pub struct SignedTransactionData {
     pub sender: String,
     pub recipient: Option<String>,
     pub amount: Option<u64>,
+    /// Coin type of a transfer, e.g. `0x42::usd::USD`; KANARI when omitted
+    #[serde(default)]
+    pub coin_type: Option<String>,
     pub gas_limit: u64,
+    /// Maximum fee per gas unit, covering the base fee and the tip
     pub gas_price: u64,
+    /// Tip per gas unit for the block producer
+    #[serde(default)]
+    pub priority_fee: u64,
     pub sequence_number: u64,
     pub signature: Option<Vec<u8>>,
+    /// Hex public key of the signer, required with a signature
+    #[serde(default)]
+    pub public_key: Option<String>,
+    /// Signature scheme flag the transaction was signed with, required with a signature
+    #[serde(default)]
+    pub scheme: Option<u8>,
+    /// Hex authentication key to rotate to; makes this a key rotation transaction
+    #[serde(default)]
+    pub new_authentication_key: Option<String>,
+    /// Staking action (`Stake`, `Unstake` or `Withdraw`); makes this a staking transaction
+    #[serde(default)]
+    pub staking: Option<serde_json::Value>,
+    /// Governance action (`Propose` or `Vote`); makes this a governance transaction
+    #[serde(default)]
+    pub governance: Option<serde_json::Value>,
 }
 
 /// Publish module request
@@ -134,9 +279,19 @@ pub struct PublishModuleRequest {
     pub module_bytes: Vec<u8>,
     pub module_name: String,
     pub gas_limit: u64,
+    /// Maximum fee per gas unit, covering the base fee and the tip
     pub gas_price: u64,
+    /// Tip per gas unit for the block producer
+    #[serde(default)]
+    pub priority_fee: u64,
     pub sequence_number: u64,
     pub signature: Option<Vec<u8>>,
+    /// Hex public key of the signer, required with a signature
+    #[serde(default)]
+    pub public_key: Option<String>,
+    /// Signature scheme flag the transaction was signed with, required with a signature
+    #[serde(default)]
+    pub scheme: Option<u8>,
 }
 
 /// Call function request
@@ -148,10 +303,72 @@ pub struct CallFunctionRequest {
     pub function: String,
     pub type_args: Vec<String>,
     pub args: Vec<Vec<u8>>,
+    /// IDs of the objects passed as the function's leading parameters
+    #[serde(default)]
+    pub objects: Vec<String>,
     pub gas_limit: u64,
+    /// Maximum fee per gas unit, covering the base fee and the tip
     pub gas_price: u64,
+    /// Tip per gas unit for the block producer
+    #[serde(default)]
+    pub priority_fee: u64,
     pub sequence_number: u64,
     pub signature: Option<Vec<u8>>,
+    /// Hex public key of the signer, required with a signature
+    #[serde(default)]
+    pub public_key: Option<String>,
+    /// Signature scheme flag the transaction was signed with, required with a signature
+    #[serde(default)]
+    pub scheme: Option<u8>,
+}
+
+/// Read-only call of a view function; no transaction is created
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ViewFunctionRequest {
+    pub package: String,
+    pub module: String,
+    pub function: String,
+    pub type_args: Vec<String>,
+    /// BCS-encoded arguments
+    pub args: Vec<Vec<u8>>,
+    /// Execute against the state at this height instead of the latest
+    #[serde(default)]
+    pub height: Option<u64>,
+}
+
+/// View function result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ViewFunctionResult {
+    pub height: u64,
+    pub return_values: Vec<ViewReturnValue>,
+}
+
+/// Value returned by a view function
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ViewReturnValue {
+    pub type_tag: String,
+    /// Hex-encoded BCS
+    pub bcs: String,
+    /// Value decoded according to `type_tag`
+    pub json: serde_json::Value,
+}
+
+/// Request for a single resource stored under an account
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetResourceRequest {
+    pub address: String,
+    /// e.g. `0x2::coin::CoinMetadata<0x2::kanari::KANARI>`
+    pub struct_tag: String,
+}
+
+/// Move resource with its decoded value
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceData {
+    pub struct_tag: String,
+    /// Hex-encoded BCS
+    pub bcs: String,
+    /// Value decoded with the layout of the published module; `null` if unavailable
+    pub json: serde_json::Value,
 }
 
 /// Contract info response
@@ -162,22 +379,99 @@ pub struct ContractInfo {
     pub version: String,
     pub author: String,
     pub functions: Vec<String>,
+    /// Full ABI; only included by `kanari_getContract`
+    #[serde(default)]
+    pub abi: Option<ContractAbi>,
+}
+
+/// Contract ABI derived from the module bytecode
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContractAbi {
+    pub functions: Vec<AbiFunction>,
+    pub structs: Vec<AbiStruct>,
+}
+
+/// Public or entry function signature
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AbiFunction {
+    pub name: String,
+    pub is_entry: bool,
+    pub visibility: String,
+    /// Type parameters with constraints, e.g. `T0: copy + drop`
+    pub type_params: Vec<String>,
+    pub parameters: Vec<AbiField>,
+    pub returns: Vec<String>,
+}
+
+/// Struct layout with abilities
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AbiStruct {
+    pub name: String,
+    pub type_params: Vec<String>,
+    pub abilities: Vec<String>,
+    pub fields: Vec<AbiField>,
+}
+
+/// Named, typed function parameter or struct field
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AbiField {
+    pub name: String,
+    pub type_name: String,
+}
+
+/// Exported state snapshot (compressed BCS, hex encoded)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotData {
+    pub height: u64,
+    pub state_root: String,
+    pub data: String,
+}
+
+/// Range of canonical blocks requested for sync
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockRangeRequest {
+    pub from: u64,
+    pub limit: u64,
+}
+
+/// Full block (or header only, with its body pruned) as hex-encoded BCS
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RawBlockData {
+    pub height: u64,
+    pub hash: String,
+    pub data: String,
 }
 
 /// RPC Methods
 pub mod methods {
     pub const GET_ACCOUNT: &str = "kanari_getAccount";
     pub const GET_BALANCE: &str = "kanari_getBalance";
+    pub const GET_BALANCES: &str = "kanari_getBalances";
     pub const GET_BLOCK: &str = "kanari_getBlock";
     pub const GET_BLOCK_HEIGHT: &str = "kanari_getBlockHeight";
     pub const GET_TRANSACTION: &str = "kanari_getTransaction";
+    pub const GET_TRANSACTION_RECEIPT: &str = "kanari_getTransactionReceipt";
     pub const SUBMIT_TRANSACTION: &str = "kanari_submitTransaction";
     pub const GET_STATS: &str = "kanari_getStats";
+    pub const GET_RESOURCES: &str = "kanari_getResources";
+    pub const GET_RESOURCE: &str = "kanari_getResource";
     pub const ESTIMATE_GAS: &str = "kanari_estimateGas";
+    pub const GET_PROPOSALS: &str = "kanari_getProposals";
+    pub const GET_PROPOSAL: &str = "kanari_getProposal";
+    pub const GET_OWNED_OBJECTS: &str = "kanari_getOwnedObjects";
+    pub const GET_OBJECT: &str = "kanari_getObject";
+    pub const GET_OWNED_NFTS: &str = "kanari_getOwnedNfts";
+    pub const GET_NFT: &str = "kanari_getNft";
 
     // Contract operations
     pub const PUBLISH_MODULE: &str = "kanari_publishModule";
     pub const CALL_FUNCTION: &str = "kanari_callFunction";
     pub const GET_CONTRACT: &str = "kanari_getContract";
     pub const LIST_CONTRACTS: &str = "kanari_listContracts";
+    pub const VIEW: &str = "kanari_view";
+
+    // Node operations
+    pub const EXPORT_SNAPSHOT: &str = "kanari_exportSnapshot";
+    pub const GET_HEADERS: &str = "kanari_getHeaders";
+    pub const GET_BLOCKS: &str = "kanari_getBlocks";
 }
@@ -106,6 +106,86 @@ impl RpcClient {
         serde_json::from_value(result).context("Failed to parse stats")
     }
 
+    /// Every coin balance of an account, with each currency's metadata
+    pub async fn get_balances(&self, address: &str) -> Result<Vec<CoinBalance>> {
+        let response = self
+            .request(methods::GET_BALANCES, serde_json::json!(address))
+            .await?;
+
+        let result = response.result.context("No result in response")?;
+        serde_json::from_value(result).context("Failed to parse balances")
+    }
+
+    /// Receipt of an included transaction, by hex transaction hash
+    pub async fn get_transaction_receipt(&self, tx_hash: &str) -> Result<TransactionReceipt> {
+        let response = self
+            .request(methods::GET_TRANSACTION_RECEIPT, serde_json::json!(tx_hash))
+            .await?;
+
+        let result = response.result.context("No result in response")?;
+        serde_json::from_value(result).context("Failed to parse transaction receipt")
+    }
+
+    /// All governance proposals, open and closed
+    pub async fn get_proposals(&self) -> Result<Vec<ProposalInfo>> {
+        let response = self
+            .request(methods::GET_PROPOSALS, serde_json::Value::Null)
+            .await?;
+
+        let result = response.result.context("No result in response")?;
+        serde_json::from_value(result).context("Failed to parse proposals")
+    }
+
+    /// Governance proposal by id
+    pub async fn get_proposal(&self, id: u64) -> Result<ProposalInfo> {
+        let response = self
+            .request(methods::GET_PROPOSAL, serde_json::json!(id))
+            .await?;
+
+        let result = response.result.context("No result in response")?;
+        serde_json::from_value(result).context("Failed to parse proposal")
+    }
+
+    /// Objects owned by an address
+    pub async fn get_owned_objects(&self, address: &str) -> Result<Vec<ObjectInfo>> {
+        let response = self
+            .request(methods::GET_OWNED_OBJECTS, serde_json::json!(address))
+            .await?;
+
+        let result = response.result.context("No result in response")?;
+        serde_json::from_value(result).context("Failed to parse objects")
+    }
+
+    /// Object by id
+    pub async fn get_object(&self, id: &str) -> Result<ObjectInfo> {
+        let response = self
+            .request(methods::GET_OBJECT, serde_json::json!(id))
+            .await?;
+
+        let result = response.result.context("No result in response")?;
+        serde_json::from_value(result).context("Failed to parse object")
+    }
+
+    /// NFTs owned by an address
+    pub async fn get_owned_nfts(&self, address: &str) -> Result<Vec<NftInfo>> {
+        let response = self
+            .request(methods::GET_OWNED_NFTS, serde_json::json!(address))
+            .await?;
+
+        let result = response.result.context("No result in response")?;
+        serde_json::from_value(result).context("Failed to parse NFTs")
+    }
+
+    /// NFT by id
+    pub async fn get_nft(&self, id: &str) -> Result<NftInfo> {
+        let response = self
+            .request(methods::GET_NFT, serde_json::json!(id))
+            .await?;
+
+        let result = response.result.context("No result in response")?;
+        serde_json::from_value(result).context("Failed to parse NFT")
+    }
+
     /// Submit signed transaction
     pub async fn submit_transaction(&self, tx: SignedTransactionData) -> Result<TransactionStatus> {
         let response = self
@@ -179,6 +259,78 @@ impl RpcClient {
         let result = response.result.context("No result in response")?;
         serde_json::from_value(result).context("Failed to parse contracts list")
     }
+
+    /// Export a compressed state snapshot at the given height
+    pub async fn export_snapshot(&self, height: u64) -> Result<SnapshotData> {
+        let response = self
+            .request(methods::EXPORT_SNAPSHOT, serde_json::json!(height))
+            .await?;
+
+        let result = response.result.context("No result in response")?;
+        serde_json::from_value(result).context("Failed to parse snapshot")
+    }
+
+    /// Headers (blocks with bodies pruned) for sync, starting at `from`
+    pub async fn get_headers(&self, from: u64, limit: u64) -> Result<Vec<RawBlockData>> {
+        let response = self
+            .request(
+                methods::GET_HEADERS,
+                serde_json::to_value(BlockRangeRequest { from, limit })?,
+            )
+            .await?;
+
+        let result = response.result.context("No result in response")?;
+        serde_json::from_value(result).context("Failed to parse headers")
+    }
+
+    /// All Move resources stored under an account
+    pub async fn get_resources(&self, address: &str) -> Result<Vec<ResourceData>> {
+        let response = self
+            .request(methods::GET_RESOURCES, serde_json::json!(address))
+            .await?;
+
+        let result = response.result.context("No result in response")?;
+        serde_json::from_value(result).context("Failed to parse resources")
+    }
+
+    /// A single Move resource, e.g. `0x2::coin::CoinMetadata<0x2::kanari::KANARI>`
+    pub async fn get_resource(&self, address: &str, struct_tag: &str) -> Result<ResourceData> {
+        let response = self
+            .request(
+                methods::GET_RESOURCE,
+                serde_json::to_value(GetResourceRequest {
+                    address: address.to_string(),
+                    struct_tag: struct_tag.to_string(),
+                })?,
+            )
+            .await?;
+
+        let result = response.result.context("No result in response")?;
+        serde_json::from_value(result).context("Failed to parse resource")
+    }
+
+    /// Call a view function read-only, at the latest height unless `request.height` is set
+    pub async fn view(&self, request: ViewFunctionRequest) -> Result<ViewFunctionResult> {
+        let response = self
+            .request(methods::VIEW, serde_json::to_value(request)?)
+            .await?;
+
+        let result = response.result.context("No result in response")?;
+        serde_json::from_value(result).context("Failed to parse view result")
+    }
+
+    /// Full blocks for sync, starting at `from`
+    pub async fn get_blocks(&self, from: u64, limit: u64) -> Result<Vec<RawBlockData>> {
+        let response = self
+            .request(
+                methods::GET_BLOCKS,
+                serde_json::to_value(BlockRangeRequest { from, limit })?,
+            )
+            .await?;
+
+        let result = response.result.context("No result in response")?;
+        serde_json::from_value(result).context("Failed to parse blocks")
+    }
 }
 
 #[cfg(test)]
@@ -6,10 +6,14 @@ use anyhow::Result;
 use axum::{Json, Router, extract::State, http::StatusCode, response::IntoResponse, routing::post};
 use kanari_move_runtime::BlockchainEngine;
 use kanari_rpc_api::*;
+use move_core_types::parser::parse_type_tag;
 use std::sync::Arc;
 use tower_http::cors::{Any, CorsLayer};
 use tracing::{error, info};
 
+/// Most blocks returned by a single `kanari_getHeaders` / `kanari_getBlocks` call
+pub const MAX_BLOCK_RANGE: u64 = 256;
+
 /// RPC server state
 #[derive(Clone)]
 pub struct RpcServerState {
@@ -46,14 +50,28 @@ async fn handle_rpc(
     let response = match request.method.as_str() {
         methods::GET_ACCOUNT => handle_get_account(&state, &request).await,
         methods::GET_BALANCE => handle_get_balance(&state, &request).await,
+        methods::GET_BALANCES => handle_get_balances(&state, &request).await,
         methods::GET_BLOCK => handle_get_block(&state, &request).await,
         methods::GET_BLOCK_HEIGHT => handle_get_block_height(&state, &request).await,
         methods::GET_STATS => handle_get_stats(&state, &request).await,
+        methods::GET_TRANSACTION_RECEIPT => handle_get_transaction_receipt(&state, &request).await,
         methods::SUBMIT_TRANSACTION => handle_submit_transaction(&state, &request).await,
         methods::PUBLISH_MODULE => handle_publish_module(&state, &request).await,
         methods::CALL_FUNCTION => handle_call_function(&state, &request).await,
         methods::GET_CONTRACT => handle_get_contract(&state, &request).await,
         methods::LIST_CONTRACTS => handle_list_contracts(&state, &request).await,
+        methods::VIEW => handle_view(&state, &request).await,
+        methods::GET_RESOURCES => handle_get_resources(&state, &request).await,
+        methods::GET_RESOURCE => handle_get_resource(&state, &request).await,
+        methods::EXPORT_SNAPSHOT => handle_export_snapshot(&state, &request).await,
+        methods::GET_HEADERS => handle_get_block_range(&state, &request, true).await,
+        methods::GET_BLOCKS => handle_get_block_range(&state, &request, false).await,
+        methods::GET_PROPOSALS => handle_get_proposals(&state, &request).await,
+        methods::GET_PROPOSAL => handle_get_proposal(&state, &request).await,
+        methods::GET_OWNED_OBJECTS => handle_get_owned_objects(&state, &request).await,
+        methods::GET_OBJECT => handle_get_object(&state, &request).await,
+        methods::GET_OWNED_NFTS => handle_get_owned_nfts(&state, &request).await,
+        methods::GET_NFT => handle_get_nft(&state, &request).await,
         _ => RpcResponse {
             jsonrpc: "2.0".to_string(),
             result: None,
@@ -86,6 +104,8 @@ async fn handle_get_account(state: &RpcServerState, request: &RpcRequest) -> Rpc
                 balance: info.balance,
                 sequence_number: info.sequence_number,
                 modules: info.modules,
+                authentication_key: info.authentication_key,
+                balances: info.balances.into_iter().map(coin_balance).collect(),
             };
             RpcResponse {
                 jsonrpc: "2.0".to_string(),
@@ -133,6 +153,44 @@ async fn handle_get_balance(state: &RpcServerState, request: &RpcRequest) -> Rpc
     }
 }
 
+fn coin_balance(balance: kanari_move_runtime::CoinBalance) -> CoinBalance {
+    CoinBalance {
+        coin_type: balance.coin_type,
+        balance: balance.balance,
+        symbol: balance.symbol,
+        name: balance.name,
+        decimals: balance.decimals,
+    }
+}
+
+/// Handle get balances request: every coin the account holds
+async fn handle_get_balances(state: &RpcServerState, request: &RpcRequest) -> RpcResponse {
+    let address: String = match serde_json::from_value(request.params.clone()) {
+        Ok(addr) => addr,
+        Err(e) => {
+            return RpcResponse {
+                jsonrpc: "2.0".to_string(),
+                result: None,
+                error: Some(RpcError::invalid_params(e.to_string())),
+                id: request.id,
+            };
+        }
+    };
+
+    let balances: Vec<CoinBalance> = state
+        .engine
+        .get_balances(&address)
+        .into_iter()
+        .map(coin_balance)
+        .collect();
+    RpcResponse {
+        jsonrpc: "2.0".to_string(),
+        result: Some(serde_json::to_value(balances).unwrap()),
+        error: None,
+        id: request.id,
+    }
+}
+
 /// Handle get block request
 async fn handle_get_block(state: &RpcServerState, request: &RpcRequest) -> RpcResponse {
     let height: u64 = match serde_json::from_value(request.params.clone()) {
@@ -154,6 +212,7 @@ async fn handle_get_block(state: &RpcServerState, request: &RpcRequest) -> RpcRe
                 .events
                 .into_iter()
                 .map(|e| RpcEvent {
+                    parsed_json: state.engine.decode_event(&e),
                     key: e.key,
                     sequence_number: e.sequence_number,
                     type_tag: e.type_tag,
@@ -167,7 +226,10 @@ async fn handle_get_block(state: &RpcServerState, request: &RpcRequest) -> RpcRe
                 hash: block.hash.clone(),
                 prev_hash: block.prev_hash,
                 tx_count: block.tx_count,
-                state_root: hex::encode(&block.hash), // Use block hash as state root placeholder
+                state_root: block.state_root,
+                producer: block.producer,
+                base_fee: block.base_fee,
+                gas_used: block.gas_used,
                 events: rpc_events,
             };
             RpcResponse {
@@ -216,9 +278,342 @@ async fn handle_get_stats(state: &RpcServerState, request: &RpcRequest) -> RpcRe
     }
 }
 
+/// Handle get transaction receipt request
+fn proposal_info(proposal: &kanari_move_runtime::Proposal) -> ProposalInfo {
+    ProposalInfo {
+        id: proposal.id,
+        proposer: proposal.proposer.to_hex_literal(),
+        action: proposal
+            .action()
+            .ok()
+            .and_then(|action| serde_json::to_value(action).ok())
+            .unwrap_or(serde_json::Value::Null),
+        voting_end_height: proposal.voting_end_height,
+        yes_votes: proposal.yes_votes,
+        no_votes: proposal.no_votes,
+        voters: proposal
+            .voters
+            .iter()
+            .map(|voter| voter.to_hex_literal())
+            .collect(),
+        status: format!("{:?}", proposal.status),
+    }
+}
+
+async fn handle_get_proposals(state: &RpcServerState, request: &RpcRequest) -> RpcResponse {
+    match state.engine.governance() {
+        Ok(governance) => {
+            let proposals: Vec<ProposalInfo> =
+                governance.proposals.iter().map(proposal_info).collect();
+            RpcResponse {
+                jsonrpc: "2.0".to_string(),
+                result: Some(serde_json::to_value(proposals).unwrap()),
+                error: None,
+                id: request.id,
+            }
+        }
+        Err(e) => RpcResponse {
+            jsonrpc: "2.0".to_string(),
+            result: None,
+            error: Some(RpcError::internal_error(e.to_string())),
+            id: request.id,
+        },
+    }
+}
+
+async fn handle_get_proposal(state: &RpcServerState, request: &RpcRequest) -> RpcResponse {
+    let id: u64 = match serde_json::from_value(request.params.clone()) {
+        Ok(id) => id,
+        Err(e) => {
+            return RpcResponse {
+                jsonrpc: "2.0".to_string(),
+                result: None,
+                error: Some(RpcError::invalid_params(e.to_string())),
+                id: request.id,
+            };
+        }
+    };
+
+    let proposal = state
+        .engine
+        .governance()
+        .map(|governance| governance.get(id).map(proposal_info));
+    match proposal {
+        Ok(Some(proposal)) => RpcResponse {
+            jsonrpc: "2.0".to_string(),
+            result: Some(serde_json::to_value(proposal).unwrap()),
+            error: None,
+            id: request.id,
+        },
+        Ok(None) => RpcResponse {
+            jsonrpc: "2.0".to_string(),
+            result: None,
+            error: Some(RpcError::internal_error(format!(
+                "Proposal {} not found",
+                id
+            ))),
+            id: request.id,
+        },
+        Err(e) => RpcResponse {
+            jsonrpc: "2.0".to_string(),
+            result: None,
+            error: Some(RpcError::internal_error(e.to_string())),
+            id: request.id,
+        },
+    }
+}
+
+fn object_info(object: kanari_move_runtime::ObjectInfo) -> ObjectInfo {
+    ObjectInfo {
+        id: object.id,
+        owner: object.owner,
+        ownership: format!("{:?}", object.ownership),
+        type_tag: object.type_tag,
+        json: object.json,
+    }
+}
+
+async fn handle_get_owned_objects(state: &RpcServerState, request: &RpcRequest) -> RpcResponse {
+    let address: String = match serde_json::from_value(request.params.clone()) {
+        Ok(address) => address,
+        Err(e) => {
+            return RpcResponse {
+                jsonrpc: "2.0".to_string(),
+                result: None,
+                error: Some(RpcError::invalid_params(e.to_string())),
+                id: request.id,
+            };
+        }
+    };
+
+    match state.engine.get_owned_objects(&address) {
+        Ok(objects) => {
+            let objects: Vec<ObjectInfo> = objects.into_iter().map(object_info).collect();
+            RpcResponse {
+                jsonrpc: "2.0".to_string(),
+                result: Some(serde_json::to_value(objects).unwrap()),
+                error: None,
+                id: request.id,
+            }
+        }
+        Err(e) => RpcResponse {
+            jsonrpc: "2.0".to_string(),
+            result: None,
+            error: Some(RpcError::invalid_params(e.to_string())),
+            id: request.id,
+        },
+    }
+}
+
+async fn handle_get_object(state: &RpcServerState, request: &RpcRequest) -> RpcResponse {
+    let id: String = match serde_json::from_value(request.params.clone()) {
+        Ok(id) => id,
+        Err(e) => {
+            return RpcResponse {
+                jsonrpc: "2.0".to_string(),
+                result: None,
+                error: Some(RpcError::invalid_params(e.to_string())),
+                id: request.id,
+            };
+        }
+    };
+
+    match state.engine.get_object(&id) {
+        Ok(Some(object)) => RpcResponse {
+            jsonrpc: "2.0".to_string(),
+            result: Some(serde_json::to_value(object_info(object)).unwrap()),
+            error: None,
+            id: request.id,
+        },
+        Ok(None) => RpcResponse {
+            jsonrpc: "2.0".to_string(),
+            result: None,
+            error: Some(RpcError::internal_error(format!("Object {} not found", id))),
+            id: request.id,
+        },
+        Err(e) => RpcResponse {
+            jsonrpc: "2.0".to_string(),
+            result: None,
+            error: Some(RpcError::invalid_params(e.to_string())),
+            id: request.id,
+        },
+    }
+}
+
+fn nft_info(info: kanari_move_runtime::NftInfo) -> NftInfo {
+    let nft = info.nft;
+    NftInfo {
+        id: format!("{:#x}", nft.id),
+        owner: info.owner,
+        collection_id: format!("{:#x}", nft.collection),
+        number: nft.number,
+        name: nft.name,
+        description: nft.description,
+        url: nft.url,
+        attributes: nft
+            .attributes
+            .into_iter()
+            .map(|attribute| NftAttribute {
+                key: attribute.key,
+                value: attribute.value,
+            })
+            .collect(),
+        collection: info.collection.map(|collection| CollectionInfo {
+            id: format!("{:#x}", collection.id),
+            creator: format!("{:#x}", collection.creator),
+            name: collection.name,
+            description: collection.description,
+            url: collection.url,
+            max_supply: collection.max_supply,
+            minted: collection.minted,
+            supply: collection.supply,
+            royalty_bps: collection.royalty_bps,
+            royalty_recipient: format!("{:#x}", collection.royalty_recipient),
+        }),
+    }
+}
+
+async fn handle_get_owned_nfts(state: &RpcServerState, request: &RpcRequest) -> RpcResponse {
+    let address: String = match serde_json::from_value(request.params.clone()) {
+        Ok(address) => address,
+        Err(e) => {
+            return RpcResponse {
+                jsonrpc: "2.0".to_string(),
+                result: None,
+                error: Some(RpcError::invalid_params(e.to_string())),
+                id: request.id,
+            };
+        }
+    };
+
+    match state.engine.get_owned_nfts(&address) {
+        Ok(nfts) => {
+            let nfts: Vec<NftInfo> = nfts.into_iter().map(nft_info).collect();
+            RpcResponse {
+                jsonrpc: "2.0".to_string(),
+                result: Some(serde_json::to_value(nfts).unwrap()),
+                error: None,
+                id: request.id,
+            }
+        }
+        Err(e) => RpcResponse {
+            jsonrpc: "2.0".to_string(),
+            result: None,
+            error: Some(RpcError::invalid_params(e.to_string())),
+            id: request.id,
+        },
+    }
+}
+
+async fn handle_get_nft(state: &RpcServerState, request: &RpcRequest) -> RpcResponse {
+    let id: String = match serde_json::from_value(request.params.clone()) {
+        Ok(id) => id,
+        Err(e) => {
+            return RpcResponse {
+                jsonrpc: "2.0".to_string(),
+                result: None,
+                error: Some(RpcError::invalid_params(e.to_string())),
+                id: request.id,
+            };
+        }
+    };
+
+    match state.engine.get_nft(&id) {
+        Ok(Some(nft)) => RpcResponse {
+            jsonrpc: "2.0".to_string(),
+            result: Some(serde_json::to_value(nft_info(nft)).unwrap()),
+            error: None,
+            id: request.id,
+        },
+        Ok(None) => RpcResponse {
+            jsonrpc: "2.0".to_string(),
+            result: None,
+            error: Some(RpcError::internal_error(format!("NFT {} not found", id))),
+            id: request.id,
+        },
+        Err(e) => RpcResponse {
+            jsonrpc: "2.0".to_string(),
+            result: None,
+            error: Some(RpcError::invalid_params(e.to_string())),
+            id: request.id,
+        },
+    }
+}
+
+async fn handle_get_transaction_receipt(
+    state: &RpcServerState,
+    request: &RpcRequest,
+) -> RpcResponse {
+    let tx_hash: String = match serde_json::from_value(request.params.clone()) {
+        Ok(hash) => hash,
+        Err(e) => {
+            return RpcResponse {
+                jsonrpc: "2.0".to_string(),
+                result: None,
+                error: Some(RpcError::invalid_params(e.to_string())),
+                id: request.id,
+            };
+        }
+    };
+
+    match state.engine.get_transaction_receipt(&tx_hash) {
+        Some(receipt) => {
+            let receipt = TransactionReceipt {
+                tx_hash: receipt.tx_hash,
+                block_height: receipt.block_height,
+                success: receipt.success,
+                error_message: receipt.error_message,
+                gas_limit: receipt.gas_limit,
+                gas_price: receipt.gas_price,
+                gas_used: receipt.gas_used,
+                base_fee: receipt.base_fee,
+                effective_gas_price: receipt.effective_gas_price,
+                gas_charged: receipt.gas_charged,
+                gas_burned: receipt.gas_burned,
+                gas_refunded: receipt.gas_refunded,
+            };
+            RpcResponse {
+                jsonrpc: "2.0".to_string(),
+                result: Some(serde_json::to_value(receipt).unwrap()),
+                error: None,
+                id: request.id,
+            }
+        }
+        None => RpcResponse {
+            jsonrpc: "2.0".to_string(),
+            result: None,
+            error: Some(RpcError::internal_error("Transaction receipt not found")),
+            id: request.id,
+        },
+    }
+}
+
+/// Attach the authenticator (signature, signer public key and scheme) sent by the client
+fn signed_transaction(
+    transaction: kanari_move_runtime::Transaction,
+    signature: Option<Vec<u8>>,
+    public_key: Option<String>,
+    scheme: Option<u8>,
+) -> Result<kanari_move_runtime::SignedTransaction, String> {
+    let mut signed_tx = kanari_move_runtime::SignedTransaction::new(transaction);
+    if let Some(signature) = signature {
+        let (Some(public_key), Some(scheme)) = (public_key, scheme) else {
+            return Err("Signed transactions must include public_key and scheme".to_string());
+        };
+        if kanari_move_runtime::CurveType::from_scheme_flag(scheme).is_none() {
+            return Err(format!("Unknown signature scheme: {}", scheme));
+        }
+        signed_tx.authenticator = Some(kanari_move_runtime::TransactionAuthenticator {
+            scheme,
+            public_key,
+            signature,
+        });
+    }
+    Ok(signed_tx)
+}
+
 /// Handle submit transaction request
 async fn handle_submit_transaction(state: &RpcServerState, request: &RpcRequest) -> RpcResponse {
-    use kanari_move_runtime::SignedTransaction;
     use kanari_types::address::Address;
 
     let tx_data: SignedTransactionData = match serde_json::from_value(request.params.clone()) {
@@ -277,14 +672,99 @@ async fn handle_submit_transaction(state: &RpcServerState, request: &RpcRequest)
 
     // Create Transaction based on type
     use kanari_move_runtime::Transaction;
-    let transaction = if let (Some(recipient), Some(amount)) = (recipient, tx_data.amount) {
+    let transaction = if let Some(ref action) = tx_data.staking {
+        let action = match serde_json::from_value(action.clone()) {
+            Ok(action) => action,
+            Err(e) => {
+                return RpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: None,
+                    error: Some(RpcError::invalid_params(format!(
+                        "Invalid staking action: {}",
+                        e
+                    ))),
+                    id: request.id,
+                };
+            }
+        };
+        Transaction::Staking {
+            sender: sender.to_string(),
+            action,
+            gas_limit: tx_data.gas_limit,
+            gas_price: tx_data.gas_price,
+            priority_fee: tx_data.priority_fee,
+            sequence_number: tx_data.sequence_number,
+        }
+    } else if let Some(ref action) = tx_data.governance {
+        let action = match serde_json::from_value(action.clone()) {
+            Ok(action) => action,
+            Err(e) => {
+                return RpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: None,
+                    error: Some(RpcError::invalid_params(format!(
+                        "Invalid governance action: {}",
+                        e
+                    ))),
+                    id: request.id,
+                };
+            }
+        };
+        Transaction::Governance {
+            sender: sender.to_string(),
+            action,
+            gas_limit: tx_data.gas_limit,
+            gas_price: tx_data.gas_price,
+            priority_fee: tx_data.priority_fee,
+            sequence_number: tx_data.sequence_number,
+        }
+    } else if let Some(ref new_key_hex) = tx_data.new_authentication_key {
+        let new_authentication_key = match hex::decode(new_key_hex.trim_start_matches("0x")) {
+            Ok(key) => key,
+            Err(e) => {
+                return RpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: None,
+                    error: Some(RpcError::invalid_params(format!(
+                        "Invalid authentication key: {}",
+                        e
+                    ))),
+                    id: request.id,
+                };
+            }
+        };
+        Transaction::RotateAuthenticationKey {
+            sender: sender.to_string(),
+            new_authentication_key,
+            gas_limit: tx_data.gas_limit,
+            gas_price: tx_data.gas_price,
+            priority_fee: tx_data.priority_fee,
+            sequence_number: tx_data.sequence_number,
+        }
+    } else if let (Some(recipient), Some(amount)) = (recipient, tx_data.amount) {
+        let coin_type = match tx_data.coin_type.as_deref().map(parse_type_tag).transpose() {
+            Ok(coin_type) => coin_type,
+            Err(e) => {
+                return RpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: None,
+                    error: Some(RpcError::invalid_params(format!(
+                        "Invalid coin type: {}",
+                        e
+                    ))),
+                    id: request.id,
+                };
+            }
+        };
         // Regular transfer
         Transaction::Transfer {
             from: sender.to_string(),
             to: recipient.to_string(),
             amount,
+            coin_type,
             gas_limit: tx_data.gas_limit,
             gas_price: tx_data.gas_price,
+            priority_fee: tx_data.priority_fee,
             sequence_number: tx_data.sequence_number,
         }
     } else if recipient.is_none() && tx_data.amount.is_some() {
@@ -310,27 +790,40 @@ async fn handle_submit_transaction(state: &RpcServerState, request: &RpcRequest)
             amount: tx_data.amount.unwrap(),
             gas_limit: tx_data.gas_limit,
             gas_price: tx_data.gas_price,
+            priority_fee: tx_data.priority_fee,
             sequence_number: tx_data.sequence_number,
         }
     } else {
-        error!("Invalid transaction type - only transfers and burns supported currently");
+        error!(
+            "Invalid transaction type - only transfers, burns, key rotations, chain parameter updates, staking and governance supported currently"
+        );
         return RpcResponse {
             jsonrpc: "2.0".to_string(),
             result: None,
             error: Some(RpcError::invalid_params(
-                "Only transfer or burn transactions are supported",
+                "Only transfer, burn, key rotation, chain parameter update, staking or governance transactions are supported",
             )),
             id: request.id,
         };
     };
 
     // Create SignedTransaction
-    let mut signed_tx = SignedTransaction::new(transaction);
-
-    // Set signature if present
-    if let Some(sig) = tx_data.signature {
-        signed_tx.signature = Some(sig);
-    }
+    let signed_tx = match signed_transaction(
+        transaction,
+        tx_data.signature,
+        tx_data.public_key,
+        tx_data.scheme,
+    ) {
+        Ok(signed_tx) => signed_tx,
+        Err(e) => {
+            return RpcResponse {
+                jsonrpc: "2.0".to_string(),
+                result: None,
+                error: Some(RpcError::invalid_params(e)),
+                id: request.id,
+            };
+        }
+    };
 
     // Submit transaction to blockchain
     match state.engine.submit_transaction(signed_tx) {
@@ -365,7 +858,7 @@ async fn handle_submit_transaction(state: &RpcServerState, request: &RpcRequest)
 
 /// Handle publish module request
 async fn handle_publish_module(state: &RpcServerState, request: &RpcRequest) -> RpcResponse {
-    use kanari_move_runtime::{SignedTransaction, Transaction};
+    use kanari_move_runtime::Transaction;
     use kanari_types::address::Address;
 
     let module_data: PublishModuleRequest = match serde_json::from_value(request.params.clone()) {
@@ -405,13 +898,26 @@ async fn handle_publish_module(state: &RpcServerState, request: &RpcRequest) ->
         module_name: module_data.module_name,
         gas_limit: module_data.gas_limit,
         gas_price: module_data.gas_price,
+        priority_fee: module_data.priority_fee,
         sequence_number: module_data.sequence_number,
     };
 
-    let mut signed_tx = SignedTransaction::new(transaction);
-    if let Some(sig) = module_data.signature {
-        signed_tx.signature = Some(sig);
-    }
+    let signed_tx = match signed_transaction(
+        transaction,
+        module_data.signature,
+        module_data.public_key,
+        module_data.scheme,
+    ) {
+        Ok(signed_tx) => signed_tx,
+        Err(e) => {
+            return RpcResponse {
+                jsonrpc: "2.0".to_string(),
+                result: None,
+                error: Some(RpcError::invalid_params(e)),
+                id: request.id,
+            };
+        }
+    };
 
     // Submit to blockchain
     match state.engine.submit_transaction(signed_tx) {
@@ -446,7 +952,7 @@ async fn handle_publish_module(state: &RpcServerState, request: &RpcRequest) ->
 
 /// Handle call function request
 async fn handle_call_function(state: &RpcServerState, request: &RpcRequest) -> RpcResponse {
-    use kanari_move_runtime::{SignedTransaction, Transaction};
+    use kanari_move_runtime::Transaction;
     use kanari_types::address::Address;
 
     let call_data: CallFunctionRequest = match serde_json::from_value(request.params.clone()) {
@@ -499,15 +1005,29 @@ async fn handle_call_function(state: &RpcServerState, request: &RpcRequest) -> R
         function: call_data.function,
         type_args: call_data.type_args,
         args: call_data.args,
+        objects: call_data.objects,
         gas_limit: call_data.gas_limit,
         gas_price: call_data.gas_price,
+        priority_fee: call_data.priority_fee,
         sequence_number: call_data.sequence_number,
     };
 
-    let mut signed_tx = SignedTransaction::new(transaction);
-    if let Some(sig) = call_data.signature {
-        signed_tx.signature = Some(sig);
-    }
+    let signed_tx = match signed_transaction(
+        transaction,
+        call_data.signature,
+        call_data.public_key,
+        call_data.scheme,
+    ) {
+        Ok(signed_tx) => signed_tx,
+        Err(e) => {
+            return RpcResponse {
+                jsonrpc: "2.0".to_string(),
+                result: None,
+                error: Some(RpcError::invalid_params(e)),
+                id: request.id,
+            };
+        }
+    };
 
     // Submit to blockchain
     match state.engine.submit_transaction(signed_tx) {
@@ -568,7 +1088,8 @@ async fn handle_get_contract(state: &RpcServerState, request: &RpcRequest) -> Rp
                 name: info.metadata.name,
                 version: info.metadata.version,
                 author: info.metadata.author,
-                functions: info.abi.functions.iter().map(|f| f.name.clone()).collect(),
+                functions: info.abi.list_functions(),
+                abi: Some(contract_abi(&info.abi)),
             };
             RpcResponse {
                 jsonrpc: "2.0".to_string(),
@@ -586,6 +1107,179 @@ async fn handle_get_contract(state: &RpcServerState, request: &RpcRequest) -> Rp
     }
 }
 
+fn resource_data(resource: kanari_move_runtime::MoveResource) -> ResourceData {
+    ResourceData {
+        struct_tag: resource.struct_tag,
+        bcs: hex::encode(resource.bcs),
+        json: resource.json,
+    }
+}
+
+/// Handle get resources request
+async fn handle_get_resources(state: &RpcServerState, request: &RpcRequest) -> RpcResponse {
+    let address: String = match serde_json::from_value(request.params.clone()) {
+        Ok(addr) => addr,
+        Err(e) => {
+            return RpcResponse {
+                jsonrpc: "2.0".to_string(),
+                result: None,
+                error: Some(RpcError::invalid_params(e.to_string())),
+                id: request.id,
+            };
+        }
+    };
+
+    match state.engine.get_resources(&address) {
+        Ok(resources) => {
+            let resources: Vec<ResourceData> = resources.into_iter().map(resource_data).collect();
+            RpcResponse {
+                jsonrpc: "2.0".to_string(),
+                result: Some(serde_json::to_value(resources).unwrap()),
+                error: None,
+                id: request.id,
+            }
+        }
+        Err(e) => RpcResponse {
+            jsonrpc: "2.0".to_string(),
+            result: None,
+            error: Some(RpcError::invalid_params(e.to_string())),
+            id: request.id,
+        },
+    }
+}
+
+/// Handle get resource request
+async fn handle_get_resource(state: &RpcServerState, request: &RpcRequest) -> RpcResponse {
+    let params: GetResourceRequest = match serde_json::from_value(request.params.clone()) {
+        Ok(p) => p,
+        Err(e) => {
+            return RpcResponse {
+                jsonrpc: "2.0".to_string(),
+                result: None,
+                error: Some(RpcError::invalid_params(e.to_string())),
+                id: request.id,
+            };
+        }
+    };
+
+    match state
+        .engine
+        .get_resource(&params.address, &params.struct_tag)
+    {
+        Ok(Some(resource)) => RpcResponse {
+            jsonrpc: "2.0".to_string(),
+            result: Some(serde_json::to_value(resource_data(resource)).unwrap()),
+            error: None,
+            id: request.id,
+        },
+        Ok(None) => RpcResponse {
+            jsonrpc: "2.0".to_string(),
+            result: None,
+            error: Some(RpcError::internal_error("Resource not found")),
+            id: request.id,
+        },
+        Err(e) => RpcResponse {
+            jsonrpc: "2.0".to_string(),
+            result: None,
+            error: Some(RpcError::invalid_params(e.to_string())),
+            id: request.id,
+        },
+    }
+}
+
+/// Handle view function request
+async fn handle_view(state: &RpcServerState, request: &RpcRequest) -> RpcResponse {
+    let view: ViewFunctionRequest = match serde_json::from_value(request.params.clone()) {
+        Ok(v) => v,
+        Err(e) => {
+            return RpcResponse {
+                jsonrpc: "2.0".to_string(),
+                result: None,
+                error: Some(RpcError::invalid_params(e.to_string())),
+                id: request.id,
+            };
+        }
+    };
+
+    let module = format!("{}::{}", view.package, view.module);
+    match state.engine.view_function(
+        &module,
+        &view.function,
+        &view.type_args,
+        view.args,
+        view.height,
+    ) {
+        Ok(result) => {
+            let result = ViewFunctionResult {
+                height: result.height,
+                return_values: result
+                    .return_values
+                    .into_iter()
+                    .map(|value| ViewReturnValue {
+                        type_tag: value.type_tag,
+                        bcs: hex::encode(value.bcs),
+                        json: value.json,
+                    })
+                    .collect(),
+            };
+            RpcResponse {
+                jsonrpc: "2.0".to_string(),
+                result: Some(serde_json::to_value(result).unwrap()),
+                error: None,
+                id: request.id,
+            }
+        }
+        Err(e) => RpcResponse {
+            jsonrpc: "2.0".to_string(),
+            result: None,
+            error: Some(RpcError::internal_error(format!("View call failed: {}", e))),
+            id: request.id,
+        },
+    }
+}
+
+/// Convert the runtime ABI into its RPC representation
+fn contract_abi(abi: &kanari_move_runtime::ContractABI) -> ContractAbi {
+    ContractAbi {
+        functions: abi
+            .functions
+            .iter()
+            .map(|f| AbiFunction {
+                name: f.name.clone(),
+                is_entry: f.is_entry,
+                visibility: f.visibility.clone(),
+                type_params: f.type_params.clone(),
+                parameters: f
+                    .parameters
+                    .iter()
+                    .map(|p| AbiField {
+                        name: p.name.clone(),
+                        type_name: p.type_name.clone(),
+                    })
+                    .collect(),
+                returns: f.returns.clone(),
+            })
+            .collect(),
+        structs: abi
+            .structs
+            .iter()
+            .map(|s| AbiStruct {
+                name: s.name.clone(),
+                type_params: s.type_params.clone(),
+                abilities: s.abilities.clone(),
+                fields: s
+                    .fields
+                    .iter()
+                    .map(|f| AbiField {
+                        name: f.name.clone(),
+                        type_name: f.type_name.clone(),
+                    })
+                    .collect(),
+            })
+            .collect(),
+    }
+}
+
 /// Handle list contracts request
 async fn handle_list_contracts(state: &RpcServerState, request: &RpcRequest) -> RpcResponse {
     let contracts = state.engine.list_all_contracts();
@@ -596,7 +1290,8 @@ async fn handle_list_contracts(state: &RpcServerState, request: &RpcRequest) ->
             name: info.metadata.name.clone(),
             version: info.metadata.version.clone(),
             author: info.metadata.author.clone(),
-            functions: info.abi.functions.iter().map(|f| f.name.clone()).collect(),
+            functions: info.abi.list_functions(),
+            abi: None,
         })
         .collect();
 
@@ -608,6 +1303,116 @@ async fn handle_list_contracts(state: &RpcServerState, request: &RpcRequest) ->
     }
 }
 
+/// Handle export snapshot request
+async fn handle_export_snapshot(state: &RpcServerState, request: &RpcRequest) -> RpcResponse {
+    let height: u64 = match serde_json::from_value(request.params.clone()) {
+        Ok(h) => h,
+        Err(e) => {
+            return RpcResponse {
+                jsonrpc: "2.0".to_string(),
+                result: None,
+                error: Some(RpcError::invalid_params(e.to_string())),
+                id: request.id,
+            };
+        }
+    };
+
+    let snapshot = match state.engine.export_snapshot(height) {
+        Ok(snapshot) => snapshot,
+        Err(e) => {
+            return RpcResponse {
+                jsonrpc: "2.0".to_string(),
+                result: None,
+                error: Some(RpcError::internal_error(e.to_string())),
+                id: request.id,
+            };
+        }
+    };
+
+    match snapshot.to_bytes() {
+        Ok(bytes) => {
+            let data = SnapshotData {
+                height: snapshot.height(),
+                state_root: hex::encode(&snapshot.header.state_root),
+                data: hex::encode(bytes),
+            };
+            RpcResponse {
+                jsonrpc: "2.0".to_string(),
+                result: Some(serde_json::to_value(data).unwrap()),
+                error: None,
+                id: request.id,
+            }
+        }
+        Err(e) => {
+            error!("Failed to encode snapshot: {}", e);
+            RpcResponse {
+                jsonrpc: "2.0".to_string(),
+                result: None,
+                error: Some(RpcError::internal_error(format!(
+                    "Snapshot export failed: {}",
+                    e
+                ))),
+                id: request.id,
+            }
+        }
+    }
+}
+
+/// Serve a range of canonical blocks for sync; `headers_only` strips transaction bodies
+async fn handle_get_block_range(
+    state: &RpcServerState,
+    request: &RpcRequest,
+    headers_only: bool,
+) -> RpcResponse {
+    let range: BlockRangeRequest = match serde_json::from_value(request.params.clone()) {
+        Ok(r) => r,
+        Err(e) => {
+            return RpcResponse {
+                jsonrpc: "2.0".to_string(),
+                result: None,
+                error: Some(RpcError::invalid_params(e.to_string())),
+                id: request.id,
+            };
+        }
+    };
+
+    let mut blocks = Vec::new();
+    for mut block in state
+        .engine
+        .get_blocks(range.from, range.limit.min(MAX_BLOCK_RANGE))
+    {
+        if headers_only {
+            block.prune_body();
+        }
+        match bcs::to_bytes(&block) {
+            Ok(bytes) => blocks.push(RawBlockData {
+                height: block.header.height,
+                hash: hex::encode(block.hash()),
+                data: hex::encode(bytes),
+            }),
+            Err(e) => {
+                error!("Failed to encode block {}: {}", block.header.height, e);
+                return RpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    result: None,
+                    error: Some(RpcError::internal_error(format!(
+                        "Block encoding failed: {}",
+                        e
+                    ))),
+                    id: request.id,
+                };
+            }
+        }
+    }
+
+    RpcResponse {
+        jsonrpc: "2.0".to_string(),
+        result: Some(serde_json::to_value(blocks).unwrap()),
+        error: None,
+        id: request.id,
+    }
+}
+
 /// Start RPC server
 pub async fn start_server(engine: Arc<BlockchainEngine>, addr: &str) -> Result<()> {
     let state = RpcServerState::new(engine);
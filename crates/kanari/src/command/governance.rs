@@ -0,0 +1,264 @@
+use anyhow::{Context, Result, bail};
+use clap::{Parser, Subcommand, ValueEnum};
+use kanari_crypto::wallet::{get_selected_wallet, load_wallet};
+use kanari_move_runtime::{
+    ChainParameters, GovernanceAction, ProposalAction, SignedTransaction, Transaction,
+};
+use kanari_rpc_api::{ProposalInfo, SignedTransactionData};
+use kanari_rpc_client::RpcClient;
+use std::path::PathBuf;
+
+const MIST_PER_KANARI: f64 = 1_000_000_000.0;
+
+/// DAO governance: submit proposals, vote and inspect their tally
+#[derive(Subcommand)]
+pub enum GovernanceCommand {
+    /// Submit a proposal for token holders to vote on
+    Propose(Propose),
+    /// Vote on an open proposal with your balance plus stake
+    Vote(Vote),
+    /// Show one proposal, or all of them
+    Inspect(Inspect),
+}
+
+impl GovernanceCommand {
+    pub fn execute(self) -> Result<()> {
+        let runtime = tokio::runtime::Runtime::new()?;
+        match self {
+            GovernanceCommand::Propose(propose) => runtime.block_on(propose.execute()),
+            GovernanceCommand::Vote(vote) => runtime.block_on(vote.execute()),
+            GovernanceCommand::Inspect(inspect) => runtime.block_on(inspect.execute()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ProposalKind {
+    /// Pay KANARI from the DAO treasury
+    TreasurySpend,
+    /// Change the chain parameters from the next epoch on
+    Parameters,
+    /// Republish framework modules at 0x2
+    Upgrade,
+}
+
+#[derive(Parser)]
+pub struct Propose {
+    /// Proposer wallet address (optional). If omitted, uses selected wallet in config.
+    #[arg(short, long)]
+    pub from: Option<String>,
+    /// Wallet password
+    #[arg(short, long)]
+    pub password: String,
+    /// What the proposal does
+    #[arg(long, value_enum)]
+    pub kind: ProposalKind,
+    /// Recipient of a treasury spend
+    #[arg(long)]
+    pub recipient: Option<String>,
+    /// Amount of a treasury spend, in Kanari
+    #[arg(long)]
+    pub amount: Option<f64>,
+    /// JSON file with the full set of chain parameters to switch to
+    #[arg(long)]
+    pub parameters_file: Option<PathBuf>,
+    /// Compiled framework modules (.mv) to upgrade to
+    #[arg(long, num_args = 1..)]
+    pub modules: Vec<PathBuf>,
+    /// RPC endpoint
+    #[arg(long = "rpc", default_value = "http://127.0.0.1:3000")]
+    pub rpc_endpoint: String,
+}
+
+impl Propose {
+    async fn execute(self) -> Result<()> {
+        let action = match self.kind {
+            ProposalKind::TreasurySpend => {
+                let recipient = self
+                    .recipient
+                    .context("A treasury spend needs --recipient")?;
+                let amount = self.amount.context("A treasury spend needs --amount")?;
+                ProposalAction::TreasurySpend {
+                    recipient,
+                    amount: (amount * MIST_PER_KANARI).round() as u64,
+                }
+            }
+            ProposalKind::Parameters => {
+                let path = self
+                    .parameters_file
+                    .context("A parameter change needs --parameters-file")?;
+                let json = std::fs::read_to_string(&path)
+                    .with_context(|| format!("Failed to read {}", path.display()))?;
+                let parameters: ChainParameters =
+                    serde_json::from_str(&json).context("Invalid chain parameters")?;
+                ProposalAction::ChangeParameters(parameters)
+            }
+            ProposalKind::Upgrade => {
+                if self.modules.is_empty() {
+                    bail!("A framework upgrade needs --modules");
+                }
+                let modules = self
+                    .modules
+                    .iter()
+                    .map(|path| {
+                        std::fs::read(path)
+                            .with_context(|| format!("Failed to read {}", path.display()))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                ProposalAction::UpgradeFramework { modules }
+            }
+        };
+        action.validate()?;
+
+        println!("🗳️  Submitting governance proposal...");
+        submit(
+            &self.rpc_endpoint,
+            self.from,
+            &self.password,
+            GovernanceAction::Propose(action),
+        )
+        .await
+    }
+}
+
+#[derive(Parser)]
+pub struct Vote {
+    /// Voter wallet address (optional). If omitted, uses selected wallet in config.
+    #[arg(short, long)]
+    pub from: Option<String>,
+    /// Wallet password
+    #[arg(short, long)]
+    pub password: String,
+    /// Proposal id
+    #[arg(long)]
+    pub proposal: u64,
+    /// Vote against the proposal instead of for it
+    #[arg(long)]
+    pub reject: bool,
+    /// RPC endpoint
+    #[arg(long = "rpc", default_value = "http://127.0.0.1:3000")]
+    pub rpc_endpoint: String,
+}
+
+impl Vote {
+    async fn execute(self) -> Result<()> {
+        println!(
+            "🗳️  Voting {} proposal {}...",
+            if self.reject { "against" } else { "for" },
+            self.proposal
+        );
+        submit(
+            &self.rpc_endpoint,
+            self.from,
+            &self.password,
+            GovernanceAction::Vote {
+                proposal_id: self.proposal,
+                approve: !self.reject,
+            },
+        )
+        .await
+    }
+}
+
+#[derive(Parser)]
+pub struct Inspect {
+    /// Proposal id; all proposals are listed when omitted
+    #[arg(long)]
+    pub proposal: Option<u64>,
+    /// RPC endpoint
+    #[arg(long = "rpc", default_value = "http://127.0.0.1:3000")]
+    pub rpc_endpoint: String,
+}
+
+impl Inspect {
+    async fn execute(self) -> Result<()> {
+        let client = RpcClient::new(&self.rpc_endpoint);
+        let proposals = match self.proposal {
+            Some(id) => vec![client.get_proposal(id).await?],
+            None => client.get_proposals().await?,
+        };
+        if proposals.is_empty() {
+            println!("No proposals");
+        }
+        for proposal in &proposals {
+            print_proposal(proposal);
+        }
+        Ok(())
+    }
+}
+
+fn print_proposal(proposal: &ProposalInfo) {
+    println!("📜 Proposal {} [{}]", proposal.id, proposal.status);
+    println!("  Proposer: {}", proposal.proposer);
+    println!("  Action: {}", proposal.action);
+    println!("  Voting ends at height: {}", proposal.voting_end_height);
+    println!(
+        "  Yes: {:.9} KANARI  No: {:.9} KANARI  Voters: {}",
+        proposal.yes_votes as f64 / MIST_PER_KANARI,
+        proposal.no_votes as f64 / MIST_PER_KANARI,
+        proposal.voters.len()
+    );
+}
+
+/// Sign a governance transaction with the sender's wallet and submit it
+async fn submit(
+    rpc_endpoint: &str,
+    from: Option<String>,
+    password: &str,
+    action: GovernanceAction,
+) -> Result<()> {
+    let from_addr = match from {
+        Some(from) => from,
+        None => get_selected_wallet().ok_or_else(|| {
+            anyhow::anyhow!(
+                "No sender provided and no selected wallet set. Use --from or run `kanari load-wallet` to select one."
+            )
+        })?,
+    };
+    let wallet = load_wallet(&from_addr, password).context("Failed to load sender wallet")?;
+
+    let client = RpcClient::new(rpc_endpoint);
+    let account = client
+        .get_account(&from_addr)
+        .await
+        .context("Failed to get sender account")?;
+
+    let mut tx = Transaction::new_governance(from_addr.clone(), action.clone());
+    if let Transaction::Governance {
+        gas_limit,
+        sequence_number,
+        ..
+    } = &mut tx
+    {
+        *gas_limit = crate::gas_limit_for(wallet.curve_type);
+        *sequence_number = account.sequence_number;
+    }
+
+    let mut signed_tx = SignedTransaction::new(tx);
+    signed_tx
+        .sign(&wallet.private_key, wallet.curve_type)
+        .context("Failed to sign transaction")?;
+
+    let authenticator = signed_tx.authenticator.as_ref();
+    let tx_data = SignedTransactionData {
+        sender: from_addr,
+        recipient: None,
+        amount: None,
+        coin_type: None,
+        gas_limit: signed_tx.transaction.gas_limit(),
+        gas_price: signed_tx.transaction.gas_price(),
+        priority_fee: signed_tx.transaction.priority_fee(),
+        sequence_number: account.sequence_number,
+        signature: authenticator.map(|a| a.signature.clone()),
+        public_key: authenticator.map(|a| a.public_key.clone()),
+        scheme: authenticator.map(|a| a.scheme),
+        new_authentication_key: None,
+        staking: None,
+        governance: Some(serde_json::to_value(&action)?),
+    };
+
+    let status = client.submit_transaction(tx_data).await?;
+    println!("  ✅ Transaction submitted: {}", status.hash);
+    println!("  Status: {}", status.status);
+    Ok(())
+}
@@ -1 +1,3 @@
+pub mod governance;
 pub mod move_cli;
+pub mod nft;
@@ -35,6 +35,11 @@ pub struct Call {
     #[clap(long = "args")]
     pub args: Vec<String>,
 
+    /// IDs of the objects passed as the function's leading parameters.
+    /// Addresses must be hex strings.
+    #[clap(long = "objects")]
+    pub objects: Vec<String>,
+
     /// Sender/Caller address (from wallet)
     #[clap(long = "sender")]
     pub sender: String,
@@ -47,10 +52,14 @@ pub struct Call {
     #[clap(long = "gas-limit", default_value = "200000")]
     pub gas_limit: u64,
 
-    /// Gas price in Mist
+    /// Maximum fee per gas unit in Mist, covering the base fee and the tip
     #[clap(long = "gas-price", default_value = "1000")]
     pub gas_price: u64,
 
+    /// Tip per gas unit in Mist for the block producer
+    #[clap(long = "priority-fee", default_value = "0")]
+    pub priority_fee: u64,
+
     /// Skip signature (for testing)
     #[clap(long = "skip-signature")]
     pub skip_signature: bool,
@@ -197,7 +206,7 @@ impl Call {
         }
 
         // Sign transaction if wallet is available
-        let signature = if let Some(ref wallet) = wallet {
+        let signed_tx = if let Some(ref wallet) = wallet {
             // Create proper Transaction to match server's expectation
             use kanari_move_runtime::Transaction;
             let transaction = Transaction::ExecuteFunction {
@@ -206,19 +215,19 @@ impl Call {
                 function: self.function.clone(),
                 type_args: self.type_args.clone(),
                 args: _args.clone(),
+                objects: self.objects.clone(),
                 gas_limit: self.gas_limit,
                 gas_price: self.gas_price,
+                priority_fee: self.priority_fee,
                 sequence_number: seq_num,
             };
 
-            // Get transaction hash (same way server does it)
-            let tx_hash = transaction.hash();
-
-            // Sign with wallet
-            match kanari_crypto::sign_message(&wallet.private_key, &tx_hash, wallet.curve_type) {
-                Ok(sig) => {
+            // Sign with wallet; this also stamps the signature scheme into the transaction
+            let mut signed_tx = kanari_move_runtime::SignedTransaction::new(transaction);
+            match signed_tx.sign(&wallet.private_key, wallet.curve_type) {
+                Ok(()) => {
                     println!("   🔐 Transaction signed with {} key", wallet.curve_type);
-                    Some(sig)
+                    Some(signed_tx)
                 }
                 Err(e) => {
                     eprintln!("   ⚠️  Failed to sign transaction: {}", e);
@@ -233,6 +242,8 @@ impl Call {
         use kanari_rpc_api::{CallFunctionRequest, RpcRequest, RpcResponse, methods};
         use reqwest::blocking::Client;
 
+        let authenticator = signed_tx.as_ref().and_then(|tx| tx.authenticator.as_ref());
+
         let call_req = CallFunctionRequest {
             sender: sender_normalized,
             package: package_normalized,
@@ -240,10 +251,14 @@ impl Call {
             function: self.function.clone(),
             type_args: self.type_args.clone(),
             args: _args.clone(),
+            objects: self.objects.clone(),
             gas_limit: self.gas_limit,
             gas_price: self.gas_price,
+            priority_fee: self.priority_fee,
             sequence_number: seq_num,
-            signature,
+            signature: authenticator.map(|a| a.signature.clone()),
+            public_key: authenticator.map(|a| a.public_key.clone()),
+            scheme: authenticator.map(|a| a.scheme),
         };
 
         let rpc_request = RpcRequest {
@@ -350,8 +365,10 @@ mod tests {
             sender: "0x1".to_string(),
             type_args: vec![],
             args: vec![],
+            objects: vec![],
             gas_limit: 200000,
             gas_price: 1000,
+            priority_fee: 0,
             password: None,
             skip_signature: true,
             rpc_endpoint: "http://localhost:3000".to_string(),
@@ -380,8 +397,10 @@ mod tests {
             sender: "0x1".to_string(),
             type_args: vec![],
             args: vec![],
+            objects: vec![],
             gas_limit: 200000,
             gas_price: 1000,
+            priority_fee: 0,
             password: None,
             skip_signature: true,
             rpc_endpoint: "http://localhost:3000".to_string(),
@@ -62,9 +62,28 @@ impl MoveCommand {
                 let system_addr = AccountAddress::from_hex_literal("0x2").unwrap();
                 let crypto_natives =
                     kanari_crypto::move_natives::all_natives(system_addr).into_iter();
+                let object_natives =
+                    kanari_move_runtime::natives::object_natives(system_addr).into_iter();
+                let field_natives =
+                    kanari_move_runtime::natives::dynamic_field_natives(system_addr).into_iter();
+                let event_natives =
+                    kanari_move_runtime::natives::event_natives(system_addr).into_iter();
+                let random_natives =
+                    kanari_move_runtime::natives::random_natives(system_addr).into_iter();
+
+                // Object, dynamic field, event and random natives keep their state in session extensions
+                move_unit_test::extensions::set_extension_hook(Box::new(
+                    kanari_move_runtime::natives::add_unit_test_extensions,
+                ));
 
                 // Merge all natives and pass into test runner
-                let natives = std_natives.chain(crypto_natives).collect();
+                let natives = std_natives
+                    .chain(crypto_natives)
+                    .chain(object_natives)
+                    .chain(field_natives)
+                    .chain(event_natives)
+                    .chain(random_natives)
+                    .collect();
                 t.execute(None, config, natives, None)
             }
             MoveCommand::Docgen(d) => {
@@ -21,10 +21,14 @@ pub struct Publish {
     #[clap(long = "gas-limit", default_value = "1000000")]
     pub gas_limit: u64,
 
-    /// Gas price in Mist
+    /// Maximum fee per gas unit in Mist, covering the base fee and the tip
     #[clap(long = "gas-price", default_value = "1000")]
     pub gas_price: u64,
 
+    /// Tip per gas unit in Mist for the block producer
+    #[clap(long = "priority-fee", default_value = "0")]
+    pub priority_fee: u64,
+
     /// Account address publishing the module (from wallet)
     #[clap(long = "sender")]
     pub sender: String,
@@ -196,7 +200,7 @@ impl Publish {
             }
 
             // Sign transaction if wallet is available
-            let signature = if let Some(ref wallet) = _wallet {
+            let signed_tx = if let Some(ref wallet) = _wallet {
                 // Create proper Transaction to match server's expectation
                 use kanari_move_runtime::Transaction;
                 let transaction = Transaction::PublishModule {
@@ -205,18 +209,16 @@ impl Publish {
                     module_name: module_name.clone(),
                     gas_limit: self.gas_limit,
                     gas_price: self.gas_price,
+                    priority_fee: self.priority_fee,
                     sequence_number: seq_num,
                 };
 
-                // Get transaction hash (same way server does it)
-                let tx_hash = transaction.hash();
-
-                // Sign with wallet
-                match kanari_crypto::sign_message(&wallet.private_key, &tx_hash, wallet.curve_type)
-                {
-                    Ok(sig) => {
+                // Sign with wallet; this also stamps the signature scheme into the transaction
+                let mut signed_tx = kanari_move_runtime::SignedTransaction::new(transaction);
+                match signed_tx.sign(&wallet.private_key, wallet.curve_type) {
+                    Ok(()) => {
                         println!("     🔐 Transaction signed with {} key", wallet.curve_type);
-                        Some(sig)
+                        Some(signed_tx)
                     }
                     Err(e) => {
                         eprintln!("     ⚠️  Failed to sign transaction: {}", e);
@@ -227,14 +229,19 @@ impl Publish {
                 None
             };
 
+            let authenticator = signed_tx.as_ref().and_then(|tx| tx.authenticator.as_ref());
+
             let pub_req = PublishModuleRequest {
                 sender: sender_normalized.clone(),
                 module_bytes: module_bytecode.clone(),
                 module_name: module_name.clone(),
                 gas_limit: self.gas_limit,
                 gas_price: self.gas_price,
+                priority_fee: self.priority_fee,
                 sequence_number: seq_num,
-                signature,
+                signature: authenticator.map(|a| a.signature.clone()),
+                public_key: authenticator.map(|a| a.public_key.clone()),
+                scheme: authenticator.map(|a| a.scheme),
             };
 
             let rpc_request = RpcRequest {
@@ -0,0 +1,116 @@
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+use kanari_crypto::wallet::get_selected_wallet;
+use kanari_rpc_api::NftInfo;
+use kanari_rpc_client::RpcClient;
+
+/// NFTs minted with `0x2::nft`: list an address's NFTs and view their metadata
+#[derive(Subcommand)]
+pub enum NftCommand {
+    /// List the NFTs an address owns
+    List(List),
+    /// Show an NFT's metadata, attributes and collection
+    Show(Show),
+}
+
+impl NftCommand {
+    pub fn execute(self) -> Result<()> {
+        let runtime = tokio::runtime::Runtime::new()?;
+        match self {
+            NftCommand::List(list) => runtime.block_on(list.execute()),
+            NftCommand::Show(show) => runtime.block_on(show.execute()),
+        }
+    }
+}
+
+#[derive(Parser)]
+pub struct List {
+    /// Owner address (optional). If omitted, uses selected wallet in config.
+    #[arg(short, long)]
+    pub address: Option<String>,
+    /// RPC endpoint
+    #[arg(long = "rpc", default_value = "http://127.0.0.1:3000")]
+    pub rpc_endpoint: String,
+}
+
+impl List {
+    async fn execute(self) -> Result<()> {
+        let address = match self.address {
+            Some(address) => address,
+            None => get_selected_wallet().ok_or_else(|| {
+                anyhow::anyhow!(
+                    "No address provided and no selected wallet set. Use --address or run `kanari load-wallet` to select one."
+                )
+            })?,
+        };
+        let nfts = RpcClient::new(&self.rpc_endpoint)
+            .get_owned_nfts(&address)
+            .await?;
+        println!("🖼️  NFTs owned by {}", address);
+        if nfts.is_empty() {
+            println!("  No NFTs");
+        }
+        for nft in &nfts {
+            let collection = nft
+                .collection
+                .as_ref()
+                .map_or(nft.collection_id.as_str(), |c| c.name.as_str());
+            println!(
+                "  {} #{} [{}]  {}",
+                nft.name, nft.number, collection, nft.id
+            );
+        }
+        Ok(())
+    }
+}
+
+#[derive(Parser)]
+pub struct Show {
+    /// NFT object id
+    #[arg(long)]
+    pub id: String,
+    /// RPC endpoint
+    #[arg(long = "rpc", default_value = "http://127.0.0.1:3000")]
+    pub rpc_endpoint: String,
+}
+
+impl Show {
+    async fn execute(self) -> Result<()> {
+        let nft = RpcClient::new(&self.rpc_endpoint).get_nft(&self.id).await?;
+        print_nft(&nft);
+        Ok(())
+    }
+}
+
+fn print_nft(nft: &NftInfo) {
+    println!("🖼️  {} #{}", nft.name, nft.number);
+    println!("  Id: {}", nft.id);
+    println!("  Owner: {}", nft.owner.as_deref().unwrap_or("(none)"));
+    println!("  Description: {}", nft.description);
+    println!("  Url: {}", nft.url);
+    for attribute in &nft.attributes {
+        println!("  {}: {}", attribute.key, attribute.value);
+    }
+    match &nft.collection {
+        Some(collection) => {
+            println!("  Collection: {} ({})", collection.name, collection.id);
+            println!("    Creator: {}", collection.creator);
+            println!("    Description: {}", collection.description);
+            println!("    Url: {}", collection.url);
+            let cap = match collection.max_supply {
+                0 => "uncapped".to_string(),
+                max_supply => format!("of {}", max_supply),
+            };
+            println!(
+                "    Supply: {} ({} minted, {})",
+                collection.supply, collection.minted, cap
+            );
+            println!(
+                "    Royalty: {:.2}% to {}",
+                collection.royalty_bps as f64 / 100.0,
+                collection.royalty_recipient
+            );
+        }
+        None => println!("  Collection: {}", nft.collection_id),
+    }
+}
@@ -11,7 +11,7 @@ use kanari_types::module_registry::ModuleRegistry;
 use std::str::FromStr;
 
 pub mod command;
-use command::move_cli;
+use command::{governance, move_cli, nft};
 
 /// Kanari - A Move-based money transfer system
 #[derive(Parser)]
@@ -67,12 +67,15 @@ enum Commands {
         /// Recipient address
         #[arg(short, long)]
         to: String,
-        /// Amount in Kanari (will be converted to Mist)
+        /// Amount in Kanari (will be converted to Mist), or in whole units of `--coin-type`
         #[arg(short, long)]
         amount: f64,
         /// Wallet password
         #[arg(short, long)]
         password: String,
+        /// Coin to send, e.g. 0x42::usd::USD (defaults to KANARI)
+        #[arg(long)]
+        coin_type: Option<String>,
     },
     /// Burn Kanari tokens from a wallet (remove from total supply)
     Burn {
@@ -101,6 +104,25 @@ enum Commands {
         #[command(subcommand)]
         command: move_cli::MoveCommand,
     },
+    /// Propose, vote on and inspect DAO governance proposals
+    Governance {
+        #[command(subcommand)]
+        command: governance::GovernanceCommand,
+    },
+    /// List NFTs and view their metadata
+    Nft {
+        #[command(subcommand)]
+        command: nft::NftCommand,
+    },
+}
+
+/// Default gas limit for wallet transactions, raised to cover the signature
+/// size of post-quantum schemes
+fn gas_limit_for(curve_type: CurveType) -> u64 {
+    let signature = kanari_move_runtime::GasOperation::SignatureVerification {
+        signature_size: curve_type.signature_size(),
+    };
+    100_000 + signature.gas_units()
 }
 
 fn main() -> Result<()> {
@@ -209,6 +231,7 @@ fn main() -> Result<()> {
             to,
             amount,
             password,
+            coin_type,
         } => {
             runtime.block_on(async {
                 // Load sender wallet to verify ownership
@@ -221,18 +244,6 @@ fn main() -> Result<()> {
                         let wallet =
                             load_wallet(&from_addr, &password).context("Failed to load sender wallet")?;
 
-                println!("💸 Transferring Kanari tokens...");
-                println!("  From: {}", from_addr);
-                println!("  To: {}", to);
-                println!("  Amount: {} KANARI", amount);
-
-                // Convert Kanari to Mist (1 KANARI = 10^9 Mist)
-                // Use rounding to avoid floating-point truncation artifacts
-                const MIST_PER_KANARI: f64 = 1_000_000_000.0;
-                let amount_mist_f = amount * MIST_PER_KANARI;
-                let amount_mist = amount_mist_f.round() as u64;
-                println!("  Amount (Mist): {}", amount_mist);
-
                 // Connect to RPC server instead of creating engine
                 let client = RpcClient::new("http://127.0.0.1:3000");
 
@@ -246,6 +257,40 @@ fn main() -> Result<()> {
                     }
                 }
 
+                // Other coins are scaled by the decimals in their metadata
+                let (symbol, decimals) = match &coin_type {
+                    None => ("KANARI".to_string(), kanari_move_runtime::KANARI_DECIMALS),
+                    Some(coin_type) => {
+                        let balances = client
+                            .get_balances(&from_addr)
+                            .await
+                            .context("Failed to get sender balances")?;
+                        let held = balances
+                            .into_iter()
+                            .find(|b| b.coin_type == *coin_type)
+                            .ok_or_else(|| anyhow::anyhow!("{} holds no {}", from_addr, coin_type))?;
+                        (
+                            held.symbol.unwrap_or_else(|| coin_type.clone()),
+                            held.decimals.unwrap_or(0),
+                        )
+                    }
+                };
+
+                println!("💸 Transferring {} tokens...", symbol);
+                println!("  From: {}", from_addr);
+                println!("  To: {}", to);
+                println!("  Amount: {} {}", amount, symbol);
+
+                // Convert to base units (1 KANARI = 10^9 Mist)
+                // Use rounding to avoid floating-point truncation artifacts
+                let amount_mist = (amount * 10f64.powi(decimals as i32)).round() as u64;
+                println!("  Amount (base units): {}", amount_mist);
+                let parsed_coin_type = coin_type
+                    .as_deref()
+                    .map(move_core_types::parser::parse_type_tag)
+                    .transpose()
+                    .context("Invalid coin type")?;
+
                 // Get account to get sequence number before creating the transaction
                 let account = client
                     .get_account(&from_addr)
@@ -257,8 +302,10 @@ fn main() -> Result<()> {
                     from: from_addr.clone(),
                     to: to.clone(),
                     amount: amount_mist,
-                    gas_limit: 100_000,
+                    coin_type: parsed_coin_type,
+                    gas_limit: gas_limit_for(wallet.curve_type),
                     gas_price: 1000,
+                    priority_fee: 0,
                     sequence_number: account.sequence_number,
                 };
 
@@ -276,14 +323,22 @@ fn main() -> Result<()> {
 
                 // Convert SignedTransaction to RPC format
                 use kanari_rpc_api::SignedTransactionData;
+                let authenticator = signed_tx.authenticator.as_ref();
                 let tx_data = SignedTransactionData {
                     sender: from_addr.clone(),
                     recipient: Some(to.clone()),
                     amount: Some(amount_mist),
+                    coin_type: coin_type.clone(),
                     gas_limit: signed_tx.transaction.gas_limit(),
                     gas_price: signed_tx.transaction.gas_price(),
+                    priority_fee: signed_tx.transaction.priority_fee(),
                     sequence_number: account.sequence_number,
-                    signature: signed_tx.signature.clone(),
+                    signature: authenticator.map(|a| a.signature.clone()),
+                    public_key: authenticator.map(|a| a.public_key.clone()),
+                    scheme: authenticator.map(|a| a.scheme),
+                    new_authentication_key: None,
+                                staking: None,
+                    governance: None,
                 };
 
                 // Submit transaction via RPC
@@ -356,8 +411,9 @@ fn main() -> Result<()> {
                 let tx = kanari_move_runtime::Transaction::Burn {
                     from: from_addr.clone(),
                     amount: amount_mist,
-                    gas_limit: 100_000,
+                    gas_limit: gas_limit_for(wallet.curve_type),
                     gas_price: 1000,
+                    priority_fee: 0,
                     sequence_number: account.sequence_number,
                 };
 
@@ -374,14 +430,22 @@ fn main() -> Result<()> {
                 println!("  📤 Submitting burn transaction to node...");
 
                 use kanari_rpc_api::SignedTransactionData;
+                let authenticator = signed_tx.authenticator.as_ref();
                 let tx_data = SignedTransactionData {
                     sender: from_addr.clone(),
                     recipient: None,
                     amount: Some(amount_mist),
+                    coin_type: None,
                     gas_limit: signed_tx.transaction.gas_limit(),
                     gas_price: signed_tx.transaction.gas_price(),
+                    priority_fee: signed_tx.transaction.priority_fee(),
                     sequence_number: account.sequence_number,
-                    signature: signed_tx.signature.clone(),
+                    signature: authenticator.map(|a| a.signature.clone()),
+                    public_key: authenticator.map(|a| a.public_key.clone()),
+                    scheme: authenticator.map(|a| a.scheme),
+                    new_authentication_key: None,
+                                staking: None,
+                    governance: None,
                 };
 
                 match client.submit_transaction(tx_data).await {
@@ -415,6 +479,22 @@ fn main() -> Result<()> {
                         println!("💰 Balance for {}", address);
                         println!("  Kanari: {:.9} KANARI", balance_kanari);
                         println!("  Mist: {} Mist", account.balance);
+                        // KANARI is shown above; list any other coins held
+                        let kanari = kanari_move_runtime::kanari_coin_type().to_string();
+                        for coin in account
+                            .balances
+                            .iter()
+                            .filter(|coin| coin.coin_type != kanari)
+                        {
+                            let decimals = coin.decimals.unwrap_or(0);
+                            println!(
+                                "  {}: {:.*} ({})",
+                                coin.symbol.as_deref().unwrap_or(&coin.coin_type),
+                                decimals as usize,
+                                coin.balance as f64 / 10f64.powi(decimals as i32),
+                                coin.coin_type
+                            );
+                        }
                         println!("  Sequence: {}", account.sequence_number);
                         if !account.modules.is_empty() {
                             println!("  Modules deployed: {}", account.modules.len());
@@ -492,5 +572,21 @@ fn main() -> Result<()> {
 
             Ok(())
         }
+
+        Commands::Governance { command } => {
+            command
+                .execute()
+                .context("Failed to execute governance subcommand")?;
+
+            Ok(())
+        }
+
+        Commands::Nft { command } => {
+            command
+                .execute()
+                .context("Failed to execute nft subcommand")?;
+
+            Ok(())
+        }
     }
 }